@@ -1,7 +1,26 @@
 use std::time::{SystemTime, UNIX_EPOCH};
 
+use chrono::NaiveDateTime;
+use serde_json::Value;
+
 use crate::url::Url;
 
+// Formats of dates found within Expires attributes of Set-Cookie headers
+const COOKIE_DATE_FORMATS: &[&str] = &[
+    "%a, %d %b %Y %H:%M:%S GMT",
+    "%a, %d-%b-%Y %H:%M:%S GMT",
+    "%A, %d-%b-%y %H:%M:%S GMT",
+    "%a %b %e %H:%M:%S %Y",
+];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CookieSameSite {
+    Strict,
+    Lax,
+    None,
+}
+
+#[derive(Clone, Debug)]
 pub struct Cookie {
     pub domain: String,
     pub include_subdomains: bool,
@@ -10,11 +29,14 @@ pub struct Cookie {
     pub expires: u64,
     pub name: String,
     pub value: String,
+    pub http_only: bool,
+    pub same_site: CookieSameSite,
 }
 
 #[derive(Debug)]
 pub enum CookieFileContentsParseError {
     InvalidHeader,
+    InvalidJson,
 }
 
 impl Cookie {
@@ -52,8 +74,7 @@ impl Cookie {
                 if let Some(url_host) = url.host_str() {
                     if self.domain.starts_with(".") && self.include_subdomains {
                         if !url_host.to_lowercase().ends_with(&self.domain)
-                            && !url_host
-                                .eq_ignore_ascii_case(&self.domain[1..self.domain.len() - 1])
+                            && !url_host.eq_ignore_ascii_case(&self.domain[1..])
                         {
                             return false;
                         }
@@ -64,9 +85,11 @@ impl Cookie {
                     return false;
                 }
 
-                // Check path
-                if !url.path().eq_ignore_ascii_case(&self.path)
-                    && !url.path().starts_with(&self.path)
+                // Check path (cookie path has to be either the whole path or its directory)
+                if url.path() != self.path
+                    && !(url.path().starts_with(&self.path)
+                        && (self.path.ends_with('/')
+                            || url.path()[self.path.len()..].starts_with('/')))
                 {
                     return false;
                 }
@@ -92,6 +115,12 @@ pub fn parse_cookie_file_contents(
                 return Err(CookieFileContentsParseError::InvalidHeader);
             }
         } else {
+            // HttpOnly cookies are written out as comments by curl and browser extensions
+            let (line, http_only) = match line.strip_prefix("#HttpOnly_") {
+                Some(line) => (line, true),
+                None => (line, false),
+            };
+
             // Ignore comment lines
             if line.starts_with("#") {
                 continue;
@@ -110,9 +139,258 @@ pub fn parse_cookie_file_contents(
                 expires: fields.next().unwrap().parse::<u64>().unwrap(),
                 name: fields.next().unwrap().to_string(),
                 value: fields.next().unwrap().to_string(),
+                http_only,
+                same_site: CookieSameSite::None,
             });
         }
     }
 
     Ok(cookies)
 }
+
+pub fn parse_cookie_header(cookie_header_value: &str, domain: &str) -> Vec<Cookie> {
+    let cookie_header_value: &str = match cookie_header_value.split_once(':') {
+        Some((header_name, value)) if header_name.trim().eq_ignore_ascii_case("cookie") => value,
+        _ => cookie_header_value,
+    };
+
+    cookie_header_value
+        .split(';')
+        .filter_map(|pair| {
+            let (name, value) = pair.split_once('=')?;
+            if name.trim().is_empty() {
+                return None;
+            }
+
+            Some(Cookie {
+                domain: domain.to_lowercase(),
+                include_subdomains: false,
+                path: "/".to_string(),
+                https_only: false,
+                expires: 0,
+                name: name.trim().to_string(),
+                value: value.trim().to_string(),
+                http_only: false,
+                same_site: CookieSameSite::None,
+            })
+        })
+        .collect()
+}
+
+pub fn parse_json_cookie_file_contents(
+    cookie_file_contents: &str,
+    domain: &str,
+) -> Result<Vec<Cookie>, CookieFileContentsParseError> {
+    let json: Value = serde_json::from_str(cookie_file_contents)
+        .map_err(|_| CookieFileContentsParseError::InvalidJson)?;
+
+    // Browser extensions and Puppeteer export arrays of cookies,
+    // Playwright and HTTPie wrap them into objects
+    let cookies: Vec<Option<Cookie>> = match json.get("cookies").unwrap_or(&json) {
+        Value::Array(items) => items
+            .iter()
+            .map(|item| parse_json_cookie(item, item["name"].as_str()?, domain))
+            .collect(),
+        // Older HTTPie session files have cookies keyed by their names
+        Value::Object(items) if json.get("cookies").is_some() => items
+            .iter()
+            .map(|(name, item)| parse_json_cookie(item, name, domain))
+            .collect(),
+        _ => return Err(CookieFileContentsParseError::InvalidJson),
+    };
+
+    Ok(cookies.into_iter().flatten().collect())
+}
+
+fn parse_json_cookie(item: &Value, name: &str, default_domain: &str) -> Option<Cookie> {
+    if !item.is_object() || name.is_empty() {
+        return None;
+    }
+
+    let domain: String = item["domain"]
+        .as_str()
+        .filter(|domain| !domain.is_empty())
+        .unwrap_or(default_domain)
+        .to_lowercase();
+    let include_subdomains: bool = match item["hostOnly"].as_bool() {
+        Some(host_only) => !host_only,
+        None => domain.starts_with('.'),
+    };
+    let expires: f64 = if item["session"].as_bool().unwrap_or(false) {
+        0.
+    } else {
+        item["expirationDate"]
+            .as_f64()
+            .or(item["expires"].as_f64())
+            .unwrap_or(0.)
+    };
+
+    Some(Cookie {
+        domain: if include_subdomains && !domain.starts_with('.') {
+            format!(".{}", domain)
+        } else {
+            domain
+        },
+        include_subdomains,
+        path: item["path"].as_str().unwrap_or("/").to_string(),
+        https_only: item["secure"].as_bool().unwrap_or(false),
+        // Session cookies have their expiration dates set to -1 by Puppeteer
+        expires: if expires > 0. { expires as u64 } else { 0 },
+        name: name.to_string(),
+        value: item["value"].as_str().unwrap_or_default().to_string(),
+        http_only: item["httpOnly"].as_bool().unwrap_or(false),
+        same_site: match item["sameSite"]
+            .as_str()
+            .unwrap_or_default()
+            .to_lowercase()
+            .as_str()
+        {
+            "strict" => CookieSameSite::Strict,
+            "lax" => CookieSameSite::Lax,
+            _ => CookieSameSite::None,
+        },
+    })
+}
+
+pub fn parse_set_cookie_header(set_cookie_header_value: &str, url: &Url) -> Option<Cookie> {
+    // Spec: https://httpwg.org/specs/rfc6265.html#set-cookie
+    let url_host: String = url.host_str()?.to_lowercase();
+    let mut parts = set_cookie_header_value.split(';');
+    let (name, value) = parts.next()?.split_once('=')?;
+    let name: &str = name.trim();
+    if name.is_empty() {
+        return None;
+    }
+
+    let mut cookie = Cookie {
+        domain: url_host.clone(),
+        include_subdomains: false,
+        path: get_default_cookie_path(url),
+        https_only: false,
+        expires: 0,
+        name: name.to_string(),
+        value: value.trim().to_string(),
+        http_only: false,
+        // Browsers treat cookies without SameSite attribute as Lax
+        same_site: CookieSameSite::Lax,
+    };
+    let mut max_age: Option<i64> = None;
+
+    for attr in parts {
+        let (attr_name, attr_value) = attr.split_once('=').unwrap_or((attr, ""));
+        let attr_value: &str = attr_value.trim();
+
+        match attr_name.trim().to_lowercase().as_str() {
+            "domain" => {
+                let domain: String = attr_value.trim_start_matches('.').to_lowercase();
+                if domain.is_empty() {
+                    continue;
+                }
+
+                // Servers can only set cookies for their own domains
+                if url_host != domain && !url_host.ends_with(&format!(".{}", domain)) {
+                    return None;
+                }
+
+                // Same as for sites, top-level domains and IP addresses can't be shared
+                let is_ip_address: bool =
+                    url_host.parse::<std::net::IpAddr>().is_ok() || url_host.starts_with('[');
+                if is_ip_address || !domain.contains('.') {
+                    if url_host == domain {
+                        continue;
+                    }
+                    return None;
+                }
+
+                cookie.domain = format!(".{}", domain);
+                cookie.include_subdomains = true;
+            }
+            "expires" => {
+                if let Some(date) = COOKIE_DATE_FORMATS
+                    .iter()
+                    .find_map(|format| NaiveDateTime::parse_from_str(attr_value, format).ok())
+                {
+                    // Dates before the epoch mean the cookie has already expired
+                    cookie.expires = date.and_utc().timestamp().max(1) as u64;
+                }
+            }
+            "max-age" => {
+                max_age = attr_value.parse::<i64>().ok().or(max_age);
+            }
+            "path" if attr_value.starts_with('/') => {
+                cookie.path = attr_value.to_string();
+            }
+            "secure" => {
+                cookie.https_only = true;
+            }
+            "httponly" => {
+                cookie.http_only = true;
+            }
+            "samesite" => {
+                cookie.same_site = match attr_value.to_lowercase().as_str() {
+                    "strict" => CookieSameSite::Strict,
+                    "none" => CookieSameSite::None,
+                    _ => CookieSameSite::Lax,
+                };
+            }
+            _ => {}
+        }
+    }
+
+    // Max-Age takes precedence over Expires
+    if let Some(max_age) = max_age {
+        let now: u64 = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("Time went backwards")
+            .as_secs();
+
+        cookie.expires = if max_age > 0 { now + max_age as u64 } else { 1 };
+    }
+
+    Some(cookie)
+}
+
+fn get_default_cookie_path(url: &Url) -> String {
+    match url.path().rfind('/') {
+        Some(0) | None => "/".to_string(),
+        Some(i) => url.path()[..i].to_string(),
+    }
+}
+
+pub fn store_cookie(cookies: &mut Vec<Cookie>, cookie: Cookie) {
+    // New cookies replace existing ones which have the same name, domain and path
+    cookies.retain(|c| {
+        c.name != cookie.name
+            || !c.domain.eq_ignore_ascii_case(&cookie.domain)
+            || c.path != cookie.path
+    });
+
+    // Expired cookies only remove existing ones
+    if !cookie.is_expired() {
+        cookies.push(cookie);
+    }
+}
+
+pub fn write_cookie_file_contents(cookies: &[Cookie]) -> String {
+    let mut cookie_file_contents: String = "# Netscape HTTP Cookie File\n".to_string();
+
+    for cookie in cookies.iter().filter(|cookie| !cookie.is_expired()) {
+        cookie_file_contents.push_str(&format!(
+            "{}{}\t{}\t{}\t{}\t{}\t{}\t{}\n",
+            if cookie.http_only { "#HttpOnly_" } else { "" },
+            cookie.domain,
+            if cookie.include_subdomains {
+                "TRUE"
+            } else {
+                "FALSE"
+            },
+            cookie.path,
+            if cookie.https_only { "TRUE" } else { "FALSE" },
+            cookie.expires,
+            cookie.name,
+            cookie.value,
+        ));
+    }
+
+    cookie_file_contents
+}
@@ -6,14 +6,17 @@ use std::io::{self, Write};
 use std::path::Path;
 
 use chrono::{SecondsFormat, Utc};
-use encoding_rs::Encoding;
+use encoding_rs::{Encoding, UTF_8};
 use markup5ever_rcdom::RcDom;
 use url::Url;
 
+use crate::cookies::write_cookie_file_contents;
+use crate::filters::{ResourceType, UrlRule};
 use crate::html::{
     add_favicon, create_metadata_tag, get_base_url, get_charset, get_robots, get_title,
     has_favicon, html_to_dom, serialize_document, set_base_url, set_charset, set_robots, walk,
 };
+use crate::reader::extract_article;
 use crate::session::Session;
 use crate::url::{create_data_url, resolve_url};
 
@@ -52,15 +55,32 @@ pub enum MonolithOutputFormat {
     // HAR,
 }
 
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub enum IntegrityMismatchPolicy {
+    #[default]
+    Drop,
+    Embed,
+    KeepRemote,
+}
+
 #[derive(Default)]
 pub struct MonolithOptions {
     pub base_url: Option<String>,
     pub blacklist_domains: bool,
+    pub cookie_jar: Option<String>,
     pub domains: Option<Vec<String>>,
     pub encoding: Option<String>,
+    pub excluded_media: Option<Vec<String>>,
+    pub frames_as_srcdoc: bool,
     pub ignore_errors: bool,
+    pub inline_stylesheets: bool,
     pub insecure: bool,
+    pub integrity_mismatch: IntegrityMismatchPolicy,
     pub isolate: bool,
+    pub keep_integrity: bool,
+    pub lazy_load_attrs: Option<Vec<String>>,
+    pub max_frame_depth: Option<usize>,
+    pub minify_css: bool,
     pub no_audio: bool,
     pub no_css: bool,
     pub no_fonts: bool,
@@ -68,11 +88,19 @@ pub struct MonolithOptions {
     pub no_images: bool,
     pub no_js: bool,
     pub no_metadata: bool,
+    pub no_tracking: bool,
+    pub no_unused_css: bool,
+    pub no_unused_font_faces: bool,
     pub no_video: bool,
     pub output_format: MonolithOutputFormat,
+    pub pixel_density: Option<f64>,
+    pub reader_mode: bool,
+    pub sanitize: bool,
     pub silent: bool,
+    pub subset_fonts: bool,
     pub timeout: u64,
     pub unwrap_noscript: bool,
+    pub url_rules: Vec<UrlRule>,
     pub user_agent: Option<String>,
     pub exp_css_prop_assets: bool,
 }
@@ -107,6 +135,7 @@ const PLAINTEXT_MEDIA_TYPES: &[&str] = &[
     "application/javascript",          // .js
     "application/json",                // .json
     "application/ld+json",             // .jsonld
+    "application/manifest+json",       // .webmanifest
     "application/x-sh",                // .sh
     "application/xhtml+xml",           // .xhtml
     "application/xml",                 // .xml
@@ -201,7 +230,20 @@ pub fn create_monolithic_document_from_data(
     }
 
     // Traverse through the document and embed remote assets
+    session.enter_frame(&base_url);
+    session.enter_encoding(Encoding::for_label(document_encoding.as_bytes()).unwrap_or(UTF_8));
     walk(&mut session, &base_url, &dom.document);
+    session.exit_encoding();
+    session.exit_frame();
+
+    // Keep only the main content of the document
+    if session.options.reader_mode {
+        dom = extract_article(&dom.document);
+        // Unless it's known, the template's own charset is kept
+        if !document_encoding.is_empty() {
+            dom = set_charset(dom, document_encoding.clone());
+        }
+    }
 
     // Update or add new BASE element to reroute network requests and hash-links
     if let Some(new_base_url) = session.options.base_url.clone() {
@@ -210,6 +252,7 @@ pub fn create_monolithic_document_from_data(
 
     // Request and embed /favicon.ico (unless it's already linked in the document)
     if !session.options.no_images
+        && !session.options.reader_mode
         && (base_url.scheme() == "http" || base_url.scheme() == "https")
         && (input_target.is_some()
             && (input_target.as_ref().unwrap().starts_with("http:")
@@ -218,7 +261,11 @@ pub fn create_monolithic_document_from_data(
     {
         let favicon_ico_url: Url = resolve_url(&base_url, "/favicon.ico");
 
-        match session.retrieve_asset(/*&target_url, */ &base_url, &favicon_ico_url) {
+        match session.retrieve_asset(
+            /*&target_url, */ &base_url,
+            &favicon_ico_url,
+            Some(ResourceType::Image),
+        ) {
             Ok((data, final_url, media_type, charset)) => {
                 let favicon_data_url: Url =
                     create_data_url(&media_type, &charset, &data, &final_url);
@@ -230,6 +277,9 @@ pub fn create_monolithic_document_from_data(
         }
     }
 
+    // Save cookies, including ones which got set while retrieving assets
+    save_cookie_jar(&session)?;
+
     // Append noindex META-tag
     let meta_robots_content_value = get_robots(&dom.document).unwrap_or_default();
     if meta_robots_content_value.trim().is_empty() || meta_robots_content_value != "none" {
@@ -375,12 +425,13 @@ pub fn create_monolithic_document(
         || target_url.scheme() == "https"
         || target_url.scheme() == "data"
     {
-        match session.retrieve_asset(&target_url, &target_url) {
+        match session.retrieve_asset(&target_url, &target_url, None) {
             Ok((retrieved_data, final_url, media_type, charset)) => {
                 if !media_type.eq_ignore_ascii_case("text/html")
                     && !media_type.eq_ignore_ascii_case("application/xhtml+xml")
                 {
                     // Provide output as text (without processing it, the way browsers do)
+                    save_cookie_jar(&session)?;
                     return Ok((retrieved_data, None));
                 }
 
@@ -449,7 +500,9 @@ pub fn detect_media_type_by_file_name(filename: &str) -> String {
             "swf" => "application/x-shockwave-flash",
             "tif" | "tiff" => "image/tiff",
             "txt" => "text/plain",
+            "vtt" => "text/vtt",
             "wav" => "audio/wav",
+            "webmanifest" => "application/manifest+json",
             "webp" => "image/webp",
             "woff" => "font/woff",
             "woff2" => "font/woff2",
@@ -506,6 +559,47 @@ pub fn format_output_path(
         .to_string()
 }
 
+pub fn get_file_extension_by_media_type(media_type: &str) -> &'static str {
+    match media_type.trim().to_lowercase().as_str() {
+        "application/json" => "json",
+        "application/ld+json" => "jsonld",
+        "application/manifest+json" => "webmanifest",
+        "application/pdf" => "pdf",
+        "application/x-shockwave-flash" => "swf",
+        "application/xhtml+xml" => "xhtml",
+        "application/javascript" | "text/javascript" => "js",
+        "audio/flac" | "audio/x-flac" => "flac",
+        "audio/mpeg" => "mp3",
+        "audio/ogg" => "ogg",
+        "audio/wav" => "wav",
+        "font/otf" => "otf",
+        "font/ttf" => "ttf",
+        "font/woff" | "application/font-woff" => "woff",
+        "font/woff2" => "woff2",
+        "image/avif" => "avif",
+        "image/bmp" => "bmp",
+        "image/gif" => "gif",
+        "image/jpeg" => "jpg",
+        "image/png" => "png",
+        "image/svg+xml" => "svg",
+        "image/tiff" => "tiff",
+        "image/webp" => "webp",
+        "image/x-icon" | "image/vnd.microsoft.icon" => "ico",
+        "text/css" => "css",
+        "text/html" => "html",
+        "text/plain" => "txt",
+        "text/vtt" => "vtt",
+        "text/xml" | "application/xml" => "xml",
+        "video/avi" => "avi",
+        "video/mp4" => "mp4",
+        "video/mpeg" => "mpeg",
+        "video/ogg" => "ogv",
+        "video/quicktime" => "mov",
+        "video/webm" => "webm",
+        _ => "",
+    }
+}
+
 pub fn is_plaintext_media_type(media_type: &str) -> bool {
     media_type.to_lowercase().as_str().starts_with("text/")
         || PLAINTEXT_MEDIA_TYPES.contains(&media_type.to_lowercase().as_str())
@@ -570,3 +664,21 @@ pub fn print_info_message(text: &str) {
 
     if handle.write_all(format!("{}\n", &text).as_bytes()).is_ok() {}
 }
+
+pub fn save_cookie_jar(session: &Session) -> Result<(), MonolithError> {
+    if let Some(cookie_jar) = &session.options.cookie_jar {
+        if fs::write(
+            cookie_jar,
+            write_cookie_file_contents(session.get_cookies()),
+        )
+        .is_err()
+        {
+            return Err(MonolithError::new(&format!(
+                "could not write cookies to \"{}\"",
+                cookie_jar
+            )));
+        }
+    }
+
+    Ok(())
+}
@@ -1,13 +1,20 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
+use encoding_rs::{Encoding, UTF_16BE, UTF_16LE, UTF_8};
+use markup5ever_rcdom::{Handle, NodeData};
 use sha2::{Digest, Sha256};
 
 use cssparser::{
-    serialize_identifier, serialize_string, ParseError, Parser, ParserInput, SourcePosition, Token,
+    serialize_identifier, serialize_string, Delimiter, ParseError, Parser, ParserInput,
+    SourcePosition, Token, UnicodeRange,
 };
 
+use crate::core::MonolithOptions;
+use crate::filters::ResourceType;
+use crate::font::subset_font;
+use crate::html::{get_node_attr, get_node_name, get_parent_element, get_previous_sibling_element};
 use crate::session::Session;
-use crate::url::{create_data_url, resolve_url, Url, EMPTY_IMAGE_DATA_URL};
+use crate::url::{create_data_url, parse_data_url, resolve_url, Url, EMPTY_IMAGE_DATA_URL};
 
 const CSS_PROPS_WITH_IMAGE_URLS: &[&str] = &[
     // Universal
@@ -29,12 +36,38 @@ const CSS_PROPS_WITH_IMAGE_URLS: &[&str] = &[
     "suffix",
     "symbols",
 ];
+// Font formats in order of preference (smaller and better supported ones go first)
+const FONT_FORMATS: &[&[&str]] = &[
+    &["woff2"],
+    &["woff"],
+    &["truetype", "opentype", "ttf", "otf"],
+    &["embedded-opentype", "svg", "eot"],
+];
+// At-rules which contain other rules (unused rules get removed from within them too)
+const CSS_GROUPING_AT_RULES: &[&str] = &["container", "document", "layer", "media", "supports"];
+const DEFAULT_EXCLUDED_MEDIA: &[&str] = &["print"];
+const IMAGE_SET_FUNCTIONS: &[&str] = &["image-set", "-webkit-image-set"];
+const IMAGE_SET_MEDIA_TYPES: &[&str] = &[
+    "image/avif",
+    "image/bmp",
+    "image/gif",
+    "image/jpeg",
+    "image/png",
+    "image/svg+xml",
+    "image/webp",
+    "image/x-icon",
+];
 
 pub fn embed_css(session: &mut Session, document_url: &Url, css: &str) -> String {
     let mut input = ParserInput::new(css);
     let mut parser = Parser::new(&mut input);
     let mut assets: HashMap<String, CssPropAsset> = HashMap::new();
 
+    // Keep track of characters added by stylesheets (to know which glyphs are needed)
+    if session.options.no_unused_font_faces || session.options.subset_fonts {
+        get_css_chars(css, &mut session.document_chars);
+    }
+
     let mut out = process_css(session, document_url, &mut parser, "", "", "", &mut assets).unwrap();
 
     if !assets.is_empty() {
@@ -65,6 +98,15 @@ pub fn is_image_url_prop(prop_name: &str) -> bool {
         .any(|p| prop_name.eq_ignore_ascii_case(p))
 }
 
+fn get_url_resource_type(in_font_face: bool) -> ResourceType {
+    // Only src descriptors of @font-face rules refer to fonts
+    if in_font_face {
+        ResourceType::Font
+    } else {
+        ResourceType::Image
+    }
+}
+
 pub fn hash_url(url: String) -> String {
     let mut hasher = Sha256::new();
     hasher.update(url.as_str().as_bytes());
@@ -72,11 +114,524 @@ pub fn hash_url(url: String) -> String {
     return format!("{:x}", hash);
 }
 
+pub fn font_format_rank(format: &str) -> usize {
+    let format: &str = format.trim_end_matches("-variations");
+
+    FONT_FORMATS
+        .iter()
+        .position(|formats| formats.iter().any(|f| format.eq_ignore_ascii_case(f)))
+        .unwrap_or(FONT_FORMATS.len())
+}
+
+pub fn select_font_face_sources(src: &str) -> String {
+    let mut input = ParserInput::new(src);
+    let mut parser = Parser::new(&mut input);
+    let sources: Vec<(&str, bool, usize)> =
+        parser.parse_comma_separated_ignoring_errors(|parser| -> Result<_, ParseError<()>> {
+            let source_start: SourcePosition = parser.position();
+            let mut is_local: bool = false;
+            let mut url: String = "".to_string();
+            let mut format: Option<String> = None;
+
+            while let Ok(token) = parser.next() {
+                match token.clone() {
+                    Token::UnquotedUrl(value) => url = value.to_string(),
+                    Token::Function(ref name) if name.eq_ignore_ascii_case("local") => {
+                        is_local = true;
+                    }
+                    Token::Function(ref name)
+                        if name.eq_ignore_ascii_case("url") || name.eq_ignore_ascii_case("src") =>
+                    {
+                        url = parser
+                            .parse_nested_block(|parser| -> Result<String, ParseError<()>> {
+                                Ok(parser.expect_string()?.to_string())
+                            })
+                            .unwrap_or_default();
+                    }
+                    Token::Function(ref name) if name.eq_ignore_ascii_case("format") => {
+                        format = parser
+                            .parse_nested_block(|parser| -> Result<String, ParseError<()>> {
+                                Ok(parser.expect_ident_or_string()?.to_string())
+                            })
+                            .ok();
+                    }
+                    _ => {}
+                }
+            }
+
+            // Fall back to file extension when there's no format() hint
+            let format: String = format.unwrap_or_else(|| {
+                let path: &str = url.split(['?', '#']).next().unwrap_or_default();
+                match path.rsplit_once('.') {
+                    Some((_, extension)) if !extension.contains('/') => extension.to_string(),
+                    _ => "".to_string(),
+                }
+            });
+
+            Ok((
+                parser.slice_from(source_start).trim(),
+                is_local,
+                font_format_rank(&format),
+            ))
+        });
+
+    let best_rank: Option<usize> = sources
+        .iter()
+        .filter(|(_, is_local, _)| !is_local)
+        .map(|(_, _, rank)| *rank)
+        .min();
+    let mut is_best_source_selected: bool = false;
+
+    // Keep local() sources intact, they don't contribute anything to the size of the document
+    sources
+        .iter()
+        .filter(|(_, is_local, rank)| {
+            if *is_local {
+                true
+            } else if Some(*rank) == best_rank && !is_best_source_selected {
+                is_best_source_selected = true;
+                true
+            } else {
+                false
+            }
+        })
+        .map(|(source, _, _)| *source)
+        .collect::<Vec<&str>>()
+        .join(", ")
+}
+
+pub fn is_unicode_range_used(unicode_range: &str, chars: &HashSet<char>) -> bool {
+    let mut input = ParserInput::new(unicode_range);
+    let mut parser = Parser::new(&mut input);
+    let ranges: Vec<UnicodeRange> = parser.parse_comma_separated_ignoring_errors(|parser| {
+        UnicodeRange::parse(parser).map_err(ParseError::<()>::from)
+    });
+
+    // Consider malformed ranges used, it's up to the browser to make sense of them
+    ranges.is_empty()
+        || chars.iter().any(|c| {
+            ranges
+                .iter()
+                .any(|range| (range.start..=range.end).contains(&(*c as u32)))
+        })
+}
+
+pub fn get_excluded_media(options: &MonolithOptions) -> Vec<String> {
+    match &options.excluded_media {
+        Some(excluded_media) => excluded_media.clone(),
+        None if options.minify_css => DEFAULT_EXCLUDED_MEDIA
+            .iter()
+            .map(|m| m.to_string())
+            .collect(),
+        None => vec![],
+    }
+}
+
+pub fn is_excluded_media_query(media_query_list: &str, excluded_media: &[String]) -> bool {
+    let mut input = ParserInput::new(media_query_list);
+    let mut parser = Parser::new(&mut input);
+    let media_queries: Vec<bool> =
+        parser.parse_comma_separated_ignoring_errors(|parser| -> Result<bool, ParseError<()>> {
+            let is_excluded: bool = parser
+                .try_parse(|parser| -> Result<bool, ParseError<()>> {
+                    let mut media_type: String = parser.expect_ident()?.to_string();
+
+                    // Negated media queries target everything but given media type
+                    if media_type.eq_ignore_ascii_case("not") {
+                        return Ok(false);
+                    }
+                    if media_type.eq_ignore_ascii_case("only") {
+                        media_type = parser.expect_ident()?.to_string();
+                    }
+
+                    Ok(excluded_media
+                        .iter()
+                        .any(|m| media_type.eq_ignore_ascii_case(m.trim())))
+                })
+                .unwrap_or(false);
+            while parser.next().is_ok() {}
+
+            Ok(is_excluded)
+        });
+
+    // Media queries without media type don't get excluded
+    !media_queries.is_empty() && media_queries.iter().all(|is_excluded| *is_excluded)
+}
+
+fn minify_block(options: &MonolithOptions, block_css: String) -> String {
+    // Semicolons which precede closing curly brackets aren't needed
+    match block_css.strip_suffix(';') {
+        Some(block_css) if options.minify_css => block_css.to_string(),
+        _ => block_css,
+    }
+}
+
+pub fn is_image_set_function(func_name: &str) -> bool {
+    IMAGE_SET_FUNCTIONS
+        .iter()
+        .any(|f| func_name.eq_ignore_ascii_case(f))
+}
+
+pub fn select_image_set_candidate(image_set: &str, pixel_density: f64) -> Option<&str> {
+    let mut input = ParserInput::new(image_set);
+    let mut parser = Parser::new(&mut input);
+    let mut candidates: Vec<(&str, f64)> = vec![];
+    let mut candidate_start: SourcePosition = parser.position();
+    let mut resolution: f64 = 1.0;
+    let mut is_supported: bool = true;
+
+    loop {
+        let token_start: SourcePosition = parser.position();
+        let token: Option<Token> = parser.next().ok().cloned();
+
+        match token {
+            Some(Token::Dimension {
+                value, ref unit, ..
+            }) => {
+                if let Some(dppx) = parse_resolution(value as f64, unit) {
+                    resolution = dppx;
+                }
+            }
+            Some(Token::Function(ref name)) if name.eq_ignore_ascii_case("type") => {
+                let media_type: String = parser
+                    .parse_nested_block(|parser| -> Result<String, ParseError<()>> {
+                        Ok(parser.expect_string()?.to_string())
+                    })
+                    .unwrap_or_default();
+                is_supported = IMAGE_SET_MEDIA_TYPES
+                    .iter()
+                    .any(|t| media_type.trim().eq_ignore_ascii_case(t));
+            }
+            Some(Token::Comma) | None => {
+                let candidate: &str = parser.slice(candidate_start..token_start).trim();
+                if is_supported && !candidate.is_empty() {
+                    candidates.push((candidate, resolution));
+                }
+
+                if token.is_none() {
+                    break;
+                }
+
+                candidate_start = parser.position();
+                resolution = 1.0;
+                is_supported = true;
+            }
+            _ => {}
+        }
+    }
+
+    // Pick the lowest resolution that satisfies given pixel density, or the highest one available
+    let mut selected: Option<(&str, f64)> = None;
+    for (candidate, resolution) in candidates {
+        selected = match selected {
+            Some((_, selected_resolution))
+                if (selected_resolution < pixel_density && resolution > selected_resolution)
+                    || (resolution >= pixel_density && resolution < selected_resolution) =>
+            {
+                Some((candidate, resolution))
+            }
+            Some(_) => selected,
+            None => Some((candidate, resolution)),
+        };
+    }
+
+    selected.map(|(candidate, _)| candidate)
+}
+
+fn parse_resolution(value: f64, unit: &str) -> Option<f64> {
+    if unit.eq_ignore_ascii_case("x") || unit.eq_ignore_ascii_case("dppx") {
+        Some(value)
+    } else if unit.eq_ignore_ascii_case("dpi") {
+        Some(value / 96.0)
+    } else if unit.eq_ignore_ascii_case("dpcm") {
+        Some(value * 2.54 / 96.0)
+    } else {
+        None
+    }
+}
+
+pub struct CssImport {
+    pub url: String,
+    pub layer: Option<String>,
+    pub supports: Option<String>,
+    pub media: String,
+}
+
+pub fn parse_import_prelude(prelude: &str) -> Option<CssImport> {
+    let mut input = ParserInput::new(prelude);
+    let mut parser = Parser::new(&mut input);
+
+    let url: String = match parser.next().ok()?.clone() {
+        Token::QuotedString(url) | Token::UnquotedUrl(url) => url.to_string(),
+        Token::Function(ref name) if name.eq_ignore_ascii_case("url") => parser
+            .parse_nested_block(|parser| -> Result<String, ParseError<()>> {
+                Ok(parser.expect_string()?.to_string())
+            })
+            .ok()?,
+        _ => return None,
+    };
+
+    // layer, layer(name)
+    let mut layer: Option<String> = None;
+    let state = parser.state();
+    match parser.next().cloned() {
+        Ok(Token::Ident(ref name)) if name.eq_ignore_ascii_case("layer") => {
+            layer = Some("".to_string());
+        }
+        Ok(Token::Function(ref name)) if name.eq_ignore_ascii_case("layer") => {
+            layer = Some(parse_block_contents(&mut parser));
+        }
+        _ => parser.reset(&state),
+    }
+
+    // supports(condition)
+    let mut supports: Option<String> = None;
+    let state = parser.state();
+    match parser.next().cloned() {
+        Ok(Token::Function(ref name)) if name.eq_ignore_ascii_case("supports") => {
+            supports = Some(parse_block_contents(&mut parser));
+        }
+        _ => parser.reset(&state),
+    }
+
+    // Whatever comes after is the media query list
+    let media_start: SourcePosition = parser.position();
+    while parser.next().is_ok() {}
+
+    Some(CssImport {
+        url,
+        layer,
+        supports,
+        media: parser.slice_from(media_start).trim().to_string(),
+    })
+}
+
+fn parse_block_contents(parser: &mut Parser) -> String {
+    parser
+        .parse_nested_block(|parser| -> Result<String, ParseError<()>> {
+            let block_start: SourcePosition = parser.position();
+            while parser.next().is_ok() {}
+            Ok(parser.slice_from(block_start).trim().to_string())
+        })
+        .unwrap_or_default()
+}
+
+pub fn decode_stylesheet(
+    data: &[u8],
+    charset: &str,
+    environment_encoding: &'static Encoding,
+) -> (String, &'static Encoding) {
+    // Spec: https://drafts.csswg.org/css-syntax/#input-byte-stream
+    // (US-ASCII is what gets assumed when Content-Type has no charset parameter)
+    let protocol_encoding: Option<&'static Encoding> =
+        if charset.trim().eq_ignore_ascii_case("US-ASCII") {
+            None
+        } else {
+            Encoding::for_label(charset.trim().as_bytes())
+        };
+    let encoding: &'static Encoding = protocol_encoding
+        .or_else(|| get_charset_rule_encoding(data))
+        .unwrap_or(environment_encoding);
+
+    // Byte order mark overrides any other way of specifying encoding
+    let (stylesheet, encoding, _) = encoding.decode(data);
+
+    // The stylesheet is no longer in the encoding its @charset rule refers to
+    let stylesheet: &str = match stylesheet.strip_prefix("@charset \"") {
+        Some(rest) if rest.find("\";").is_some() => &rest[rest.find("\";").unwrap() + 2..],
+        _ => &stylesheet,
+    };
+
+    (stylesheet.to_string(), encoding)
+}
+
+fn get_charset_rule_encoding(data: &[u8]) -> Option<&'static Encoding> {
+    let prefix: &[u8] = b"@charset \"";
+    let data: &[u8] = &data[..data.len().min(1024)];
+
+    if !data.starts_with(prefix) {
+        return None;
+    }
+
+    let label_length: usize = data[prefix.len()..]
+        .windows(2)
+        .position(|window| window == b"\";")?;
+    let encoding: &'static Encoding =
+        Encoding::for_label(&data[prefix.len()..prefix.len() + label_length])?;
+
+    // Stylesheets which declare themselves as UTF-16 can't really be UTF-16
+    if encoding == UTF_16BE || encoding == UTF_16LE {
+        Some(UTF_8)
+    } else {
+        Some(encoding)
+    }
+}
+
+pub fn embed_stylesheet(
+    session: &mut Session,
+    stylesheet_url: &Url,
+    data: &[u8],
+    charset: &str,
+) -> String {
+    let (stylesheet, encoding) =
+        decode_stylesheet(data, charset, session.get_environment_encoding());
+
+    // Stylesheets imported by this one fall back to its encoding
+    session.enter_encoding(encoding);
+    let css: String = embed_css(session, stylesheet_url, &stylesheet);
+    session.exit_encoding();
+
+    css
+}
+
+pub fn create_stylesheet_data_url(media_type: &str, css: &str, final_asset_url: &Url) -> Url {
+    // Embedded stylesheets are always UTF-8, no need to label them unless they contain non-ASCII text
+    let charset: &str = if css.is_ascii() { "" } else { "UTF-8" };
+
+    create_data_url(media_type, charset, css.as_bytes(), final_asset_url)
+}
+
+fn inline_import(session: &mut Session, document_url: &Url, import: &CssImport) -> Option<String> {
+    let import_url: Url = resolve_url(document_url, &import.url);
+    let (data, final_url, _media_type, charset) = session
+        .retrieve_asset(document_url, &import_url, Some(ResourceType::Stylesheet))
+        .ok()?;
+
+    let mut css: String = embed_stylesheet(session, &final_url, &data, &charset);
+
+    // Wrap imported rules into blocks which retain conditions of the @import rule
+    if let Some(layer) = &import.layer {
+        if layer.is_empty() {
+            css = format!("@layer {{\n{}\n}}", css);
+        } else {
+            css = format!("@layer {} {{\n{}\n}}", layer, css);
+        }
+    }
+    if let Some(supports) = &import.supports {
+        css = format!("@supports ({}) {{\n{}\n}}", supports, css);
+    }
+    if !import.media.is_empty() {
+        css = format!("@media {} {{\n{}\n}}", import.media, css);
+    }
+
+    Some(css)
+}
+
 pub struct CssPropAsset {
     prop_name: String,
     data_url: String,
 }
 
+fn subset_font_asset(
+    session: &Session,
+    prop_name: &str,
+    data: Vec<u8>,
+    media_type: String,
+) -> (Vec<u8>, String) {
+    // Only fonts referenced by src descriptors of @font-face rules get subset
+    if session.options.subset_fonts && prop_name.eq_ignore_ascii_case("src") {
+        if let Some((subset_data, subset_media_type)) = subset_font(&data, &session.document_chars)
+        {
+            return (subset_data, subset_media_type.to_string());
+        }
+    }
+
+    (data, media_type)
+}
+
+fn process_font_face<'a>(
+    session: &mut Session,
+    document_url: &Url,
+    parser: &mut Parser,
+    css_assets: &mut HashMap<String, CssPropAsset>,
+) -> Result<Option<String>, ParseError<'a, String>> {
+    let block_start: SourcePosition = parser.position();
+    let mut src_declarations: Vec<[SourcePosition; 4]> = vec![];
+    let mut unicode_range: Option<&str> = None;
+    let mut descriptor_name: String = "".to_string();
+    let mut descriptor_start: SourcePosition = block_start;
+
+    loop {
+        let token_start: SourcePosition = parser.position();
+        let token: Token = match parser.next() {
+            Ok(token) => token.clone(),
+            Err(_) => break,
+        };
+
+        match token {
+            Token::Ident(ref name) => {
+                descriptor_name = name.to_string();
+                descriptor_start = token_start;
+            }
+            Token::Colon => {
+                let value_start: SourcePosition = parser.position();
+                let _ = parser.parse_until_before(
+                    Delimiter::Semicolon,
+                    |parser| -> Result<(), ParseError<()>> {
+                        while parser.next().is_ok() {}
+                        Ok(())
+                    },
+                );
+                let value_end: SourcePosition = parser.position();
+                let _ = parser.expect_semicolon();
+
+                if descriptor_name.eq_ignore_ascii_case("src") {
+                    src_declarations.push([
+                        descriptor_start,
+                        value_start,
+                        value_end,
+                        parser.position(),
+                    ]);
+                } else if descriptor_name.eq_ignore_ascii_case("unicode-range") {
+                    unicode_range = Some(parser.slice(value_start..value_end));
+                }
+                descriptor_name.clear();
+            }
+            _ => {}
+        }
+    }
+
+    // Drop font faces which don't cover any characters found in the document
+    if session.options.no_unused_font_faces {
+        if let Some(unicode_range) = unicode_range {
+            if !is_unicode_range_used(unicode_range, &session.document_chars) {
+                return Ok(None);
+            }
+        }
+    }
+
+    // Only the last src descriptor matters, preceding ones are fallbacks for legacy browsers
+    let mut font_face_css: String = "".to_string();
+    let mut copied_until: SourcePosition = block_start;
+    if let Some((last_src, other_srcs)) = src_declarations.split_last() {
+        for [src_start, _, _, src_end] in other_srcs {
+            font_face_css.push_str(parser.slice(copied_until..*src_start));
+            copied_until = *src_end;
+        }
+
+        let [_, value_start, value_end, _] = *last_src;
+        let value: &str = parser.slice(value_start..value_end);
+        font_face_css.push_str(parser.slice(copied_until..value_start));
+        font_face_css.push_str(&value[..value.len() - value.trim_start().len()]);
+        font_face_css.push_str(&select_font_face_sources(value));
+        copied_until = value_end;
+    }
+    font_face_css.push_str(parser.slice_from(copied_until));
+
+    let mut input = ParserInput::new(&font_face_css);
+    let mut parser = Parser::new(&mut input);
+    process_css(
+        session,
+        document_url,
+        &mut parser,
+        "font-face",
+        "",
+        "",
+        css_assets,
+    )
+    .map(Some)
+}
+
 pub fn process_css<'a>(
     session: &mut Session,
     document_url: &Url,
@@ -91,6 +646,9 @@ pub fn process_css<'a>(
 
     let mut curr_rule: String = rule_name.to_string();
     let mut curr_prop: String = prop_name.to_string();
+    // Descriptors such as src must not make nested values forget they're within @font-face
+    let in_font_face: bool = rule_name == "font-face";
+    let mut at_rule_offset: usize = 0;
     let mut token: &Token;
     let mut token_offset: SourcePosition;
 
@@ -103,6 +661,28 @@ pub fn process_css<'a>(
             }
         };
 
+        if session.options.minify_css {
+            match *token {
+                // Drop comments and collapse whitespace
+                Token::Comment(_) => continue,
+                Token::WhiteSpace(_) => {
+                    if !result.is_empty()
+                        && !result.ends_with(['{', '}', ';', ',', '>', '(', ':', ' '])
+                    {
+                        result.push(' ');
+                    }
+                    continue;
+                }
+                // Whitespace preceding these isn't needed either
+                Token::Semicolon | Token::Comma | Token::CurlyBracketBlock | Token::Delim('>')
+                    if result.ends_with(' ') =>
+                {
+                    result.pop();
+                }
+                _ => {}
+            }
+        }
+
         match *token {
             Token::Comment(_) => {
                 let token_slice = parser.slice_from(token_offset);
@@ -116,6 +696,24 @@ pub fn process_css<'a>(
                     continue;
                 }
 
+                if token == &Token::CurlyBracketBlock && curr_rule == "font-face" {
+                    let font_face_css: Option<String> = parser
+                        .parse_nested_block(|parser| {
+                            process_font_face(session, document_url, parser, css_assets)
+                        })
+                        .unwrap();
+
+                    match font_face_css {
+                        Some(font_face_css) => {
+                            result.push('{');
+                            result.push_str(&minify_block(&session.options, font_face_css));
+                            result.push('}');
+                        }
+                        None => result.truncate(at_rule_offset),
+                    }
+                    continue;
+                }
+
                 let closure: &str;
                 if token == &Token::ParenthesisBlock {
                     result.push('(');
@@ -141,7 +739,11 @@ pub fn process_css<'a>(
                         )
                     })
                     .unwrap();
-                result.push_str(block_css.as_str());
+                if closure == "}" {
+                    result.push_str(&minify_block(&session.options, block_css));
+                } else {
+                    result.push_str(block_css.as_str());
+                }
 
                 result.push_str(closure);
             }
@@ -158,9 +760,32 @@ pub fn process_css<'a>(
             Token::WhiteSpace(value) => {
                 result.push_str(value);
             }
+            // U+0000-00FF, U+4??...
+            Token::Ident(ref value)
+                if value.eq_ignore_ascii_case("u")
+                    && curr_prop.eq_ignore_ascii_case("unicode-range") =>
+            {
+                // Keep unicode ranges as they are, re-serializing their tokens would break them
+                loop {
+                    let state = parser.state();
+                    match parser.next_including_whitespace_and_comments() {
+                        Ok(Token::Number { .. })
+                        | Ok(Token::Dimension { .. })
+                        | Ok(Token::Delim(_))
+                        | Ok(Token::Ident(_)) => {}
+                        _ => {
+                            parser.reset(&state);
+                            break;
+                        }
+                    }
+                }
+                result.push_str(parser.slice_from(token_offset));
+            }
             // div...
             Token::Ident(ref value) => {
-                curr_rule = "".to_string();
+                if !in_font_face {
+                    curr_rule = "".to_string();
+                }
                 curr_prop = value.to_string();
                 result.push_str(&format_ident(value));
             }
@@ -170,8 +795,69 @@ pub fn process_css<'a>(
                 if session.options.no_fonts && curr_rule == "font-face" {
                     continue;
                 }
+                if curr_rule.eq_ignore_ascii_case("import") {
+                    let state = parser.state();
+                    let prelude_start: SourcePosition = parser.position();
+                    let _ = parser.parse_until_before(
+                        Delimiter::Semicolon,
+                        |parser| -> Result<(), ParseError<()>> {
+                            while parser.next().is_ok() {}
+                            Ok(())
+                        },
+                    );
+
+                    if let Some(import) = parse_import_prelude(parser.slice_from(prelude_start)) {
+                        // Skip @import rules which only target excluded media (no need to fetch those)
+                        if is_excluded_media_query(
+                            &import.media,
+                            &get_excluded_media(&session.options),
+                        ) {
+                            let _ = parser.expect_semicolon();
+                            curr_rule = "".to_string();
+                            continue;
+                        }
+
+                        // Replace @import rule with contents of the stylesheet it refers to
+                        if session.options.inline_stylesheets {
+                            if let Some(import_css) = inline_import(session, document_url, &import)
+                            {
+                                let _ = parser.expect_semicolon();
+                                result.push_str(&import_css);
+                                curr_rule = "".to_string();
+                                continue;
+                            }
+                        }
+                    }
+                    parser.reset(&state);
+                }
+                if curr_rule.eq_ignore_ascii_case("media") {
+                    let excluded_media: Vec<String> = get_excluded_media(&session.options);
+
+                    // Skip @media rules which only target excluded media
+                    if !excluded_media.is_empty() {
+                        let state = parser.state();
+                        let media_query_start: SourcePosition = parser.position();
+                        let _ = parser.parse_until_before(
+                            Delimiter::CurlyBracketBlock,
+                            |parser| -> Result<(), ParseError<()>> {
+                                while parser.next().is_ok() {}
+                                Ok(())
+                            },
+                        );
+                        if is_excluded_media_query(
+                            parser.slice_from(media_query_start),
+                            &excluded_media,
+                        ) {
+                            let _ = parser.next();
+                            curr_rule = "".to_string();
+                            continue;
+                        }
+                        parser.reset(&state);
+                    }
+                }
+                at_rule_offset = result.len();
                 result.push('@');
-                result.push_str(value);
+                result.push_str(&curr_rule);
             }
             Token::Hash(ref value) => {
                 result.push('#');
@@ -189,22 +875,26 @@ pub fn process_css<'a>(
                     }
 
                     let import_full_url: Url = resolve_url(document_url, value);
-                    match session.retrieve_asset(document_url, &import_full_url) {
+                    match session.retrieve_asset(
+                        document_url,
+                        &import_full_url,
+                        Some(ResourceType::Stylesheet),
+                    ) {
                         Ok((
                             import_contents,
                             import_final_url,
                             import_media_type,
                             import_charset,
                         )) => {
-                            let mut import_data_url = create_data_url(
-                                &import_media_type,
+                            let import_css: String = embed_stylesheet(
+                                session,
+                                &import_final_url,
+                                &import_contents,
                                 &import_charset,
-                                embed_css(
-                                    session,
-                                    &import_final_url,
-                                    &String::from_utf8_lossy(&import_contents),
-                                )
-                                .as_bytes(),
+                            );
+                            let mut import_data_url = create_stylesheet_data_url(
+                                &import_media_type,
+                                &import_css,
                                 &import_final_url,
                             );
                             import_data_url.set_fragment(import_full_url.fragment());
@@ -233,7 +923,11 @@ pub fn process_css<'a>(
                     } else {
                         let resolved_url: Url = resolve_url(document_url, value);
 
-                        match session.retrieve_asset(document_url, &resolved_url) {
+                        match session.retrieve_asset(
+                            document_url,
+                            &resolved_url,
+                            Some(get_url_resource_type(in_font_face)),
+                        ) {
                             Ok((data, final_url, media_type, charset)) => {
                                 // hash the url and create a css custom prop to use as the background
                                 // all the props are written after fully processing the css
@@ -260,7 +954,8 @@ pub fn process_css<'a>(
                                         result.push(')');
                                     }
                                 } else {
-                                    // TODO: if it's @font-face, exclude definitions of non-woff/woff-2 fonts (if woff/woff-2 are present)
+                                    let (data, media_type) =
+                                        subset_font_asset(session, &curr_prop, data, media_type);
                                     let mut data_url =
                                         create_data_url(&media_type, &charset, &data, &final_url);
                                     data_url.set_fragment(resolved_url.fragment());
@@ -349,15 +1044,15 @@ pub fn process_css<'a>(
                 result.push_str("url(");
                 if is_import {
                     let full_url: Url = resolve_url(document_url, value);
-                    match session.retrieve_asset(document_url, &full_url) {
+                    match session.retrieve_asset(
+                        document_url,
+                        &full_url,
+                        Some(ResourceType::Stylesheet),
+                    ) {
                         Ok((css, final_url, media_type, charset)) => {
-                            let mut data_url = create_data_url(
-                                &media_type,
-                                &charset,
-                                embed_css(session, &final_url, &String::from_utf8_lossy(&css))
-                                    .as_bytes(),
-                                &final_url,
-                            );
+                            let css: String = embed_stylesheet(session, &final_url, &css, &charset);
+                            let mut data_url =
+                                create_stylesheet_data_url(&media_type, &css, &final_url);
                             data_url.set_fragment(full_url.fragment());
                             result.push_str(format_quoted_string(data_url.as_ref()).as_str());
                         }
@@ -373,7 +1068,11 @@ pub fn process_css<'a>(
                 } else {
                     let full_url: Url = resolve_url(document_url, value);
                     // same css custom property approach as above
-                    match session.retrieve_asset(document_url, &full_url) {
+                    match session.retrieve_asset(
+                        document_url,
+                        &full_url,
+                        Some(get_url_resource_type(in_font_face)),
+                    ) {
                         Ok((data, final_url, media_type, charset)) => {
                             if is_image_url_prop(curr_prop.as_str()) && session.options.exp_css_prop_assets {
                                     if let Some(asset) = css_assets.get(final_url.as_str()) {
@@ -402,6 +1101,8 @@ pub fn process_css<'a>(
                                         // end ) is closed before next token
                                     }
                                 } else {
+                                let (data, media_type) =
+                                    subset_font_asset(session, &curr_prop, data, media_type);
                                 let mut data_url =
                                     create_data_url(&media_type, &charset, &data, &final_url);
                                 data_url.set_fragment(full_url.fragment());
@@ -453,6 +1154,41 @@ pub fn process_css<'a>(
                         result.push_str(&inner);
                         result.push(')');
                     }
+                } else if is_image_set_function(function_name) {
+                    let block_start: SourcePosition = parser.position();
+                    let _ = parser.parse_nested_block(|parser| -> Result<(), ParseError<()>> {
+                        while parser.next().is_ok() {}
+                        Ok(())
+                    });
+                    let block: &str = parser.slice_from(block_start);
+                    let mut candidates: &str = block.strip_suffix(')').unwrap_or(block);
+
+                    // Drop all candidates but the one best suited for given pixel density
+                    if let Some(pixel_density) = session.options.pixel_density {
+                        if let Some(candidate) =
+                            select_image_set_candidate(candidates, pixel_density)
+                        {
+                            candidates = candidate;
+                        }
+                    }
+
+                    // Plain strings within image-set() are URLs, process them as such
+                    let mut candidates_input = ParserInput::new(candidates);
+                    let mut candidates_parser = Parser::new(&mut candidates_input);
+                    let block_css: String = process_css(
+                        session,
+                        document_url,
+                        &mut candidates_parser,
+                        curr_rule.as_str(),
+                        curr_prop.as_str(),
+                        "url",
+                        css_assets,
+                    )
+                    .unwrap();
+                    result.push_str(function_name);
+                    result.push('(');
+                    result.push_str(block_css.as_str());
+                    result.push(')');
                 } else {
                     result.push_str(function_name);
                     result.push('(');
@@ -477,6 +1213,10 @@ pub fn process_css<'a>(
         }
     }
 
+    if session.options.minify_css && result.ends_with(' ') {
+        result.pop();
+    }
+
     // Ensure empty CSS is really empty
     if !result.is_empty() && result.trim().is_empty() {
         result = result.trim().to_string()
@@ -484,3 +1224,632 @@ pub fn process_css<'a>(
 
     Ok(result)
 }
+
+#[derive(Clone, Copy, PartialEq)]
+enum Combinator {
+    Child,
+    Descendant,
+    NextSibling,
+    SubsequentSibling,
+}
+
+enum SimpleSelector {
+    Attribute(String, Option<(String, String)>),
+    Class(String),
+    Id(String),
+    PseudoClass,
+    Type(String),
+}
+
+// Compound selectors, each one paired with the combinator which ties it to the preceding compound
+type ComplexSelector = Vec<(Combinator, Vec<SimpleSelector>)>;
+
+fn parse_selector_list(selector_list: &str) -> Option<Vec<ComplexSelector>> {
+    let mut input = ParserInput::new(selector_list);
+    let mut parser = Parser::new(&mut input);
+    let mut selectors: Vec<ComplexSelector> = vec![];
+    let mut complex_selector: ComplexSelector = vec![];
+    let mut compound_selector: Vec<SimpleSelector> = vec![];
+    let mut compound_started: bool = false;
+    let mut combinator: Combinator = Combinator::Descendant;
+    let mut combinator_pending: bool = false;
+
+    loop {
+        let token = parser.next_including_whitespace().ok().cloned();
+
+        // Finish current compound selector
+        if !matches!(
+            token,
+            Some(Token::Ident(_))
+                | Some(Token::IDHash(_))
+                | Some(Token::Colon)
+                | Some(Token::SquareBracketBlock)
+                | Some(Token::Delim('.'))
+                | Some(Token::Delim('*'))
+        ) && compound_started
+        {
+            complex_selector.push((combinator, std::mem::take(&mut compound_selector)));
+            compound_started = false;
+            combinator = Combinator::Descendant;
+        }
+
+        match token {
+            None | Some(Token::Comma) => {
+                if complex_selector.is_empty() || combinator_pending {
+                    return None;
+                }
+                selectors.push(std::mem::take(&mut complex_selector));
+                if token.is_none() {
+                    break;
+                }
+            }
+            Some(Token::WhiteSpace(_)) => {}
+            Some(Token::Delim(delim)) if ['>', '+', '~'].contains(&delim) => {
+                // Relative selectors and chained combinators aren't supported
+                if complex_selector.is_empty() || combinator_pending {
+                    return None;
+                }
+                combinator = match delim {
+                    '>' => Combinator::Child,
+                    '+' => Combinator::NextSibling,
+                    _ => Combinator::SubsequentSibling,
+                };
+                combinator_pending = true;
+            }
+            Some(token) => {
+                compound_started = true;
+                combinator_pending = false;
+
+                match token {
+                    Token::Ident(name) => {
+                        compound_selector.push(SimpleSelector::Type(name.to_string()));
+                    }
+                    Token::IDHash(id) => {
+                        compound_selector.push(SimpleSelector::Id(id.to_string()));
+                    }
+                    Token::Delim('*') => {}
+                    Token::Delim('.') => match parser.next_including_whitespace() {
+                        Ok(Token::Ident(class)) => {
+                            compound_selector.push(SimpleSelector::Class(class.to_string()));
+                        }
+                        _ => return None,
+                    },
+                    Token::SquareBracketBlock => {
+                        compound_selector
+                            .push(parser.parse_nested_block(parse_attribute_selector).ok()?);
+                    }
+                    Token::Colon => {
+                        // Pseudo-classes and pseudo-elements depend on state of the document
+                        // which can't be determined here, hence they're assumed to always match
+                        compound_selector.push(SimpleSelector::PseudoClass);
+                        let mut token = parser.next_including_whitespace().ok()?.clone();
+                        if token == Token::Colon {
+                            token = parser.next_including_whitespace().ok()?.clone();
+                        }
+                        match token {
+                            Token::Ident(_) => {}
+                            Token::Function(_) => {
+                                parse_block_contents(&mut parser);
+                            }
+                            _ => return None,
+                        }
+                    }
+                    _ => return None,
+                }
+            }
+        }
+    }
+
+    Some(selectors)
+}
+
+fn parse_attribute_selector<'i>(
+    parser: &mut Parser<'i, '_>,
+) -> Result<SimpleSelector, ParseError<'i, ()>> {
+    let attr_name: String = parser.expect_ident()?.to_string();
+
+    let operator: String = match parser.next() {
+        Err(_) => return Ok(SimpleSelector::Attribute(attr_name, None)),
+        Ok(Token::Delim('=')) => "=".to_string(),
+        Ok(Token::IncludeMatch) => "~=".to_string(),
+        Ok(Token::DashMatch) => "|=".to_string(),
+        Ok(Token::PrefixMatch) => "^=".to_string(),
+        Ok(Token::SuffixMatch) => "$=".to_string(),
+        Ok(Token::SubstringMatch) => "*=".to_string(),
+        Ok(token) => {
+            let token = token.clone();
+            return Err(parser.new_unexpected_token_error(token));
+        }
+    };
+    let value: String = parser.expect_ident_or_string()?.to_string();
+
+    // Values are always compared case-insensitively, so the case-sensitivity flag doesn't matter
+    let _ = parser.try_parse(|parser| parser.expect_ident().cloned());
+    parser.expect_exhausted()?;
+
+    Ok(SimpleSelector::Attribute(
+        attr_name,
+        Some((operator, value)),
+    ))
+}
+
+fn simple_selector_matches(node: &Handle, simple_selector: &SimpleSelector) -> bool {
+    // Comparisons here are case-insensitive, since matching too much is harmless
+    match simple_selector {
+        SimpleSelector::Type(name) => get_node_name(node)
+            .map(|node_name| node_name.eq_ignore_ascii_case(name))
+            .unwrap_or(false),
+        SimpleSelector::Id(id) => get_node_attr(node, "id")
+            .map(|node_id| node_id.eq_ignore_ascii_case(id))
+            .unwrap_or(false),
+        SimpleSelector::Class(class) => get_node_attr(node, "class")
+            .map(|classes| {
+                classes
+                    .split_ascii_whitespace()
+                    .any(|node_class| node_class.eq_ignore_ascii_case(class))
+            })
+            .unwrap_or(false),
+        SimpleSelector::PseudoClass => true,
+        SimpleSelector::Attribute(attr_name, condition) => {
+            let attr_value: String = match &node.data {
+                NodeData::Element { attrs, .. } => {
+                    match attrs
+                        .borrow()
+                        .iter()
+                        .find(|attr| attr.name.local.as_ref().eq_ignore_ascii_case(attr_name))
+                    {
+                        Some(attr) => attr.value.to_lowercase(),
+                        None => return false,
+                    }
+                }
+                _ => return false,
+            };
+
+            match condition {
+                None => true,
+                Some((operator, value)) => {
+                    let value: String = value.to_lowercase();
+
+                    match operator.as_str() {
+                        "=" => attr_value == value,
+                        "~=" => attr_value.split_ascii_whitespace().any(|v| v == value),
+                        "|=" => attr_value == value || attr_value.starts_with(&(value + "-")),
+                        "^=" => !value.is_empty() && attr_value.starts_with(&value),
+                        "$=" => !value.is_empty() && attr_value.ends_with(&value),
+                        _ => !value.is_empty() && attr_value.contains(&value),
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn complex_selector_matches(
+    node: &Handle,
+    complex_selector: &[(Combinator, Vec<SimpleSelector>)],
+) -> bool {
+    let Some(((combinator, compound_selector), preceding)) = complex_selector.split_last() else {
+        return true;
+    };
+
+    if !compound_selector
+        .iter()
+        .all(|simple_selector| simple_selector_matches(node, simple_selector))
+    {
+        return false;
+    }
+
+    if preceding.is_empty() {
+        return true;
+    }
+
+    let mut next: Option<Handle> = match combinator {
+        Combinator::Child | Combinator::Descendant => get_parent_element(node),
+        Combinator::NextSibling | Combinator::SubsequentSibling => {
+            get_previous_sibling_element(node)
+        }
+    };
+    while let Some(current) = next {
+        if complex_selector_matches(&current, preceding) {
+            return true;
+        }
+
+        next = match combinator {
+            Combinator::Descendant => get_parent_element(&current),
+            Combinator::SubsequentSibling => get_previous_sibling_element(&current),
+            _ => None,
+        };
+    }
+
+    false
+}
+
+// Selectors parsed ahead of time (for checking lots of them against every element),
+// indexed by IDs and classes elements need to have in order to match them
+#[derive(Default)]
+pub struct SelectorSet {
+    by_class: HashMap<String, Vec<ComplexSelector>>,
+    by_id: HashMap<String, Vec<ComplexSelector>>,
+    others: Vec<ComplexSelector>,
+}
+
+impl SelectorSet {
+    pub fn add(&mut self, selector_list: &str) {
+        for complex_selector in parse_selector_list(selector_list).unwrap_or_default() {
+            // Unlike when looking for unused CSS, matching too much isn't harmless here
+            if complex_selector.iter().any(|(_, compound_selector)| {
+                compound_selector
+                    .iter()
+                    .any(|simple_selector| matches!(simple_selector, SimpleSelector::PseudoClass))
+            }) {
+                continue;
+            }
+
+            let key: Option<(bool, String)> = complex_selector.last().and_then(|(_, compound)| {
+                compound
+                    .iter()
+                    .find_map(|simple_selector| match simple_selector {
+                        SimpleSelector::Id(id) => Some((true, id.to_lowercase())),
+                        SimpleSelector::Class(class) => Some((false, class.to_lowercase())),
+                        _ => None,
+                    })
+            });
+            match key {
+                Some((true, id)) => self.by_id.entry(id).or_default().push(complex_selector),
+                Some((false, class)) => self
+                    .by_class
+                    .entry(class)
+                    .or_default()
+                    .push(complex_selector),
+                None => self.others.push(complex_selector),
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_class.is_empty() && self.by_id.is_empty() && self.others.is_empty()
+    }
+
+    pub fn matches(&self, node: &Handle) -> bool {
+        let id: String = get_node_attr(node, "id").unwrap_or_default().to_lowercase();
+        let classes: String = get_node_attr(node, "class")
+            .unwrap_or_default()
+            .to_lowercase();
+
+        self.by_id
+            .get(&id)
+            .into_iter()
+            .chain(
+                classes
+                    .split_ascii_whitespace()
+                    .filter_map(|class| self.by_class.get(class)),
+            )
+            .flatten()
+            .chain(self.others.iter())
+            .any(|complex_selector| complex_selector_matches(node, complex_selector))
+    }
+}
+
+pub fn selector_list_may_match(selector_list: &str, elements: &[Handle]) -> bool {
+    match parse_selector_list(selector_list) {
+        Some(complex_selectors) => complex_selectors.iter().any(|complex_selector| {
+            elements
+                .iter()
+                .any(|element| complex_selector_matches(element, complex_selector))
+        }),
+        // Keep rules which have selectors that can't be fully understood
+        None => true,
+    }
+}
+
+fn filter_css_rules(
+    parser: &mut Parser,
+    keep_rule: &mut dyn FnMut(&str, &str, &str) -> bool,
+) -> String {
+    let mut result: String = "".to_string();
+    let mut rule_start: Option<SourcePosition> = None;
+    let mut prelude_start: SourcePosition = parser.position();
+    let mut at_rule_name: String = "".to_string();
+
+    loop {
+        let token_start: SourcePosition = parser.position();
+        let token = match parser.next_including_whitespace_and_comments() {
+            Ok(token) => token.clone(),
+            Err(_) => break,
+        };
+
+        match token {
+            Token::WhiteSpace(_) | Token::Comment(_) | Token::CDO | Token::CDC
+                if rule_start.is_none() =>
+            {
+                result.push_str(parser.slice_from(token_start));
+            }
+            Token::Semicolon if rule_start.is_some() && !at_rule_name.is_empty() => {
+                // Statement at-rules such as @import or @charset
+                result.push_str(parser.slice_from(rule_start.unwrap()));
+                rule_start = None;
+            }
+            Token::CurlyBracketBlock => {
+                let start: SourcePosition = rule_start.unwrap_or(token_start);
+                let prelude: &str = parser.slice(prelude_start..token_start).trim();
+                let at_rule: String = at_rule_name.to_lowercase();
+                let at_rule: &str = at_rule.trim_start_matches("-moz-");
+
+                if CSS_GROUPING_AT_RULES.contains(&at_rule) {
+                    let head: String = parser.slice(start..token_start).to_string();
+                    let block: String = parser
+                        .parse_nested_block(|parser| -> Result<String, ParseError<()>> {
+                            Ok(filter_css_rules(parser, keep_rule))
+                        })
+                        .unwrap_or_default();
+
+                    // Layers affect precedence of other layers even when they're empty
+                    if at_rule == "layer" || !block.trim().is_empty() {
+                        result.push_str(&head);
+                        result.push('{');
+                        result.push_str(&block);
+                        result.push('}');
+                    } else {
+                        result.truncate(result.trim_end().len());
+                    }
+                } else {
+                    let prelude: String = prelude.to_string();
+                    let block: String = parse_block_contents(parser);
+
+                    if keep_rule(&at_rule_name, &prelude, &block) {
+                        result.push_str(parser.slice_from(start));
+                    } else {
+                        result.truncate(result.trim_end().len());
+                    }
+                }
+
+                rule_start = None;
+            }
+            _ => {
+                if rule_start.is_none() {
+                    rule_start = Some(token_start);
+                    prelude_start = token_start;
+                    at_rule_name.clear();
+
+                    if let Token::AtKeyword(name) = &token {
+                        at_rule_name = name.to_string();
+                        prelude_start = parser.position();
+                    }
+                }
+
+                // Step over nested blocks, so that the position doesn't get stuck within them
+                if matches!(
+                    token,
+                    Token::Function(_) | Token::ParenthesisBlock | Token::SquareBracketBlock
+                ) {
+                    parse_block_contents(parser);
+                }
+            }
+        }
+    }
+
+    // Retain whatever incomplete rule there might be at the end
+    if let Some(rule_start) = rule_start {
+        result.push_str(parser.slice_from(rule_start));
+    }
+
+    result
+}
+
+pub fn remove_unused_css_rules(css: &str, elements: &[Handle]) -> String {
+    let mut input = ParserInput::new(css);
+    let mut parser = Parser::new(&mut input);
+
+    filter_css_rules(&mut parser, &mut |at_rule_name, prelude, _block| {
+        // Only style rules get checked against the document
+        !at_rule_name.is_empty() || selector_list_may_match(prelude, elements)
+    })
+}
+
+fn collect_css_prop_values(parser: &mut Parser, prop_names: &[&str], values: &mut Vec<String>) {
+    let mut prop_name: String = "".to_string();
+    let mut at_rule_name: String = "".to_string();
+
+    while let Ok(token) = parser.next() {
+        match token.clone() {
+            Token::Ident(name) => prop_name = name.to_string(),
+            Token::AtKeyword(name) => at_rule_name = name.to_string(),
+            Token::Colon if prop_names.iter().any(|p| is_prop_name_match(&prop_name, p)) => {
+                let value_start: SourcePosition = parser.position();
+                let _ = parser.parse_until_before(
+                    Delimiter::Semicolon,
+                    |parser| -> Result<(), ParseError<()>> {
+                        while parser.next().is_ok() {}
+                        Ok(())
+                    },
+                );
+                values.push(parser.slice_from(value_start).trim().to_string());
+                prop_name.clear();
+            }
+            Token::CurlyBracketBlock => {
+                // Font families defined by @font-face rules aren't references to them
+                if !at_rule_name.eq_ignore_ascii_case("font-face") {
+                    let _ = parser.parse_nested_block(|parser| -> Result<(), ParseError<()>> {
+                        collect_css_prop_values(parser, prop_names, values);
+                        Ok(())
+                    });
+                }
+                at_rule_name.clear();
+                prop_name.clear();
+            }
+            Token::Semicolon => {
+                at_rule_name.clear();
+                prop_name.clear();
+            }
+            _ => {}
+        }
+    }
+}
+
+fn is_prop_name_match(prop_name: &str, pattern: &str) -> bool {
+    // Patterns ending with an asterisk match any property with that prefix (e.g. "--*")
+    match pattern.strip_suffix('*') {
+        Some(prefix) => prop_name.starts_with(prefix),
+        None => prop_name.eq_ignore_ascii_case(pattern),
+    }
+}
+
+pub fn get_css_chars(css: &str, chars: &mut HashSet<char>) {
+    // Generated content brings characters of its own
+    let mut values: Vec<String> = vec![];
+    let mut input = ParserInput::new(css);
+    let mut parser = Parser::new(&mut input);
+    collect_css_prop_values(&mut parser, &["content"], &mut values);
+    for value in values {
+        let mut input = ParserInput::new(&value);
+        let mut parser = Parser::new(&mut input);
+        while let Ok(token) = parser.next() {
+            if let Token::QuotedString(string) = token {
+                chars.extend(string.chars());
+            }
+        }
+    }
+
+    // Transformed text may need glyphs for either case of every character
+    let mut values: Vec<String> = vec![];
+    let mut input = ParserInput::new(css);
+    let mut parser = Parser::new(&mut input);
+    collect_css_prop_values(&mut parser, &["text-transform"], &mut values);
+    if values
+        .iter()
+        .any(|value| !value.eq_ignore_ascii_case("none"))
+    {
+        let case_variants: Vec<char> = chars
+            .iter()
+            .flat_map(|c| c.to_uppercase().chain(c.to_lowercase()))
+            .collect();
+        chars.extend(case_variants);
+    }
+}
+
+pub fn get_css_references(
+    css: &str,
+    font_families: &mut Vec<String>,
+    animation_names: &mut HashSet<String>,
+) {
+    // Values of custom properties may end up in fonts and animations by the means of var()
+    let mut values: Vec<String> = vec![];
+    let mut input = ParserInput::new(css);
+    let mut parser = Parser::new(&mut input);
+    collect_css_prop_values(&mut parser, &["font", "font-family", "--*"], &mut values);
+    font_families.extend(values.iter().map(|value| normalize_font_family(value)));
+
+    values.clear();
+    let mut input = ParserInput::new(css);
+    let mut parser = Parser::new(&mut input);
+    collect_css_prop_values(&mut parser, &["animation", "animation-name", "--*"], &mut values);
+    for value in values {
+        let mut input = ParserInput::new(&value);
+        let mut parser = Parser::new(&mut input);
+        while let Ok(token) = parser.next() {
+            if let Token::Ident(name) | Token::QuotedString(name) = token {
+                animation_names.insert(name.to_string());
+            }
+        }
+    }
+
+    // Stylesheets imported as data URLs are part of this one
+    for css_url in get_css_urls(css) {
+        if let Ok(css_url) = Url::parse(&css_url) {
+            if css_url.scheme() == "data" {
+                let (media_type, charset, data) = parse_data_url(&css_url);
+                if media_type.eq_ignore_ascii_case("text/css") {
+                    let (css, _) = decode_stylesheet(&data, &charset, UTF_8);
+                    get_css_references(&css, font_families, animation_names);
+                }
+            }
+        }
+    }
+}
+
+pub fn get_css_urls(css: &str) -> Vec<String> {
+    let mut input = ParserInput::new(css);
+    let mut parser = Parser::new(&mut input);
+    let mut urls: Vec<String> = vec![];
+
+    collect_css_urls(&mut parser, false, &mut urls);
+
+    urls
+}
+
+fn collect_css_urls(parser: &mut Parser, is_url_function: bool, urls: &mut Vec<String>) {
+    let mut is_import_rule: bool = false;
+
+    while let Ok(token) = parser.next_including_whitespace_and_comments() {
+        match token {
+            Token::UnquotedUrl(value) => {
+                urls.push(value.to_string());
+            }
+            Token::QuotedString(value) if is_url_function || is_import_rule => {
+                urls.push(value.to_string());
+                is_import_rule = false;
+            }
+            Token::AtKeyword(name) => {
+                is_import_rule = name.eq_ignore_ascii_case("import");
+            }
+            Token::Semicolon => {
+                is_import_rule = false;
+            }
+            Token::Function(name) => {
+                let is_url_function: bool = ["url", "src", "image-set", "-webkit-image-set"]
+                    .iter()
+                    .any(|url_function| name.eq_ignore_ascii_case(url_function));
+
+                let _ = parser.parse_nested_block(|parser| -> Result<(), ParseError<()>> {
+                    collect_css_urls(parser, is_url_function, urls);
+                    Ok(())
+                });
+            }
+            Token::ParenthesisBlock | Token::SquareBracketBlock | Token::CurlyBracketBlock => {
+                let _ = parser.parse_nested_block(|parser| -> Result<(), ParseError<()>> {
+                    collect_css_urls(parser, false, urls);
+                    Ok(())
+                });
+            }
+            _ => {}
+        }
+    }
+}
+
+fn normalize_font_family(font_family: &str) -> String {
+    font_family
+        .replace(['"', '\''], "")
+        .split_ascii_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+pub fn remove_unreferenced_css_at_rules(
+    css: &str,
+    font_families: &[String],
+    animation_names: &HashSet<String>,
+) -> String {
+    let mut input = ParserInput::new(css);
+    let mut parser = Parser::new(&mut input);
+
+    filter_css_rules(&mut parser, &mut |at_rule_name, prelude, block| {
+        let at_rule_name: String = at_rule_name.to_lowercase();
+
+        if at_rule_name == "font-face" {
+            let mut input = ParserInput::new(block);
+            let mut parser = Parser::new(&mut input);
+            let mut values: Vec<String> = vec![];
+            collect_css_prop_values(&mut parser, &["font-family"], &mut values);
+
+            values.iter().all(|value| {
+                let font_family: String = normalize_font_family(value);
+                font_family.is_empty() || font_families.iter().any(|f| f.contains(&font_family))
+            })
+        } else if at_rule_name.ends_with("keyframes") {
+            animation_names.contains(prelude.trim_matches(['"', '\'']))
+        } else {
+            true
+        }
+    })
+}
@@ -0,0 +1,417 @@
+use std::collections::{HashMap, HashSet};
+
+use encoding_rs::UTF_8;
+use markup5ever_rcdom::{Handle, NodeData};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+
+use crate::css::{decode_stylesheet, get_css_urls};
+use crate::html::{
+    get_node_name, get_title, parse_html_document, parse_metadata_tag, parse_srcset,
+};
+use crate::url::{parse_data_url, Url};
+
+const BLOCK_ELEMENTS: &[&str] = &[
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "br",
+    "caption",
+    "dd",
+    "details",
+    "div",
+    "dl",
+    "dt",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hr",
+    "li",
+    "main",
+    "nav",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "summary",
+    "table",
+    "td",
+    "th",
+    "tr",
+    "ul",
+];
+const NON_TEXT_ELEMENTS: &[&str] = &["head", "noscript", "script", "style", "template"];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SnapshotAsset {
+    pub location: String,
+    pub hash: String,
+    pub media_type: String,
+    pub size: usize,
+}
+
+#[derive(Debug, Default)]
+pub struct DocumentSnapshot {
+    pub assets: Vec<SnapshotAsset>,
+    pub metadata: Option<(String, String)>,
+    pub text: Vec<String>,
+    pub title: Option<String>,
+}
+
+pub fn diff_document_snapshots(previous: &DocumentSnapshot, current: &DocumentSnapshot) -> Value {
+    let (removed_text, added_text) = diff_text_blocks(&previous.text, &current.text);
+    let previous_assets: Vec<(String, &SnapshotAsset)> = index_assets(&previous.assets);
+    let current_assets: Vec<(String, &SnapshotAsset)> = index_assets(&current.assets);
+
+    // Assets found in both versions stay the same, no matter where they ended up
+    let previous_hashes: HashSet<&str> = previous_assets
+        .iter()
+        .map(|(_, asset)| asset.hash.as_str())
+        .collect();
+    let current_hashes: HashSet<&str> = current_assets
+        .iter()
+        .map(|(_, asset)| asset.hash.as_str())
+        .collect();
+    let mut removed_assets: Vec<&(String, &SnapshotAsset)> = previous_assets
+        .iter()
+        .filter(|(_, asset)| !current_hashes.contains(asset.hash.as_str()))
+        .collect();
+    let mut added_assets: Vec<&(String, &SnapshotAsset)> = current_assets
+        .iter()
+        .filter(|(_, asset)| !previous_hashes.contains(asset.hash.as_str()))
+        .collect();
+
+    // What's left at the same kind of location got replaced in place
+    let mut changed_assets: Vec<Value> = vec![];
+    removed_assets.retain(|(_, previous_asset)| {
+        let Some(index) = added_assets
+            .iter()
+            .position(|(_, current_asset)| current_asset.location == previous_asset.location)
+        else {
+            return true;
+        };
+        let (location, current_asset) = added_assets.remove(index);
+        changed_assets.push(json!({
+            "location": location,
+            "previous": asset_to_json(previous_asset),
+            "current": asset_to_json(current_asset),
+        }));
+        false
+    });
+
+    json!({
+        "title": if previous.title != current.title {
+            json!({ "previous": previous.title, "current": current.title })
+        } else {
+            Value::Null
+        },
+        // Every save has its own timestamp, only a different source counts as a change
+        "metadata": if previous.metadata.as_ref().map(|(source, _)| source)
+            != current.metadata.as_ref().map(|(source, _)| source)
+        {
+            json!({
+                "previous": metadata_to_json(&previous.metadata),
+                "current": metadata_to_json(&current.metadata),
+            })
+        } else {
+            Value::Null
+        },
+        "text": {
+            "removed": removed_text,
+            "added": added_text,
+        },
+        "assets": {
+            "added": list_unique_assets(&added_assets),
+            "removed": list_unique_assets(&removed_assets),
+            "changed": changed_assets,
+        },
+    })
+}
+
+pub fn diff_text_blocks(previous: &[String], current: &[String]) -> (Vec<String>, Vec<String>) {
+    // Leave out what both versions start and end with
+    let prefix_len: usize = previous
+        .iter()
+        .zip(current.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let suffix_len: usize = previous[prefix_len..]
+        .iter()
+        .rev()
+        .zip(current[prefix_len..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let previous: &[String] = &previous[prefix_len..previous.len() - suffix_len];
+    let current: &[String] = &current[prefix_len..current.len() - suffix_len];
+
+    let mut removed: Vec<String> = vec![];
+    let mut added: Vec<String> = vec![];
+    collect_text_differences(previous, current, &mut removed, &mut added);
+
+    (removed, added)
+}
+
+pub fn take_document_snapshot(data: &[u8]) -> DocumentSnapshot {
+    let mut snapshot: DocumentSnapshot = DocumentSnapshot {
+        metadata: parse_metadata_tag(&String::from_utf8_lossy(data)),
+        ..Default::default()
+    };
+    let (dom, _) = parse_html_document(data, "");
+
+    snapshot.title = get_title(&dom.document)
+        .map(|title| title.split_whitespace().collect::<Vec<&str>>().join(" "))
+        .filter(|title| !title.is_empty());
+
+    let mut text_block: String = String::new();
+    collect_text_blocks(&dom.document, &mut snapshot.text, &mut text_block);
+    flush_text_block(&mut snapshot.text, &mut text_block);
+
+    collect_assets(&dom.document, "", &mut snapshot.assets);
+
+    snapshot
+}
+
+fn add_asset(assets: &mut Vec<SnapshotAsset>, location: &str, reference: &str) {
+    let Some(url) = Url::parse(reference.trim())
+        .ok()
+        .filter(|url| url.scheme() == "data")
+    else {
+        return;
+    };
+    let (media_type, charset, data) = parse_data_url(&url);
+
+    assets.push(SnapshotAsset {
+        location: location.to_string(),
+        hash: format!("{:x}", Sha256::digest(&data)),
+        media_type: media_type.clone(),
+        size: data.len(),
+    });
+
+    // Assets embedded into stylesheets and frames are tracked separately as well
+    match media_type.to_lowercase().as_str() {
+        "text/css" => {
+            let (css, _) = decode_stylesheet(&data, &charset, UTF_8);
+            for css_url in get_css_urls(&css) {
+                add_asset(assets, &format!("{} > url()", location), &css_url);
+            }
+        }
+        "text/html" | "application/xhtml+xml" => {
+            let (dom, _) = parse_html_document(&data, &charset);
+            collect_assets(&dom.document, location, assets);
+        }
+        _ => {}
+    }
+}
+
+fn asset_to_json(asset: &SnapshotAsset) -> Value {
+    json!({
+        "hash": asset.hash,
+        "media_type": asset.media_type,
+        "size": asset.size,
+    })
+}
+
+fn collect_assets(node: &Handle, parent_location: &str, assets: &mut Vec<SnapshotAsset>) {
+    if let NodeData::Element { ref attrs, .. } = node.data {
+        let node_name: &str = get_node_name(node).unwrap_or_default();
+        let location = |name: &str| -> String {
+            if parent_location.is_empty() {
+                name.to_string()
+            } else {
+                format!("{} > {}", parent_location, name)
+            }
+        };
+
+        for attr in attrs.borrow().iter() {
+            let attr_name: &str = &attr.name.local;
+            let attr_location: String = location(&format!("{}[{}]", node_name, attr_name));
+
+            match attr_name {
+                "imagesrcset" | "srcset" => {
+                    for srcset_item in parse_srcset(&attr.value) {
+                        add_asset(assets, &attr_location, srcset_item.path);
+                    }
+                }
+                "srcdoc" => {
+                    let (dom, _) = parse_html_document(attr.value.as_bytes(), "utf-8");
+                    collect_assets(&dom.document, &attr_location, assets);
+                }
+                "style" => {
+                    for css_url in get_css_urls(&attr.value) {
+                        add_asset(assets, &attr_location, &css_url);
+                    }
+                }
+                _ => {
+                    add_asset(assets, &attr_location, &attr.value);
+                }
+            }
+        }
+
+        if node_name == "style" {
+            for child_node in node.children.borrow().iter() {
+                if let NodeData::Text { ref contents } = child_node.data {
+                    for css_url in get_css_urls(&contents.borrow()) {
+                        add_asset(assets, &location("style"), &css_url);
+                    }
+                }
+            }
+        }
+    }
+
+    for child_node in node.children.borrow().iter() {
+        collect_assets(child_node, parent_location, assets);
+    }
+}
+
+fn collect_text_differences(
+    previous: &[String],
+    current: &[String],
+    removed: &mut Vec<String>,
+    added: &mut Vec<String>,
+) {
+    if previous.is_empty() || current.is_empty() {
+        removed.extend_from_slice(previous);
+        added.extend_from_slice(current);
+        return;
+    }
+
+    if previous.len() == 1 {
+        match current.iter().position(|block| *block == previous[0]) {
+            Some(position) => {
+                added.extend_from_slice(&current[..position]);
+                added.extend_from_slice(&current[position + 1..]);
+            }
+            None => {
+                removed.push(previous[0].clone());
+                added.extend_from_slice(current);
+            }
+        }
+        return;
+    }
+
+    // Split both versions where the longest common subsequence crosses the middle of the
+    // previous one, that way memory use stays linear even for very long documents
+    let middle: usize = previous.len() / 2;
+    let head_lengths: Vec<usize> = get_lcs_lengths(previous[..middle].iter(), current.iter());
+    let tail_lengths: Vec<usize> =
+        get_lcs_lengths(previous[middle..].iter().rev(), current.iter().rev());
+    let split: usize = (0..=current.len())
+        .max_by_key(|&j| head_lengths[j] + tail_lengths[current.len() - j])
+        .unwrap_or(0);
+
+    collect_text_differences(&previous[..middle], &current[..split], removed, added);
+    collect_text_differences(&previous[middle..], &current[split..], removed, added);
+}
+
+fn collect_text_blocks(node: &Handle, text_blocks: &mut Vec<String>, text_block: &mut String) {
+    match node.data {
+        NodeData::Text { ref contents } => {
+            text_block.push_str(&contents.borrow());
+        }
+        NodeData::Element { .. } => {
+            let node_name: &str = get_node_name(node).unwrap_or_default();
+            if NON_TEXT_ELEMENTS.contains(&node_name) {
+                return;
+            }
+
+            let is_block: bool = BLOCK_ELEMENTS.contains(&node_name);
+            if is_block {
+                flush_text_block(text_blocks, text_block);
+            }
+            for child_node in node.children.borrow().iter() {
+                collect_text_blocks(child_node, text_blocks, text_block);
+            }
+            if is_block {
+                flush_text_block(text_blocks, text_block);
+            }
+        }
+        NodeData::Document => {
+            for child_node in node.children.borrow().iter() {
+                collect_text_blocks(child_node, text_blocks, text_block);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn flush_text_block(text_blocks: &mut Vec<String>, text_block: &mut String) {
+    let text: String = text_block
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ");
+    if !text.is_empty() {
+        text_blocks.push(text);
+    }
+    text_block.clear();
+}
+
+// Returns lengths of longest common subsequences of given blocks and every prefix of other blocks
+fn get_lcs_lengths<'a>(
+    blocks: impl Iterator<Item = &'a String>,
+    other_blocks: impl Iterator<Item = &'a String> + Clone,
+) -> Vec<usize> {
+    let mut lengths: Vec<usize> = vec![0; other_blocks.clone().count() + 1];
+
+    for block in blocks {
+        let mut diagonal_length: usize = 0;
+        for (j, other_block) in other_blocks.clone().enumerate() {
+            let length: usize = if block == other_block {
+                diagonal_length + 1
+            } else {
+                lengths[j + 1].max(lengths[j])
+            };
+            diagonal_length = lengths[j + 1];
+            lengths[j + 1] = length;
+        }
+    }
+
+    lengths
+}
+
+fn index_assets(assets: &[SnapshotAsset]) -> Vec<(String, &SnapshotAsset)> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+
+    // The same kind of location may occur many times, number them in document order
+    assets
+        .iter()
+        .map(|asset| {
+            let count: &mut usize = counts.entry(&asset.location).or_default();
+            *count += 1;
+            (format!("{} #{}", asset.location, count), asset)
+        })
+        .collect()
+}
+
+fn list_unique_assets(assets: &[&(String, &SnapshotAsset)]) -> Vec<Value> {
+    let mut listed_hashes: HashSet<&str> = HashSet::new();
+    let mut list: Vec<Value> = vec![];
+
+    for (location, asset) in assets {
+        if !listed_hashes.insert(&asset.hash) {
+            continue;
+        }
+
+        let mut asset_json: Value = asset_to_json(asset);
+        asset_json["location"] = json!(location);
+        list.push(asset_json);
+    }
+
+    list
+}
+
+fn metadata_to_json(metadata: &Option<(String, String)>) -> Value {
+    match metadata {
+        Some((source, saved_at)) => json!({ "source": source, "saved_at": saved_at }),
+        None => Value::Null,
+    }
+}
@@ -0,0 +1,580 @@
+use regex::bytes::{Regex, RegexBuilder};
+
+use crate::core::detect_media_type_by_file_name;
+use crate::url::{is_same_site, Url};
+
+const COSMETIC_FILTER_SEPARATORS: &[&str] =
+    &["##", "#@#", "#?#", "#@?#", "#$#", "#@$#", "#%#", "#@%#"];
+// Types of requests which don't ever get made while saving documents
+const IGNORED_RESOURCE_TYPES: &[&str] = &[
+    "beacon",
+    "ping",
+    "webrtc",
+    "websocket",
+    "xhr",
+    "xmlhttprequest",
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ResourceType {
+    Font,
+    Image,
+    Media,
+    Object,
+    Other,
+    Script,
+    Stylesheet,
+    Subdocument,
+}
+
+#[derive(Debug)]
+pub enum FilterPattern {
+    Regex(Regex),
+    Wildcard {
+        pattern: String,
+        host_anchor: bool,
+        start_anchor: bool,
+        end_anchor: bool,
+    },
+}
+
+#[derive(Debug)]
+pub struct NetworkFilter {
+    pub pattern: FilterPattern,
+    pub is_exception: bool,
+    pub is_important: bool,
+    pub match_case: bool,
+    pub third_party: Option<bool>,
+    pub resource_types: Option<Vec<ResourceType>>,
+    pub excluded_resource_types: Vec<ResourceType>,
+    pub domains: Vec<String>,
+    pub excluded_domains: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ElementHidingFilter {
+    pub selector: String,
+    pub is_exception: bool,
+    pub domains: Vec<String>,
+    pub excluded_domains: Vec<String>,
+}
+
+#[derive(Debug, Default)]
+pub struct FilterList {
+    pub element_hiding_filters: Vec<ElementHidingFilter>,
+    pub network_filters: Vec<NetworkFilter>,
+}
+
+#[derive(Debug)]
+pub enum UrlPattern {
+    Glob(String),
+    Regex(Regex),
+}
+
+#[derive(Debug)]
+pub struct UrlRule {
+    pub pattern: UrlPattern,
+    pub is_exclusion: bool,
+    pub resource_type: Option<ResourceType>, // Rules without type apply to assets of all types
+}
+
+impl NetworkFilter {
+    // Both $third-party and $domain= options are relative to the document making the request
+    pub fn matches(
+        &self,
+        url: &Url,
+        document_url: &Url,
+        resource_type: Option<ResourceType>,
+    ) -> bool {
+        let url_string: String = if self.match_case {
+            url.as_str().to_string()
+        } else {
+            url.as_str().to_lowercase()
+        };
+
+        if let Some(third_party) = self.third_party {
+            if third_party == is_same_site(document_url, url) {
+                return false;
+            }
+        }
+
+        // Requests of unknown type match any type
+        if let Some(resource_type) = resource_type {
+            if self
+                .resource_types
+                .as_ref()
+                .is_some_and(|resource_types| !resource_types.contains(&resource_type))
+                || self.excluded_resource_types.contains(&resource_type)
+            {
+                return false;
+            }
+        } else if self
+            .resource_types
+            .as_ref()
+            .is_some_and(|resource_types| resource_types.is_empty())
+        {
+            return false;
+        }
+
+        let document_host: &str = document_url.host_str().unwrap_or_default();
+        if (!self.domains.is_empty()
+            && !self
+                .domains
+                .iter()
+                .any(|domain| host_is_within_domain(document_host, domain)))
+            || self
+                .excluded_domains
+                .iter()
+                .any(|domain| host_is_within_domain(document_host, domain))
+        {
+            return false;
+        }
+
+        match &self.pattern {
+            FilterPattern::Regex(regex) => regex.is_match(url_string.as_bytes()),
+            FilterPattern::Wildcard {
+                pattern,
+                host_anchor,
+                start_anchor,
+                end_anchor,
+            } => {
+                // Quickly rule out URLs which don't contain the longest literal part of the pattern
+                if let Some(literal) = pattern.split(['*', '^']).max_by_key(|part| part.len()) {
+                    if !url_string.contains(literal) {
+                        return false;
+                    }
+                }
+
+                let url_bytes: &[u8] = url_string.as_bytes();
+                let pattern_bytes: &[u8] = pattern.as_bytes();
+
+                if *host_anchor {
+                    // Domain anchors match at the beginning of the host or any of its labels
+                    let host_start: usize = url_string.find("://").map(|i| i + 3).unwrap_or(0);
+                    let host_end: usize = url_string[host_start..]
+                        .find(['/', '?', '#'])
+                        .map(|i| host_start + i)
+                        .unwrap_or(url_string.len());
+                    let host_start: usize = url_string[host_start..host_end]
+                        .rfind('@')
+                        .map(|i| host_start + i + 1)
+                        .unwrap_or(host_start);
+
+                    (host_start..host_end)
+                        .filter(|i| *i == host_start || url_bytes[i - 1] == b'.')
+                        .any(|i| wildcard_matches(pattern_bytes, &url_bytes[i..], *end_anchor))
+                } else if *start_anchor {
+                    wildcard_matches(pattern_bytes, url_bytes, *end_anchor)
+                } else {
+                    (0..url_bytes.len())
+                        .any(|i| wildcard_matches(pattern_bytes, &url_bytes[i..], *end_anchor))
+                }
+            }
+        }
+    }
+}
+
+impl FilterList {
+    pub fn add_filters(&mut self, filter_list: &str) {
+        for line in filter_list.lines() {
+            let line: &str = line.trim();
+
+            // Skip comments and headers
+            if line.is_empty() || line.starts_with('!') || line.starts_with('[') {
+                continue;
+            }
+
+            if let Some(element_hiding_filter) = parse_element_hiding_filter(line) {
+                self.element_hiding_filters.push(element_hiding_filter);
+            } else if COSMETIC_FILTER_SEPARATORS
+                .iter()
+                .any(|separator| line.contains(separator))
+            {
+                // Extended (procedural, scriptlet, etc) cosmetic filters aren't supported
+                continue;
+            } else if let Some(network_filter) = parse_network_filter(line) {
+                self.network_filters.push(network_filter);
+            }
+        }
+    }
+
+    pub fn blocks_request(
+        &self,
+        url: &Url,
+        document_url: &Url,
+        resource_type: Option<ResourceType>,
+    ) -> bool {
+        let mut is_blocked: bool = false;
+
+        for network_filter in self.network_filters.iter().filter(|f| !f.is_exception) {
+            if network_filter.matches(url, document_url, resource_type) {
+                // Exception filters can't override important filters
+                if network_filter.is_important {
+                    return true;
+                }
+                is_blocked = true;
+            }
+        }
+
+        is_blocked
+            && !self
+                .network_filters
+                .iter()
+                .filter(|f| f.is_exception)
+                .any(|f| f.matches(url, document_url, resource_type))
+    }
+
+    pub fn get_hidden_selectors(&self, document_url: &Url) -> Vec<&str> {
+        let host: &str = document_url.host_str().unwrap_or_default();
+        let applies = |filter: &ElementHidingFilter| -> bool {
+            (filter.domains.is_empty()
+                || filter
+                    .domains
+                    .iter()
+                    .any(|domain| host_is_within_domain(host, domain)))
+                && !filter
+                    .excluded_domains
+                    .iter()
+                    .any(|domain| host_is_within_domain(host, domain))
+        };
+        let exceptions: Vec<&str> = self
+            .element_hiding_filters
+            .iter()
+            .filter(|filter| filter.is_exception && applies(filter))
+            .map(|filter| filter.selector.as_str())
+            .collect();
+
+        self.element_hiding_filters
+            .iter()
+            .filter(|filter| {
+                !filter.is_exception
+                    && applies(filter)
+                    && !exceptions.contains(&filter.selector.as_str())
+            })
+            .map(|filter| filter.selector.as_str())
+            .collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.element_hiding_filters.is_empty() && self.network_filters.is_empty()
+    }
+}
+
+impl UrlRule {
+    pub fn matches(&self, url: &Url, resource_type: Option<ResourceType>) -> bool {
+        if self.resource_type.is_some() && self.resource_type != resource_type {
+            return false;
+        }
+
+        let mut url: Url = url.clone();
+        url.set_fragment(None);
+
+        match &self.pattern {
+            UrlPattern::Glob(glob) => {
+                // Globs starting with a slash only match paths (along with queries if they have one)
+                let text: String = if glob.starts_with('/') {
+                    match url.query() {
+                        Some(query) if glob.contains('?') => format!("{}?{}", url.path(), query),
+                        _ => url.path().to_string(),
+                    }
+                } else {
+                    url.to_string()
+                };
+
+                glob_matches(glob.as_bytes(), text.as_bytes())
+            }
+            UrlPattern::Regex(regex) => regex.is_match(url.as_str().as_bytes()),
+        }
+    }
+}
+
+pub fn guess_resource_type(url: &Url) -> Option<ResourceType> {
+    let media_type: String = detect_media_type_by_file_name(url.path());
+
+    match media_type.as_str() {
+        "" => None,
+        "text/css" => Some(ResourceType::Stylesheet),
+        "text/javascript" => Some(ResourceType::Script),
+        "text/html" | "application/xhtml+xml" => Some(ResourceType::Subdocument),
+        "application/x-shockwave-flash" => Some(ResourceType::Object),
+        _ if media_type.starts_with("audio/") || media_type.starts_with("video/") => {
+            Some(ResourceType::Media)
+        }
+        _ if media_type.starts_with("font/") => Some(ResourceType::Font),
+        _ if media_type.starts_with("image/") => Some(ResourceType::Image),
+        _ => Some(ResourceType::Other),
+    }
+}
+
+pub fn parse_element_hiding_filter(line: &str) -> Option<ElementHidingFilter> {
+    let (domains, selector, is_exception) =
+        if let Some((domains, selector)) = line.split_once("#@#") {
+            (domains, selector, true)
+        } else {
+            let (domains, selector) = line.split_once("##")?;
+            (domains, selector, false)
+        };
+    let selector: &str = selector.trim();
+
+    // Scriptlet injections and HTML filters aren't element hiding rules
+    if selector.is_empty() || selector.starts_with('+') || selector.starts_with('^') {
+        return None;
+    }
+
+    let (domains, excluded_domains) = parse_domains(domains, ',');
+
+    Some(ElementHidingFilter {
+        selector: selector.to_string(),
+        is_exception,
+        domains,
+        excluded_domains,
+    })
+}
+
+pub fn parse_network_filter(line: &str) -> Option<NetworkFilter> {
+    let (line, is_exception): (&str, bool) = match line.strip_prefix("@@") {
+        Some(line) => (line, true),
+        None => (line, false),
+    };
+    let is_regex = |pattern: &str| -> bool {
+        pattern.len() > 2 && pattern.starts_with('/') && pattern.ends_with('/')
+    };
+
+    let mut network_filter: NetworkFilter = NetworkFilter {
+        pattern: FilterPattern::Wildcard {
+            pattern: "".to_string(),
+            host_anchor: false,
+            start_anchor: false,
+            end_anchor: false,
+        },
+        is_exception,
+        is_important: false,
+        match_case: false,
+        third_party: None,
+        resource_types: None,
+        excluded_resource_types: vec![],
+        domains: vec![],
+        excluded_domains: vec![],
+    };
+
+    // Regular expressions may contain dollar signs too
+    let pattern: &str = match line.rsplit_once('$') {
+        Some((pattern, options)) if !is_regex(line) || is_regex(pattern) => {
+            for option in options.split(',') {
+                let option: &str = option.trim();
+                let (option, is_negated): (&str, bool) = match option.strip_prefix('~') {
+                    Some(option) => (option, true),
+                    None => (option, false),
+                };
+                let resource_type: Option<ResourceType> = match option {
+                    "font" => Some(ResourceType::Font),
+                    "image" => Some(ResourceType::Image),
+                    "media" => Some(ResourceType::Media),
+                    "object" => Some(ResourceType::Object),
+                    "other" => Some(ResourceType::Other),
+                    "script" => Some(ResourceType::Script),
+                    "css" | "stylesheet" => Some(ResourceType::Stylesheet),
+                    "frame" | "subdocument" => Some(ResourceType::Subdocument),
+                    _ => None,
+                };
+
+                match (option, resource_type) {
+                    (_, Some(resource_type)) if is_negated => {
+                        network_filter.excluded_resource_types.push(resource_type);
+                    }
+                    (_, Some(resource_type)) => {
+                        network_filter
+                            .resource_types
+                            .get_or_insert_with(Vec::new)
+                            .push(resource_type);
+                    }
+                    (_, None) if IGNORED_RESOURCE_TYPES.contains(&option) => {
+                        if !is_negated {
+                            network_filter.resource_types.get_or_insert_with(Vec::new);
+                        }
+                    }
+                    ("3p" | "third-party", _) => network_filter.third_party = Some(!is_negated),
+                    ("1p" | "first-party", _) => network_filter.third_party = Some(is_negated),
+                    ("important", _) => network_filter.is_important = true,
+                    ("match-case", _) => network_filter.match_case = true,
+                    _ => {
+                        if let Some(domains) = option
+                            .strip_prefix("domain=")
+                            .or_else(|| option.strip_prefix("from="))
+                        {
+                            let (domains, excluded_domains) = parse_domains(domains, '|');
+                            network_filter.domains = domains;
+                            network_filter.excluded_domains = excluded_domains;
+                        } else {
+                            // Filters with options which can't be honored get skipped entirely
+                            return None;
+                        }
+                    }
+                }
+            }
+
+            pattern
+        }
+        _ => line,
+    };
+
+    if is_regex(pattern) {
+        // URLs consist of ASCII characters only
+        network_filter.pattern = FilterPattern::Regex(
+            RegexBuilder::new(&pattern[1..pattern.len() - 1])
+                .unicode(false)
+                .case_insensitive(!network_filter.match_case)
+                .build()
+                .ok()?,
+        );
+    } else {
+        let mut pattern: &str = pattern;
+        let host_anchor: bool = pattern.starts_with("||");
+        let start_anchor: bool = !host_anchor && pattern.starts_with('|');
+        let end_anchor: bool = pattern.len() > 1 && pattern.ends_with('|');
+        pattern = pattern.trim_start_matches('|').trim_end_matches('|');
+
+        // Filters which match everything would block all requests
+        if pattern.trim_matches('*').is_empty() && !host_anchor {
+            return None;
+        }
+
+        let mut pattern: String = if network_filter.match_case {
+            pattern.to_string()
+        } else {
+            pattern.to_lowercase()
+        };
+        while pattern.contains("**") {
+            pattern = pattern.replace("**", "*");
+        }
+
+        network_filter.pattern = FilterPattern::Wildcard {
+            pattern,
+            host_anchor,
+            start_anchor,
+            end_anchor,
+        };
+    }
+
+    Some(network_filter)
+}
+
+pub fn parse_url_rule(rule: &str, is_exclusion: bool) -> Option<UrlRule> {
+    let mut resource_type: Option<ResourceType> = None;
+    let mut pattern: &str = rule.trim();
+
+    if let Some((prefix, rest)) = pattern.split_once(':') {
+        resource_type = match prefix {
+            "css" => Some(ResourceType::Stylesheet),
+            "font" => Some(ResourceType::Font),
+            "frame" => Some(ResourceType::Subdocument),
+            "image" => Some(ResourceType::Image),
+            "media" => Some(ResourceType::Media),
+            "script" => Some(ResourceType::Script),
+            _ => None,
+        };
+
+        if resource_type.is_some() {
+            pattern = rest;
+        } else if prefix != "regex"
+            && !rest.starts_with("//")
+            && prefix.bytes().all(|c| c.is_ascii_lowercase())
+        {
+            // Unknown asset type (prefixes followed by slashes are URL schemes)
+            return None;
+        }
+    }
+
+    let pattern: UrlPattern = match pattern.strip_prefix("regex:") {
+        Some(regex) => UrlPattern::Regex(RegexBuilder::new(regex).unicode(false).build().ok()?),
+        None if !pattern.is_empty() => UrlPattern::Glob(pattern.to_string()),
+        None => return None,
+    };
+
+    Some(UrlRule {
+        pattern,
+        is_exclusion,
+        resource_type,
+    })
+}
+
+pub fn rules_allow_url(rules: &[UrlRule], url: &Url, resource_type: Option<ResourceType>) -> bool {
+    // Exclusion rules take precedence over inclusion ones
+    if rules
+        .iter()
+        .any(|rule| rule.is_exclusion && rule.matches(url, resource_type))
+    {
+        return false;
+    }
+
+    // Once there are inclusion rules for the type of asset, it has to match one of them
+    let mut inclusion_rules = rules.iter().filter(|rule| {
+        !rule.is_exclusion && (rule.resource_type.is_none() || rule.resource_type == resource_type)
+    });
+
+    inclusion_rules.clone().next().is_none()
+        || inclusion_rules.any(|rule| rule.matches(url, resource_type))
+}
+
+fn glob_matches(pattern: &[u8], text: &[u8]) -> bool {
+    match pattern.first() {
+        None => text.is_empty(),
+        Some(b'*') => (0..=text.len()).any(|i| glob_matches(&pattern[1..], &text[i..])),
+        Some(c) => text.first() == Some(c) && glob_matches(&pattern[1..], &text[1..]),
+    }
+}
+
+fn host_is_within_domain(host: &str, domain: &str) -> bool {
+    let host: &str = host.trim_end_matches('.');
+
+    // Wildcard top-level domains (e.g. "example.*") match any of them
+    if let Some(domain) = domain.strip_suffix(".*") {
+        return host
+            .rsplit_once('.')
+            .is_some_and(|(host, _)| host_is_within_domain(host, domain));
+    }
+
+    host.eq_ignore_ascii_case(domain)
+        || (host.len() > domain.len()
+            && host.as_bytes()[host.len() - domain.len() - 1] == b'.'
+            && host[host.len() - domain.len()..].eq_ignore_ascii_case(domain))
+}
+
+fn is_separator(c: u8) -> bool {
+    !(c.is_ascii_alphanumeric() || [b'_', b'-', b'.', b'%'].contains(&c))
+}
+
+fn parse_domains(domains: &str, separator: char) -> (Vec<String>, Vec<String>) {
+    let mut included_domains: Vec<String> = vec![];
+    let mut excluded_domains: Vec<String> = vec![];
+
+    for domain in domains.split(separator) {
+        let domain: String = domain.trim().to_lowercase();
+
+        match domain.strip_prefix('~') {
+            Some(domain) if !domain.is_empty() => excluded_domains.push(domain.to_string()),
+            None if !domain.is_empty() => included_domains.push(domain),
+            _ => {}
+        }
+    }
+
+    (included_domains, excluded_domains)
+}
+
+fn wildcard_matches(pattern: &[u8], text: &[u8], end_anchor: bool) -> bool {
+    match pattern.first() {
+        None => !end_anchor || text.is_empty(),
+        Some(b'*') => {
+            (0..=text.len()).any(|i| wildcard_matches(&pattern[1..], &text[i..], end_anchor))
+        }
+        // Separators also match the end of URLs
+        Some(b'^') => match text.first() {
+            None => wildcard_matches(&pattern[1..], text, end_anchor),
+            Some(c) if is_separator(*c) => wildcard_matches(&pattern[1..], &text[1..], end_anchor),
+            Some(_) => false,
+        },
+        Some(c) => {
+            text.first() == Some(c) && wildcard_matches(&pattern[1..], &text[1..], end_anchor)
+        }
+    }
+}
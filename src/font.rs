@@ -0,0 +1,38 @@
+use std::collections::HashSet;
+
+use allsorts::binary::read::ReadScope;
+use allsorts::font::MatchingPresentation;
+use allsorts::font_data::FontData;
+use allsorts::subset::subset;
+use allsorts::tables::FontTableProvider;
+use allsorts::tag;
+use allsorts::Font;
+
+pub fn subset_font(data: &[u8], chars: &HashSet<char>) -> Option<(Vec<u8>, &'static str)> {
+    let font_data: FontData = ReadScope::new(data).read::<FontData>().ok()?;
+    let provider = font_data.table_provider(0).ok()?;
+    let media_type: &str = if provider.has_table(tag::CFF) {
+        "font/otf"
+    } else {
+        "font/ttf"
+    };
+    let mut font = Font::new(provider).ok()?;
+
+    // Glyph 0 (.notdef) has to be present in every font
+    let mut glyph_ids: Vec<u16> = vec![0];
+    for c in chars {
+        let (glyph_id, _) = font.lookup_glyph_index(*c, MatchingPresentation::NotRequired, None);
+        glyph_ids.push(glyph_id);
+    }
+    glyph_ids.sort_unstable();
+    glyph_ids.dedup();
+
+    let subset_data: Vec<u8> = subset(&font.font_table_provider, &glyph_ids).ok()?;
+
+    // Subsets don't get compressed, keep the original font if it's still smaller
+    if subset_data.len() >= data.len() {
+        return None;
+    }
+
+    Some((subset_data, media_type))
+}
@@ -9,18 +9,55 @@ use html5ever::tree_builder::{create_element, TreeSink};
 use html5ever::{namespace_url, ns, LocalName};
 use markup5ever_rcdom::{Handle, NodeData, RcDom, SerializableHandle};
 use regex::Regex;
+use serde_json::Value;
 use sha2::{Digest, Sha256, Sha384, Sha512};
+use std::collections::HashSet;
 use std::default::Default;
+use std::rc::Rc;
 
-use crate::core::{parse_content_type, MonolithOptions};
-use crate::css::embed_css;
-use crate::js::attr_is_event_handler;
+use crate::core::{
+    detect_media_type_by_file_name, parse_content_type, print_error_message,
+    IntegrityMismatchPolicy, MonolithOptions,
+};
+use crate::css::{
+    create_stylesheet_data_url, embed_css, embed_stylesheet, format_quoted_string,
+    get_css_references, remove_unreferenced_css_at_rules, remove_unused_css_rules, SelectorSet,
+};
+use crate::filters::ResourceType;
+use crate::js::{attr_is_event_handler, embed_js_module, embed_js_module_url, parse_import_map};
+use crate::sanitize::sanitize_document;
 use crate::session::Session;
+use crate::svg::embed_svg;
 use crate::url::{
-    clean_url, create_data_url, is_url_and_has_protocol, resolve_url, Url, EMPTY_IMAGE_DATA_URL,
+    clean_url, create_data_url, is_tracker_url, is_url_and_has_protocol, parse_data_url,
+    remove_tracking_params, resolve_url, Url, EMPTY_IMAGE_DATA_URL,
 };
 
 const FAVICON_VALUES: &[&str] = &["icon", "shortcut icon"];
+pub const LAZY_LOAD_ATTRS: &[&str] = &[
+    "data-src",
+    "data-lazy-src",
+    "data-original",
+    "data-srcset",
+    "data-lazy-srcset",
+    "data-poster",
+    "data-bg",
+    "data-background",
+];
+const LAZY_LOAD_CLASSES: &[&str] = &["lazy", "lazyload", "lazyloading"];
+const LAZY_LOAD_ELEMENTS: &[&str] = &["audio", "iframe", "img", "source", "video"];
+const MAX_TRACKING_SNIPPET_LEN: usize = 2048; // Analytics snippets are just a few lines long
+const STYLESHEET_LINK_ATTRS_TO_KEEP: &[&str] = &["class", "id", "media", "nonce", "title"];
+// Calls found within inline snippets of common analytics services
+const TRACKING_SCRIPT_SNIPPETS: &[&str] = &[
+    "_gaq.push(",
+    "_paq.push(",
+    "fbq(",
+    "ga('create'",
+    "ga(\"create\"",
+    "GoogleAnalyticsObject",
+    "gtag(",
+];
 const WHITESPACES: &[char] = &[' ', '\t', '\n', '\x0c', '\r']; // ASCII whitespaces
 
 #[derive(PartialEq, Eq)]
@@ -29,6 +66,8 @@ pub enum LinkType {
     AppleTouchIcon,
     DnsPrefetch,
     Favicon,
+    Manifest,
+    ModulePreload,
     Preload,
     Stylesheet,
 }
@@ -72,20 +111,25 @@ pub fn add_favicon(document: &Handle, favicon_data_url: String) -> RcDom {
 }
 
 pub fn check_integrity(data: &[u8], integrity: &str) -> bool {
-    if integrity.starts_with("sha256-") {
-        let mut hasher = Sha256::new();
-        hasher.update(data);
-        BASE64_STANDARD.encode(hasher.finalize()) == integrity[7..]
-    } else if integrity.starts_with("sha384-") {
-        let mut hasher = Sha384::new();
-        hasher.update(data);
-        BASE64_STANDARD.encode(hasher.finalize()) == integrity[7..]
-    } else if integrity.starts_with("sha512-") {
-        let mut hasher = Sha512::new();
-        hasher.update(data);
-        BASE64_STANDARD.encode(hasher.finalize()) == integrity[7..]
-    } else {
-        false
+    // Spec: https://w3c.github.io/webappsec-subresource-integrity/#does-response-match-metadatalist
+    let hashes: Vec<(&str, &str)> = integrity
+        .split_whitespace()
+        .filter_map(|token| token.split_once('-'))
+        .map(|(algorithm, hash)| (algorithm, hash.split('?').next().unwrap_or_default()))
+        .collect();
+
+    // Only hashes made with the strongest of listed algorithms matter, any of them may match
+    match ["sha512", "sha384", "sha256"]
+        .into_iter()
+        .find(|strongest| hashes.iter().any(|(algorithm, _)| algorithm == strongest))
+    {
+        Some(strongest) => {
+            let data_hash: String = compute_integrity(data, strongest).unwrap();
+            hashes
+                .iter()
+                .any(|(algorithm, hash)| *algorithm == strongest && *hash == data_hash)
+        }
+        None => false,
     }
 }
 
@@ -121,6 +165,60 @@ pub fn compose_csp(options: &MonolithOptions) -> String {
     string_list.join(" ")
 }
 
+pub fn compute_integrity(data: &[u8], algorithm: &str) -> Option<String> {
+    match algorithm {
+        "sha256" => Some(BASE64_STANDARD.encode(Sha256::digest(data))),
+        "sha384" => Some(BASE64_STANDARD.encode(Sha384::digest(data))),
+        "sha512" => Some(BASE64_STANDARD.encode(Sha512::digest(data))),
+        _ => None,
+    }
+}
+
+pub fn can_inline_stylesheet(node: &Handle) -> bool {
+    // STYLE elements can't be disabled or serve as alternate stylesheets, such LINKs stay as they are
+    get_node_attr(node, "disabled").is_none()
+        && !parse_link_type(&get_node_attr(node, "rel").unwrap_or_default())
+            .contains(&LinkType::Alternate)
+}
+
+pub fn convert_stylesheet_links(node: &Handle) {
+    let mut children = node.children.borrow_mut();
+
+    for child_node in children.iter_mut() {
+        convert_stylesheet_links(child_node);
+
+        if get_node_name(child_node) != Some("link") {
+            continue;
+        }
+
+        // Only LINKs which had their stylesheets embedded as text contain child nodes
+        let text_node: Handle = match child_node.children.borrow().first() {
+            Some(text_node) => text_node.clone(),
+            None => continue,
+        };
+
+        let mut attrs: Vec<Attribute> = vec![];
+        for attr_name in STYLESHEET_LINK_ATTRS_TO_KEEP {
+            if let Some(attr_value) = get_node_attr(child_node, attr_name) {
+                attrs.push(Attribute {
+                    name: QualName::new(None, ns!(), LocalName::from(*attr_name)),
+                    value: format_tendril!("{}", attr_value),
+                });
+            }
+        }
+        let style_node: Handle = create_element(
+            &RcDom::default(),
+            QualName::new(None, ns!(html), LocalName::from("style")),
+            attrs,
+        );
+        text_node.parent.set(Some(Rc::downgrade(&style_node)));
+        style_node.children.borrow_mut().push(text_node);
+        style_node.parent.set(Some(Rc::downgrade(node)));
+
+        *child_node = style_node;
+    }
+}
+
 pub fn create_metadata_tag(url: &Url) -> String {
     let datetime: &str = &Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true);
     let mut clean_url: Url = clean_url(url.clone());
@@ -145,6 +243,76 @@ pub fn create_metadata_tag(url: &Url) -> String {
     )
 }
 
+pub fn embed_import_map(session: &mut Session, document_url: &Url, import_map: &str) -> String {
+    // Remember the import map, it's needed for resolving module specifiers later on
+    session.import_map = Some(parse_import_map(import_map, document_url));
+
+    let mut json: Value = match serde_json::from_str(import_map) {
+        Ok(json) => json,
+        Err(_) => return import_map.to_string(),
+    };
+
+    // Replace addresses of mapped modules with data URLs
+    fn embed_specifier_map(session: &mut Session, document_url: &Url, specifier_map: &mut Value) {
+        if let Some(entries) = specifier_map.as_object_mut() {
+            for (specifier_key, address) in entries.iter_mut() {
+                // Prefix mappings must keep pointing to actual locations
+                if specifier_key.ends_with('/') {
+                    continue;
+                }
+
+                if let Some(address_value) = address.as_str() {
+                    let module_url: Url = resolve_url(document_url, address_value);
+                    *address =
+                        Value::String(embed_js_module_url(session, document_url, &module_url));
+                }
+            }
+        }
+    }
+
+    if let Some(imports) = json.get_mut("imports") {
+        embed_specifier_map(session, document_url, imports);
+    }
+    if let Some(scopes) = json.get_mut("scopes").and_then(|s| s.as_object_mut()) {
+        for scope_imports in scopes.values_mut() {
+            embed_specifier_map(session, document_url, scope_imports);
+        }
+    }
+
+    serde_json::to_string(&json).unwrap_or(import_map.to_string())
+}
+
+pub fn embed_remote_reference(node: &Handle, attr_name: &str, url: &Url) {
+    if url.scheme() == "http" || url.scheme() == "https" {
+        // Keep remote references if unable to retrieve the asset
+        set_node_attr(node, attr_name, Some(url.to_string()));
+    } else {
+        // Remove local references if they can't be successfully embedded as data URLs
+        set_node_attr(node, attr_name, None);
+    }
+}
+
+pub fn embed_srcdoc(session: &mut Session, document_url: &Url, srcdoc: &str) -> Option<String> {
+    let srcdoc_url: Url = Url::parse("about:srcdoc").unwrap();
+
+    if !session.can_enter_frame(&srcdoc_url) {
+        return None;
+    }
+
+    // Documents within srcdoc attributes share base URL with their parent document
+    let srcdoc_dom: RcDom = html_to_dom(&srcdoc.as_bytes().to_vec(), "utf-8".to_string());
+    session.enter_frame(&srcdoc_url);
+    walk(session, document_url, &srcdoc_dom.document);
+    session.exit_frame();
+
+    let mut buf: Vec<u8> = Vec::new();
+    let serializable: SerializableHandle = srcdoc_dom.document.into();
+    serialize(&mut buf, &serializable, SerializeOpts::default())
+        .expect("Unable to serialize DOM into buffer");
+
+    Some(String::from_utf8_lossy(&buf).to_string())
+}
+
 pub fn embed_srcset(session: &mut Session, document_url: &Url, srcset: &str) -> String {
     let srcset_items: Vec<SrcSetItem> = parse_srcset(srcset);
 
@@ -155,9 +323,17 @@ pub fn embed_srcset(session: &mut Session, document_url: &Url, srcset: &str) ->
         if session.options.no_images {
             result.push_str(EMPTY_IMAGE_DATA_URL);
         } else {
-            let image_full_url: Url = resolve_url(document_url, srcset_item.path);
-            match session.retrieve_asset(document_url, &image_full_url) {
+            let mut image_full_url: Url = resolve_url(document_url, srcset_item.path);
+            if session.options.no_tracking {
+                image_full_url = remove_tracking_params(image_full_url);
+            }
+            match session.retrieve_asset(document_url, &image_full_url, Some(ResourceType::Image)) {
                 Ok((image_data, image_final_url, image_media_type, image_charset)) => {
+                    let image_data: Vec<u8> = if image_media_type == "image/svg+xml" {
+                        embed_svg(session, &image_final_url, &image_data)
+                    } else {
+                        image_data
+                    };
                     let mut image_data_url = create_data_url(
                         &image_media_type,
                         &image_charset,
@@ -266,6 +442,13 @@ pub fn get_child_node_by_name(parent: &Handle, node_name: &str) -> Option<Handle
     matching_children.cloned()
 }
 
+pub fn get_lazy_load_attrs(options: &MonolithOptions) -> Vec<String> {
+    options
+        .lazy_load_attrs
+        .clone()
+        .unwrap_or(LAZY_LOAD_ATTRS.iter().map(|a| a.to_string()).collect())
+}
+
 pub fn get_node_attr(node: &Handle, attr_name: &str) -> Option<String> {
     match &node.data {
         NodeData::Element { attrs, .. } => {
@@ -287,11 +470,60 @@ pub fn get_node_name(node: &Handle) -> Option<&'_ str> {
     }
 }
 
+pub fn get_next_sibling_element(node: &Handle) -> Option<Handle> {
+    let parent_node = get_parent_node(node);
+    let siblings = parent_node.children.borrow();
+    let position = siblings
+        .iter()
+        .position(|sibling| Rc::ptr_eq(sibling, node))?;
+
+    for sibling in siblings.iter().skip(position + 1) {
+        match &sibling.data {
+            NodeData::Element { .. } => return Some(sibling.clone()),
+            NodeData::Text { contents } if contents.borrow().trim().is_empty() => continue,
+            NodeData::Comment { .. } => continue,
+            _ => return None,
+        }
+    }
+
+    None
+}
+
+pub fn get_parent_element(node: &Handle) -> Option<Handle> {
+    let parent = node.parent.take();
+    node.parent.set(parent.clone());
+    let parent_node: Handle = parent.and_then(|node| node.upgrade())?;
+
+    match parent_node.data {
+        NodeData::Element { .. } => Some(parent_node),
+        _ => None,
+    }
+}
+
 pub fn get_parent_node(child: &Handle) -> Handle {
-    let parent = child.parent.take().clone();
+    let parent = child.parent.take();
+    // Put the reference back, otherwise the child gets detached from its parent
+    child.parent.set(parent.clone());
     parent.and_then(|node| node.upgrade()).unwrap()
 }
 
+pub fn get_previous_sibling_element(node: &Handle) -> Option<Handle> {
+    let parent = node.parent.take();
+    node.parent.set(parent.clone());
+    let parent_node: Handle = parent.and_then(|node| node.upgrade())?;
+    let siblings = parent_node.children.borrow();
+    let position = siblings
+        .iter()
+        .position(|sibling| Rc::ptr_eq(sibling, node))?;
+
+    siblings
+        .iter()
+        .take(position)
+        .rev()
+        .find(|sibling| matches!(sibling.data, NodeData::Element { .. }))
+        .cloned()
+}
+
 pub fn get_robots(handle: &Handle) -> Option<String> {
     for meta_node in find_nodes(handle, vec!["html", "head", "meta"]).iter() {
         // Only the first base tag matters (we ignore the rest, if there's any)
@@ -306,6 +538,38 @@ pub fn get_robots(handle: &Handle) -> Option<String> {
     None
 }
 
+pub fn get_text(node: &Handle) -> String {
+    let mut text: String = "".to_string();
+
+    match node.data {
+        NodeData::Text { ref contents } => text.push_str(&contents.borrow()),
+        NodeData::Element { ref name, .. } => {
+            if matches!(name.local.as_ref(), "script" | "style" | "template") {
+                return text;
+            }
+
+            // Text of these attributes gets rendered too
+            for attr_name in ["alt", "placeholder", "value"] {
+                if let Some(attr_value) = get_node_attr(node, attr_name) {
+                    text.push_str(&attr_value);
+                }
+            }
+
+            for child_node in node.children.borrow().iter() {
+                text.push_str(&get_text(child_node));
+            }
+        }
+        NodeData::Document => {
+            for child_node in node.children.borrow().iter() {
+                text.push_str(&get_text(child_node));
+            }
+        }
+        _ => {}
+    }
+
+    text
+}
+
 pub fn get_title(node: &Handle) -> Option<String> {
     for title_node in find_nodes(node, vec!["html", "head", "title"]).iter() {
         for child_node in title_node.children.borrow().iter() {
@@ -349,10 +613,103 @@ pub fn html_to_dom(data: &Vec<u8>, document_encoding: String) -> RcDom {
         .unwrap()
 }
 
+pub fn is_excluded_media_type(options: &MonolithOptions, media_type: &str) -> bool {
+    let media_type: &str = &media_type.trim().to_lowercase();
+
+    (options.no_audio && media_type.starts_with("audio/"))
+        || (options.no_images && media_type.starts_with("image/"))
+        || (options.no_video && media_type.starts_with("video/"))
+        || (options.no_frames
+            && (media_type == "text/html" || media_type == "application/xhtml+xml"))
+}
+
 pub fn is_favicon(attr_value: &str) -> bool {
     FAVICON_VALUES.contains(&attr_value.to_lowercase().as_str())
 }
 
+pub fn is_tracking_element(node: &Handle, document_url: &Url) -> bool {
+    match get_node_name(node) {
+        Some("script") => {
+            if let Some(script_attr_src) = get_node_attr(node, "src") {
+                return is_tracker_url(&resolve_url(document_url, script_attr_src.trim()));
+            }
+
+            // Inline snippets which load analytics scripts or report to analytics services
+            let script_attr_type: String = get_node_attr(node, "type").unwrap_or_default();
+            if !script_attr_type.is_empty()
+                && !script_attr_type.eq_ignore_ascii_case("module")
+                && !script_attr_type.to_lowercase().contains("javascript")
+            {
+                return false;
+            }
+            node.children.borrow().iter().any(|child_node| {
+                if let NodeData::Text { ref contents } = child_node.data {
+                    let script: &str = &contents.borrow();
+
+                    // Larger scripts are application code which may merely mention analytics
+                    if script.len() > MAX_TRACKING_SNIPPET_LEN {
+                        return false;
+                    }
+
+                    TRACKING_SCRIPT_SNIPPETS
+                        .iter()
+                        .any(|snippet| script.contains(snippet))
+                        || script
+                            .split(['"', '\'', '`'])
+                            .map(str::trim)
+                            .filter(|string| string.starts_with("//") || string.starts_with("http"))
+                            .filter_map(|string| document_url.join(string).ok())
+                            .any(|url| is_tracker_url(&url))
+                } else {
+                    false
+                }
+            })
+        }
+        Some("img") => {
+            let Some(img_attr_src) = get_node_attr(node, "src") else {
+                return false;
+            };
+            let img_url: Url = resolve_url(document_url, img_attr_src.trim());
+            // Remote images sized 1x1 (or less) are only there to report visits
+            let is_pixel: bool = ["height", "width"].iter().all(|attr_name| {
+                matches!(
+                    get_node_attr(node, attr_name).as_deref().map(str::trim),
+                    Some("0" | "1")
+                )
+            });
+
+            is_tracker_url(&img_url)
+                || (is_pixel && (img_url.scheme() == "http" || img_url.scheme() == "https"))
+        }
+        _ => false,
+    }
+}
+
+pub fn parse_html_document(data: &[u8], charset: &str) -> (RcDom, String) {
+    let data: Vec<u8> = data.to_vec();
+
+    // Charsets specified by protocol take precedence over ones declared within documents,
+    // except for US-ASCII which is merely implied by data URLs
+    if Encoding::for_label_no_replacement(charset.as_bytes()).is_some()
+        && !charset.eq_ignore_ascii_case("US-ASCII")
+    {
+        return (html_to_dom(&data, charset.to_string()), charset.to_string());
+    }
+
+    let dom: RcDom = html_to_dom(&data, "utf-8".to_string());
+
+    // Re-parse documents which declare a different charset
+    if let Some(html_charset) = get_charset(&dom.document) {
+        if let Some(html_encoding) = Encoding::for_label_no_replacement(html_charset.as_bytes()) {
+            if html_encoding != encoding_rs::UTF_8 {
+                return (html_to_dom(&data, html_charset.clone()), html_charset);
+            }
+        }
+    }
+
+    (dom, "utf-8".to_string())
+}
+
 pub fn parse_link_type(link_attr_rel_value: &str) -> Vec<LinkType> {
     let mut types: Vec<LinkType> = vec![];
 
@@ -361,6 +718,10 @@ pub fn parse_link_type(link_attr_rel_value: &str) -> Vec<LinkType> {
             types.push(LinkType::Alternate);
         } else if link_attr_rel_type.eq_ignore_ascii_case("dns-prefetch") {
             types.push(LinkType::DnsPrefetch);
+        } else if link_attr_rel_type.eq_ignore_ascii_case("manifest") {
+            types.push(LinkType::Manifest);
+        } else if link_attr_rel_type.eq_ignore_ascii_case("modulepreload") {
+            types.push(LinkType::ModulePreload);
         } else if link_attr_rel_type.eq_ignore_ascii_case("preload") {
             types.push(LinkType::Preload);
         } else if link_attr_rel_type.eq_ignore_ascii_case("stylesheet") {
@@ -375,6 +736,18 @@ pub fn parse_link_type(link_attr_rel_value: &str) -> Vec<LinkType> {
     types
 }
 
+pub fn parse_metadata_tag(html: &str) -> Option<(String, String)> {
+    // Metadata comments created by create_metadata_tag() are placed at the very top of documents
+    let comment: &str = html
+        .trim_start_matches(['\u{feff}', ' ', '\t', '\n', '\x0c', '\r'])
+        .strip_prefix("<!-- Saved from ")?;
+    let comment: &str = &comment[..comment.find("-->")?];
+    let (source, rest) = comment.split_once(" at ")?;
+    let datetime: &str = rest.split_whitespace().next()?;
+
+    Some((source.to_string(), datetime.to_string()))
+}
+
 pub fn parse_srcset(srcset: &str) -> Vec<SrcSetItem> {
     let mut srcset_items: Vec<SrcSetItem> = vec![];
 
@@ -442,6 +815,276 @@ pub fn parse_srcset(srcset: &str) -> Vec<SrcSetItem> {
     srcset_items
 }
 
+pub fn regenerate_integrity(data: &[u8], integrity: &str) -> Option<String> {
+    let mut algorithms: Vec<&str> = vec![];
+    let mut hashes: Vec<String> = vec![];
+
+    // Stick to algorithms the document itself chose, ignoring ones no browser supports
+    for (algorithm, _) in integrity
+        .split_whitespace()
+        .filter_map(|token| token.split_once('-'))
+    {
+        if algorithms.contains(&algorithm) {
+            continue;
+        }
+        algorithms.push(algorithm);
+
+        if let Some(hash) = compute_integrity(data, algorithm) {
+            hashes.push(format!("{}-{}", algorithm, hash));
+        }
+    }
+
+    if hashes.is_empty() {
+        None
+    } else {
+        Some(hashes.join(" "))
+    }
+}
+
+pub fn remove_hidden_elements(node: &Handle, hidden_selectors: &SelectorSet) {
+    fn collect_hidden_elements(
+        node: &Handle,
+        hidden_selectors: &SelectorSet,
+        elements: &mut Vec<Handle>,
+    ) {
+        for child_node in node.children.borrow().iter() {
+            if let NodeData::Element { .. } = child_node.data {
+                if hidden_selectors.matches(child_node) {
+                    elements.push(child_node.clone());
+                    continue;
+                }
+            }
+            collect_hidden_elements(child_node, hidden_selectors, elements);
+        }
+    }
+
+    // Elements get matched first, removing them right away would affect sibling combinators
+    let mut hidden_elements: Vec<Handle> = vec![];
+    collect_hidden_elements(node, hidden_selectors, &mut hidden_elements);
+
+    for hidden_element in hidden_elements {
+        get_parent_node(&hidden_element)
+            .children
+            .borrow_mut()
+            .retain(|child_node| !Rc::ptr_eq(child_node, &hidden_element));
+    }
+}
+
+pub fn remove_tracking_elements(node: &Handle, document_url: &Url, lazy_load_attrs: &[String]) {
+    // Pixels may hide their URLs within lazy-loading attributes
+    for child_node in node.children.borrow().iter() {
+        resolve_lazy_loading(child_node, lazy_load_attrs);
+    }
+
+    node.children
+        .borrow_mut()
+        .retain(|child_node| !is_tracking_element(child_node, document_url));
+
+    for child_node in node.children.borrow().iter() {
+        remove_tracking_elements(child_node, document_url, lazy_load_attrs);
+    }
+}
+
+pub fn remove_unused_css(node: &Handle) {
+    fn collect_elements(node: &Handle, elements: &mut Vec<Handle>) {
+        if let NodeData::Element { .. } = node.data {
+            elements.push(node.clone());
+        }
+        for child_node in node.children.borrow().iter() {
+            collect_elements(child_node, elements);
+        }
+    }
+
+    let mut elements: Vec<Handle> = vec![];
+    collect_elements(node, &mut elements);
+
+    // Drop style rules first, fonts and animations may be only referenced by those rules
+    let mut stylesheets: Vec<(Handle, String)> = vec![];
+    for element in &elements {
+        let css: String = match get_node_name(element) {
+            Some("style") => element
+                .children
+                .borrow()
+                .iter()
+                .filter_map(|child_node| match &child_node.data {
+                    NodeData::Text { contents } => Some(contents.borrow().to_string()),
+                    _ => None,
+                })
+                .collect(),
+            Some("link") => match Url::parse(&get_node_attr(element, "href").unwrap_or_default()) {
+                Ok(href_url) if href_url.scheme() == "data" => {
+                    String::from_utf8_lossy(&parse_data_url(&href_url).2).to_string()
+                }
+                _ => continue,
+            },
+            _ => continue,
+        };
+
+        stylesheets.push((element.clone(), remove_unused_css_rules(&css, &elements)));
+    }
+
+    let mut font_families: Vec<String> = vec![];
+    let mut animation_names: HashSet<String> = HashSet::new();
+    for (_, css) in &stylesheets {
+        get_css_references(css, &mut font_families, &mut animation_names);
+    }
+    for element in &elements {
+        if let Some(style) = get_node_attr(element, "style") {
+            get_css_references(&style, &mut font_families, &mut animation_names);
+        }
+        for attr_name in ["face", "font-family"] {
+            if let Some(font_family) = get_node_attr(element, attr_name) {
+                font_families.push(font_family.to_lowercase());
+            }
+        }
+    }
+
+    for (element, css) in stylesheets {
+        let css: String = remove_unreferenced_css_at_rules(&css, &font_families, &animation_names);
+
+        if get_node_name(&element) == Some("style") {
+            let mut is_first_text_node: bool = true;
+
+            for child_node in element.children.borrow().iter() {
+                if let NodeData::Text { ref contents } = child_node.data {
+                    let mut tendril = contents.borrow_mut();
+                    tendril.clear();
+                    if is_first_text_node {
+                        tendril.push_slice(&css);
+                        is_first_text_node = false;
+                    }
+                }
+            }
+        } else {
+            let href_url: Url = Url::parse(&get_node_attr(&element, "href").unwrap()).unwrap();
+            let (media_type, charset, _) = parse_data_url(&href_url);
+            let css_data_url: Url =
+                create_data_url(&media_type, &charset, css.as_bytes(), &href_url);
+            set_node_attr(&element, "href", Some(css_data_url.to_string()));
+
+            // Hashes of kept integrity attributes have to match what's left of the stylesheet
+            if let Some(integrity) = get_node_attr(&element, "integrity") {
+                set_node_attr(
+                    &element,
+                    "integrity",
+                    regenerate_integrity(&parse_data_url(&css_data_url).2, &integrity),
+                );
+            }
+        }
+    }
+}
+
+pub fn resolve_lazy_loading(node: &Handle, lazy_load_attrs: &[String]) {
+    let node_name: &str = get_node_name(node).unwrap_or_default();
+    let mut is_lazy: bool = false;
+    let mut found_lazy_src: bool = false;
+
+    // Move URLs out of lazy-loading attributes into the ones browsers actually read
+    for lazy_load_attr in lazy_load_attrs {
+        let lazy_load_attr: &str = &lazy_load_attr.trim().to_lowercase();
+
+        if let Some(lazy_load_attr_value) = get_node_attr(node, lazy_load_attr) {
+            let lazy_load_attr_value: &str = lazy_load_attr_value.trim();
+
+            if lazy_load_attr_value.is_empty() {
+                continue;
+            }
+
+            if lazy_load_attr.ends_with("-bg") || lazy_load_attr.ends_with("background") {
+                // Background images can be set on any element, turn them into inline CSS
+                let style: String = get_node_attr(node, "style").unwrap_or_default();
+                let background_image: String = if lazy_load_attr_value.starts_with("url(") {
+                    lazy_load_attr_value.to_string()
+                } else {
+                    format!("url({})", format_quoted_string(lazy_load_attr_value))
+                };
+                set_node_attr(
+                    node,
+                    "style",
+                    Some(
+                        format!("background-image: {}; {}", background_image, style)
+                            .trim_end()
+                            .to_string(),
+                    ),
+                );
+            } else if !LAZY_LOAD_ELEMENTS.contains(&node_name) {
+                continue;
+            } else if lazy_load_attr.ends_with("srcset") {
+                set_node_attr(node, "srcset", Some(lazy_load_attr_value.to_string()));
+            } else if lazy_load_attr.ends_with("poster") {
+                if node_name != "video" {
+                    continue;
+                }
+                set_node_attr(node, "poster", Some(lazy_load_attr_value.to_string()));
+            } else {
+                set_node_attr(node, "src", Some(lazy_load_attr_value.to_string()));
+                found_lazy_src = true;
+            }
+
+            set_node_attr(node, lazy_load_attr, None);
+            is_lazy = true;
+        }
+    }
+
+    // Remove native lazy-loading hint
+    if get_node_attr(node, "loading")
+        .unwrap_or_default()
+        .eq_ignore_ascii_case("lazy")
+    {
+        set_node_attr(node, "loading", None);
+        is_lazy = true;
+    }
+
+    // Mark elements handled by lazy-loading libraries as already loaded
+    if let Some(class_attr_value) = get_node_attr(node, "class") {
+        let classes: Vec<&str> = class_attr_value.split_whitespace().collect();
+
+        if classes.iter().any(|c| LAZY_LOAD_CLASSES.contains(c)) {
+            let mut new_classes: Vec<&str> = classes
+                .into_iter()
+                .filter(|c| !LAZY_LOAD_CLASSES.contains(c) && *c != "lazyloaded")
+                .collect();
+            new_classes.push("lazyloaded");
+            set_node_attr(node, "class", Some(new_classes.join(" ")));
+            is_lazy = true;
+        }
+    }
+
+    // Lazy images without a real URL may have one within the NOSCRIPT fallback next to them
+    if node_name == "img" && is_lazy && !found_lazy_src {
+        let img_attr_src_value: String = get_node_attr(node, "src").unwrap_or_default();
+        let img_attr_src_value: &str = img_attr_src_value.trim();
+
+        if img_attr_src_value.is_empty() || img_attr_src_value.starts_with("data:") {
+            if let Some(sibling_node) = get_next_sibling_element(node) {
+                if get_node_name(&sibling_node) == Some("noscript") {
+                    let mut noscript_contents: String = "".to_string();
+                    for child_node in sibling_node.children.borrow().iter() {
+                        if let NodeData::Text { ref contents } = child_node.data {
+                            noscript_contents.push_str(&contents.borrow());
+                        }
+                    }
+
+                    let noscript_dom: RcDom =
+                        html_to_dom(&noscript_contents.as_bytes().to_vec(), "".to_string());
+                    if let Some(fallback_img_node) =
+                        find_nodes(&noscript_dom.document, vec!["html", "body", "img"]).first()
+                    {
+                        for attr_name in ["src", "srcset"] {
+                            if let Some(attr_value) = get_node_attr(fallback_img_node, attr_name) {
+                                set_node_attr(node, attr_name, Some(attr_value));
+                            }
+                        }
+
+                        // The fallback is no longer needed
+                        sibling_node.children.borrow_mut().clear();
+                    }
+                }
+            }
+        }
+    }
+}
+
 pub fn set_base_url(document: &Handle, base_href_value: String) -> RcDom {
     let mut buf: Vec<u8> = Vec::new();
     serialize(
@@ -669,19 +1312,84 @@ pub fn retrieve_and_embed_asset(
     attr_name: &str,
     attr_value: &str,
 ) {
-    let resolved_url: Url = resolve_url(document_url, attr_value);
+    let mut resolved_url: Url = resolve_url(document_url, attr_value);
+    let node_name: &str = get_node_name(node).unwrap();
 
-    match session.retrieve_asset(&document_url.clone(), &resolved_url) {
-        Ok((data, final_url, media_type, charset)) => {
-            let node_name: &str = get_node_name(node).unwrap();
+    if session.options.no_tracking {
+        resolved_url = remove_tracking_params(resolved_url);
+    }
+
+    // Avoid retrieving (I)FRAMEs that would never get embedded
+    if (node_name == "frame" || node_name == "iframe") && !session.can_enter_frame(&resolved_url) {
+        if !session.options.silent {
+            print_error_message(&format!("{} (frame not embedded)", &resolved_url));
+        }
+        embed_remote_reference(node, attr_name, &resolved_url);
+        return;
+    }
 
+    let resource_type: Option<ResourceType> = match node_name {
+        "audio" | "source" | "track" => Some(ResourceType::Media),
+        "embed" | "object" => Some(ResourceType::Object),
+        "frame" | "iframe" => Some(ResourceType::Subdocument),
+        "link" => {
+            let link_node_types: Vec<LinkType> =
+                parse_link_type(&get_node_attr(node, "rel").unwrap_or_default());
+
+            if link_node_types.contains(&LinkType::Stylesheet) {
+                Some(ResourceType::Stylesheet)
+            } else if link_node_types.contains(&LinkType::Favicon)
+                || link_node_types.contains(&LinkType::AppleTouchIcon)
+            {
+                Some(ResourceType::Image)
+            } else {
+                None
+            }
+        }
+        "script" => Some(ResourceType::Script),
+        "video" if attr_name == "src" => Some(ResourceType::Media),
+        // Images, inputs, backgrounds and posters
+        _ => Some(ResourceType::Image),
+    };
+
+    match session.retrieve_asset(&document_url.clone(), &resolved_url, resource_type) {
+        Ok((data, final_url, media_type, charset)) => {
             // Check integrity if it's a LINK or SCRIPT element
             let mut ok_to_include: bool = true;
+            let mut declared_integrity: Option<String> = None;
             if node_name == "link" || node_name == "script" {
                 // Check integrity
                 if let Some(node_integrity_attr_value) = get_node_attr(node, "integrity") {
                     if !node_integrity_attr_value.is_empty() {
-                        ok_to_include = check_integrity(&data, &node_integrity_attr_value);
+                        if !check_integrity(&data, &node_integrity_attr_value) {
+                            if !session.options.silent {
+                                print_error_message(&format!(
+                                    "{} (integrity mismatch)",
+                                    &final_url
+                                ));
+                            }
+
+                            match session.options.integrity_mismatch {
+                                IntegrityMismatchPolicy::Drop => {
+                                    ok_to_include = false;
+                                }
+                                IntegrityMismatchPolicy::Embed => {
+                                    // Leave a trace of what the document expected to get
+                                    set_node_attr(
+                                        node,
+                                        "data-integrity-mismatch",
+                                        Some(node_integrity_attr_value.clone()),
+                                    );
+                                }
+                                IntegrityMismatchPolicy::KeepRemote => {
+                                    // Browsers will get to verify it on their own
+                                    embed_remote_reference(node, attr_name, &resolved_url);
+                                    return;
+                                }
+                            }
+                        }
+
+                        declared_integrity = Some(node_integrity_attr_value);
                     }
 
                     // Wipe the integrity attribute
@@ -694,35 +1402,83 @@ pub fn retrieve_and_embed_asset(
                     && parse_link_type(&get_node_attr(node, "rel").unwrap_or(String::from("")))
                         .contains(&LinkType::Stylesheet)
                 {
-                    let stylesheet: String;
-                    if let Some(encoding) = Encoding::for_label(charset.as_bytes()) {
-                        let (string, _, _) = encoding.decode(&data);
-                        stylesheet = string.to_string();
-                    } else {
-                        stylesheet = String::from_utf8_lossy(&data).to_string();
-                    }
-
                     // Stylesheet LINK elements require special treatment
-                    let css: String = embed_css(session, &final_url, &stylesheet);
+                    let css: String = embed_stylesheet(session, &final_url, &data, &charset);
+
+                    if session.options.inline_stylesheets && can_inline_stylesheet(node) {
+                        // Keep stylesheet within the LINK element until it gets turned into STYLE
+                        let style_dom: RcDom = parse_document(RcDom::default(), Default::default())
+                            .one("<style>;</style>");
+                        for style_node in
+                            find_nodes(&style_dom.document, vec!["html", "head", "style"]).iter()
+                        {
+                            let text_node = &style_node.children.borrow()[0];
+
+                            if let NodeData::Text { ref contents } = text_node.data {
+                                let mut tendril = contents.borrow_mut();
+                                tendril.clear();
+                                tendril.push_slice(&css.replace("</style", "<\\/style"));
+                            }
 
-                    // Create and embed data URL
-                    let css_data_url =
-                        create_data_url(&media_type, &charset, css.as_bytes(), &final_url);
-                    set_node_attr(node, attr_name, Some(css_data_url.to_string()));
-                } else if node_name == "frame" || node_name == "iframe" {
+                            node.children.borrow_mut().push(text_node.clone());
+                            set_node_attr(node, attr_name, None);
+                        }
+                    } else {
+                        // Create and embed data URL
+                        let css_data_url =
+                            create_stylesheet_data_url(&media_type, &css, &final_url);
+                        set_node_attr(node, attr_name, Some(css_data_url.to_string()));
+                    }
+                } else if node_name == "frame"
+                    || node_name == "iframe"
+                    || ((node_name == "object" || node_name == "embed")
+                        && (media_type.eq_ignore_ascii_case("text/html")
+                            || media_type.eq_ignore_ascii_case("application/xhtml+xml")))
+                {
                     // (I)FRAMEs are also quite different from conventional resources
+                    if !session.can_enter_frame(&final_url) {
+                        if !session.options.silent {
+                            print_error_message(&format!("{} (frame not embedded)", &final_url));
+                        }
+                        embed_remote_reference(node, attr_name, &resolved_url);
+                        return;
+                    }
+
                     let frame_dom = html_to_dom(&data, charset.clone());
+                    let frame_encoding: &'static Encoding =
+                        if charset.is_empty() || charset.eq_ignore_ascii_case("US-ASCII") {
+                            get_charset(&frame_dom.document)
+                                .and_then(|charset| Encoding::for_label(charset.as_bytes()))
+                                .unwrap_or(session.get_environment_encoding())
+                        } else {
+                            Encoding::for_label(charset.as_bytes())
+                                .unwrap_or(session.get_environment_encoding())
+                        };
+                    session.enter_frame(&final_url);
+                    session.enter_encoding(frame_encoding);
                     walk(session, &final_url, &frame_dom.document);
+                    session.exit_encoding();
+                    session.exit_frame();
 
                     let mut frame_data: Vec<u8> = Vec::new();
                     let serializable: SerializableHandle = frame_dom.document.into();
                     serialize(&mut frame_data, &serializable, SerializeOpts::default()).unwrap();
 
-                    // Create and embed data URL
-                    let mut frame_data_url =
-                        create_data_url(&media_type, &charset, &frame_data, &final_url);
-                    frame_data_url.set_fragment(resolved_url.fragment());
-                    set_node_attr(node, attr_name, Some(frame_data_url.to_string()));
+                    if node_name == "iframe" && session.options.frames_as_srcdoc {
+                        // Embed as srcdoc to keep the frame same-origin with its parent document
+                        set_node_attr(
+                            node,
+                            "srcdoc",
+                            Some(String::from_utf8_lossy(&frame_data).to_string()),
+                        );
+                        set_node_attr(node, attr_name, None);
+                    } else {
+                        // Create and embed data URL
+                        let mut frame_data_url =
+                            create_data_url(&media_type, &charset, &frame_data, &final_url);
+                        frame_data_url.set_fragment(resolved_url.fragment());
+                        set_node_attr(node, attr_name, Some(frame_data_url.to_string()));
+                    }
                 } else {
                     // Every other type of element gets processed here
 
@@ -731,9 +1487,31 @@ pub fn retrieve_and_embed_asset(
                         let script_media_type =
                             get_node_attr(node, "type").unwrap_or(String::from("text/javascript"));
 
-                        if script_media_type == "text/javascript"
+                        let script_text: Option<String> = if declared_integrity.is_some()
+                            && session.options.keep_integrity
+                            && !script_media_type.eq_ignore_ascii_case("module")
+                        {
+                            // Data URLs keep scripts byte for byte, letting browsers verify them
+                            None
+                        } else if script_media_type == "text/javascript"
                             || script_media_type == "application/javascript"
                         {
+                            Some(String::from_utf8_lossy(&data).to_string())
+                        } else if script_media_type.eq_ignore_ascii_case("module") {
+                            // Modules get their static imports embedded as data URLs
+                            let module_source: String =
+                                if let Some(encoding) = Encoding::for_label(charset.as_bytes()) {
+                                    let (string, _, _) = encoding.decode(&data);
+                                    string.to_string()
+                                } else {
+                                    String::from_utf8_lossy(&data).to_string()
+                                };
+                            Some(embed_js_module(session, &final_url, &module_source))
+                        } else {
+                            None
+                        };
+
+                        if let Some(script_text) = script_text {
                             // Embed javascript code instead of using data URLs
                             let script_dom: RcDom =
                                 parse_document(RcDom::default(), Default::default())
@@ -748,8 +1526,7 @@ pub fn retrieve_and_embed_asset(
                                     let mut tendril = contents.borrow_mut();
                                     tendril.clear();
                                     tendril.push_slice(
-                                        &String::from_utf8_lossy(&data)
-                                            .replace("</script>", "<\\/script>"),
+                                        &script_text.replace("</script>", "<\\/script>"),
                                     );
                                 }
 
@@ -763,6 +1540,18 @@ pub fn retrieve_and_embed_asset(
                             data_url.set_fragment(resolved_url.fragment());
                             set_node_attr(node, attr_name, Some(data_url.to_string()));
                         }
+                    } else if node_name == "track" {
+                        // WebVTT files are always UTF-8 text, regardless of what the server says
+                        let mut data_url = create_data_url("text/vtt", "utf-8", &data, &final_url);
+                        data_url.set_fragment(resolved_url.fragment());
+                        set_node_attr(node, attr_name, Some(data_url.to_string()));
+                    } else if media_type == "image/svg+xml" {
+                        // SVG documents may reference external resources of their own
+                        let svg_data: Vec<u8> = embed_svg(session, &final_url, &data);
+                        let mut data_url =
+                            create_data_url(&media_type, &charset, &svg_data, &final_url);
+                        data_url.set_fragment(resolved_url.fragment());
+                        set_node_attr(node, attr_name, Some(data_url.to_string()));
                     } else {
                         // Create and embed data URL
                         let mut data_url =
@@ -772,15 +1561,39 @@ pub fn retrieve_and_embed_asset(
                     }
                 }
             }
+
+            // Hash what got embedded, it's not always the same as what got retrieved
+            if let Some(declared_integrity) = declared_integrity {
+                if ok_to_include && session.options.keep_integrity {
+                    let embedded_data: Option<Vec<u8>> = match get_node_attr(node, attr_name) {
+                        Some(embedded_url) => Url::parse(&embedded_url)
+                            .ok()
+                            .filter(|url| url.scheme() == "data")
+                            .map(|url| parse_data_url(&url).2),
+                        // Scripts and stylesheets embedded as text
+                        None => {
+                            let mut text: String = "".to_string();
+                            for child_node in node.children.borrow().iter() {
+                                if let NodeData::Text { ref contents } = child_node.data {
+                                    text.push_str(&contents.borrow());
+                                }
+                            }
+                            Some(text.into_bytes())
+                        }
+                    };
+
+                    if let Some(embedded_data) = embedded_data {
+                        set_node_attr(
+                            node,
+                            "integrity",
+                            regenerate_integrity(&embedded_data, &declared_integrity),
+                        );
+                    }
+                }
+            }
         }
         Err(_) => {
-            if resolved_url.scheme() == "http" || resolved_url.scheme() == "https" {
-                // Keep remote references if unable to retrieve the asset
-                set_node_attr(node, attr_name, Some(resolved_url.to_string()));
-            } else {
-                // Remove local references if they can't be successfully embedded as data URLs
-                set_node_attr(node, attr_name, None);
-            }
+            embed_remote_reference(node, attr_name, &resolved_url);
         }
     }
 }
@@ -788,16 +1601,56 @@ pub fn retrieve_and_embed_asset(
 pub fn walk(session: &mut Session, document_url: &Url, node: &Handle) {
     match node.data {
         NodeData::Document => {
+            // Remove elements hidden by filter lists, along with assets they would need
+            if let Some(filter_list) = &session.filter_list {
+                let mut hidden_selectors: SelectorSet = SelectorSet::default();
+                for selector in filter_list.get_hidden_selectors(document_url) {
+                    hidden_selectors.add(selector);
+                }
+                if !hidden_selectors.is_empty() {
+                    remove_hidden_elements(node, &hidden_selectors);
+                }
+            }
+
+            // Remove analytics scripts and tracking pixels
+            if session.options.no_tracking {
+                remove_tracking_elements(
+                    node,
+                    document_url,
+                    &get_lazy_load_attrs(&session.options),
+                );
+            }
+
+            // Keep track of characters used within the document (to know which glyphs are needed)
+            if session.options.no_unused_font_faces || session.options.subset_fonts {
+                session.document_chars.extend(get_text(node).chars());
+            }
+
             // Dig deeper
             for child_node in node.children.borrow().iter() {
                 walk(session, document_url, child_node);
             }
+
+            if session.options.inline_stylesheets {
+                convert_stylesheet_links(node);
+            }
+
+            if session.options.no_unused_css {
+                remove_unused_css(node);
+            }
+
+            if session.options.sanitize {
+                sanitize_document(node);
+            }
         }
         NodeData::Element {
             ref name,
             ref attrs,
             ..
         } => {
+            // Promote URLs hidden in lazy-loading attributes
+            resolve_lazy_loading(node, &get_lazy_load_attrs(&session.options));
+
             match name.local.as_ref() {
                 "meta" => {
                     if let Some(meta_attr_http_equiv_value) = get_node_attr(node, "http-equiv") {
@@ -848,7 +1701,20 @@ pub fn walk(session: &mut Session, document_url: &Url, node: &Handle) {
                                 );
                             }
                         }
+                    } else if link_node_types.contains(&LinkType::Manifest) {
+                        if let Some(link_attr_href_value) = get_node_attr(node, "href") {
+                            if !link_attr_href_value.is_empty() {
+                                retrieve_and_embed_asset(
+                                    session,
+                                    document_url,
+                                    node,
+                                    "href",
+                                    &link_attr_href_value,
+                                );
+                            }
+                        }
                     } else if link_node_types.contains(&LinkType::Preload)
+                        || link_node_types.contains(&LinkType::ModulePreload)
                         || link_node_types.contains(&LinkType::DnsPrefetch)
                     {
                         // Since all resources are embedded as data URLs, preloading and prefetching are not necessary
@@ -890,38 +1756,25 @@ pub fn walk(session: &mut Session, document_url: &Url, node: &Handle) {
                     }
                 }
                 "img" => {
-                    // Find src and data-src attribute(s)
                     let img_attr_src_value: Option<String> = get_node_attr(node, "src");
-                    let img_attr_data_src_value: Option<String> = get_node_attr(node, "data-src");
 
                     if session.options.no_images {
-                        // Put empty images into src and data-src attributes
+                        // Put empty image into src attribute
                         if img_attr_src_value.is_some() {
                             set_node_attr(node, "src", Some(EMPTY_IMAGE_DATA_URL.to_string()));
                         }
-                        if img_attr_data_src_value.is_some() {
-                            set_node_attr(node, "data-src", Some(EMPTY_IMAGE_DATA_URL.to_string()));
-                        }
-                    } else if img_attr_src_value.clone().unwrap_or_default().is_empty()
-                        && img_attr_data_src_value
-                            .clone()
-                            .unwrap_or_default()
-                            .is_empty()
-                    {
+                    } else if img_attr_src_value.clone().unwrap_or_default().is_empty() {
                         // Add empty src attribute
                         set_node_attr(node, "src", Some("".to_string()));
                     } else {
                         // Add data URL src attribute
-                        let img_full_url: String = if !img_attr_data_src_value
-                            .clone()
-                            .unwrap_or_default()
-                            .is_empty()
-                        {
-                            img_attr_data_src_value.unwrap_or_default()
-                        } else {
-                            img_attr_src_value.unwrap_or_default()
-                        };
-                        retrieve_and_embed_asset(session, document_url, node, "src", &img_full_url);
+                        retrieve_and_embed_asset(
+                            session,
+                            document_url,
+                            node,
+                            "src",
+                            &img_attr_src_value.unwrap_or_default(),
+                        );
                     }
 
                     // Resolve srcset attribute
@@ -1000,16 +1853,22 @@ pub fn walk(session: &mut Session, document_url: &Url, node: &Handle) {
                                 let image_asset_url: Url =
                                     resolve_url(document_url, &use_attr_href_value);
 
-                                match session.retrieve_asset(document_url, &image_asset_url) {
+                                match session.retrieve_asset(
+                                    document_url,
+                                    &image_asset_url,
+                                    Some(ResourceType::Image),
+                                ) {
                                     Ok((data, final_url, media_type, charset)) => {
                                         if media_type == "image/svg+xml" {
                                             // Parse SVG
+                                            let svg_data: Vec<u8> =
+                                                embed_svg(session, &final_url, &data);
                                             let svg_dom: RcDom = parse_document(
                                                 RcDom::default(),
                                                 Default::default(),
                                             )
                                             .from_utf8()
-                                            .read_from(&mut data.as_slice())
+                                            .read_from(&mut svg_data.as_slice())
                                             .unwrap();
 
                                             if image_asset_url.fragment().is_some() {
@@ -1174,6 +2033,22 @@ pub fn walk(session: &mut Session, document_url: &Url, node: &Handle) {
                             }
                         }
                     }
+
+                    if session.options.no_tracking {
+                        // Campaign IDs shouldn't leak out of saved documents
+                        if let Some(href_url) = get_node_attr(node, "href")
+                            .and_then(|href| Url::parse(href.trim()).ok())
+                            .filter(|url| url.scheme() == "http" || url.scheme() == "https")
+                        {
+                            let clean_href_url: Url = remove_tracking_params(href_url.clone());
+                            if clean_href_url != href_url {
+                                set_node_attr(node, "href", Some(clean_href_url.to_string()));
+                            }
+                        }
+
+                        // Following links shouldn't notify anyone
+                        set_node_attr(node, "ping", None);
+                    }
                 }
                 "script" => {
                     // Read values of integrity and src attributes
@@ -1196,6 +2071,26 @@ pub fn walk(session: &mut Session, document_url: &Url, node: &Handle) {
                             "src",
                             script_attr_src,
                         );
+                    } else {
+                        let script_attr_type: String =
+                            get_node_attr(node, "type").unwrap_or_default();
+
+                        for child_node in node.children.borrow_mut().iter_mut() {
+                            if let NodeData::Text { ref contents } = child_node.data {
+                                let mut tendril = contents.borrow_mut();
+                                let replacement: String =
+                                    if script_attr_type.eq_ignore_ascii_case("importmap") {
+                                        embed_import_map(session, document_url, tendril.as_ref())
+                                    } else if script_attr_type.eq_ignore_ascii_case("module") {
+                                        embed_js_module(session, document_url, tendril.as_ref())
+                                    } else {
+                                        continue;
+                                    };
+                                tendril.clear();
+                                tendril
+                                    .push_slice(&replacement.replace("</script>", "<\\/script>"));
+                            }
+                        }
                     }
                 }
                 "style" => {
@@ -1223,13 +2118,36 @@ pub fn walk(session: &mut Session, document_url: &Url, node: &Handle) {
                     }
                 }
                 "frame" | "iframe" => {
+                    let frame_attr_srcdoc_value: Option<String> = get_node_attr(node, "srcdoc");
+
+                    if let Some(frame_attr_srcdoc_value) = frame_attr_srcdoc_value.clone() {
+                        if session.options.no_frames {
+                            set_node_attr(node, "srcdoc", Some("".to_string()));
+                        } else {
+                            match embed_srcdoc(session, document_url, &frame_attr_srcdoc_value) {
+                                Some(embedded_srcdoc) => {
+                                    set_node_attr(node, "srcdoc", Some(embedded_srcdoc));
+                                }
+                                None => {
+                                    if !session.options.silent {
+                                        print_error_message("about:srcdoc (frame not embedded)");
+                                    }
+                                    set_node_attr(node, "srcdoc", Some("".to_string()));
+                                }
+                            }
+                        }
+                    }
+
                     if let Some(frame_attr_src_value) = get_node_attr(node, "src") {
                         if session.options.no_frames {
                             // Empty the src attribute
                             set_node_attr(node, "src", Some("".to_string()));
                         } else {
-                            // Ignore (i)frames with empty source (they cause infinite loops)
-                            if !frame_attr_src_value.trim().is_empty() {
+                            // Ignore (i)frames with empty source (they cause infinite loops),
+                            //  as well as those which have their src overridden by srcdoc
+                            if !frame_attr_src_value.trim().is_empty()
+                                && frame_attr_srcdoc_value.is_none()
+                            {
                                 retrieve_and_embed_asset(
                                     session,
                                     document_url,
@@ -1295,7 +2213,63 @@ pub fn walk(session: &mut Session, document_url: &Url, node: &Handle) {
                         }
                     }
                 }
-                "noscript" => {
+                "object" | "embed" => {
+                    let attr_name: &str = if name.local.as_ref() == "object" {
+                        "data"
+                    } else {
+                        "src"
+                    };
+
+                    if let Some(attr_value) = get_node_attr(node, attr_name) {
+                        if !attr_value.trim().is_empty() {
+                            let media_type: String =
+                                get_node_attr(node, "type").unwrap_or_else(|| {
+                                    let full_url: Url = resolve_url(document_url, &attr_value);
+                                    detect_media_type_by_file_name(
+                                        full_url
+                                            .path_segments()
+                                            .and_then(|mut s| s.next_back())
+                                            .unwrap_or_default(),
+                                    )
+                                });
+
+                            if is_excluded_media_type(&session.options, &media_type) {
+                                set_node_attr(node, attr_name, None);
+                            } else {
+                                retrieve_and_embed_asset(
+                                    session,
+                                    document_url,
+                                    node,
+                                    attr_name,
+                                    &attr_value,
+                                );
+                            }
+                        }
+                    }
+                }
+                "track" => {
+                    if let Some(track_attr_src_value) = get_node_attr(node, "src") {
+                        let parent_node = get_parent_node(node);
+                        let parent_node_name: &str =
+                            get_node_name(&parent_node).unwrap_or_default();
+
+                        if (parent_node_name == "audio" && session.options.no_audio)
+                            || (parent_node_name == "video" && session.options.no_video)
+                        {
+                            set_node_attr(node, "src", None);
+                        } else if !track_attr_src_value.is_empty() {
+                            retrieve_and_embed_asset(
+                                session,
+                                document_url,
+                                node,
+                                "src",
+                                &track_attr_src_value,
+                            );
+                        }
+                    }
+                }
+                // Sanitized documents don't get to keep NOSCRIPT elements
+                "noscript" if !session.options.sanitize => {
                     for child_node in node.children.borrow_mut().iter_mut() {
                         if let NodeData::Text { ref contents } = child_node.data {
                             // Get contents of NOSCRIPT node
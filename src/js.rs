@@ -1,3 +1,14 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+use encoding_rs::Encoding;
+use regex::Regex;
+use serde_json::Value;
+
+use crate::filters::ResourceType;
+use crate::session::Session;
+use crate::url::{clean_url, create_data_url, resolve_url, Url};
+
 const JS_DOM_EVENT_ATTRS: &[&str] = &[
     // From WHATWG HTML spec 8.1.5.2 "Event handlers on elements, Document objects, and Window objects":
     //   https://html.spec.whatwg.org/#event-handlers-on-elements,-document-objects,-and-window-objects
@@ -93,6 +104,8 @@ const JS_DOM_EVENT_ATTRS: &[&str] = &[
     "oncopy",
     "onpaste",
 ];
+// Matches specifiers of static import and export-from statements
+const STATIC_IMPORT_REGEX: &str = r#"(?:\bimport\s*(?:[\w$*{}\s,]+?\s*\bfrom\s*)?|\bexport\s*(?:\*(?:\s*as\s+[\w$]+)?|\{[^}]*\})\s*from\s*)(?:"(?P<double_quoted>[^"\n]+)"|'(?P<single_quoted>[^'\n]+)')"#;
 
 // Returns true if DOM attribute name matches a native JavaScript event handler
 pub fn attr_is_event_handler(attr_name: &str) -> bool {
@@ -100,3 +113,248 @@ pub fn attr_is_event_handler(attr_name: &str) -> bool {
         .iter()
         .any(|a| attr_name.eq_ignore_ascii_case(a))
 }
+
+pub struct ImportMap {
+    pub imports: Vec<(String, Url)>,
+    pub scopes: Vec<(String, Vec<(String, Url)>)>,
+}
+
+// Returns import map with all addresses resolved into full URLs
+pub fn parse_import_map(import_map: &str, base_url: &Url) -> ImportMap {
+    let mut result = ImportMap {
+        imports: vec![],
+        scopes: vec![],
+    };
+
+    fn parse_specifier_map(value: &Value, base_url: &Url) -> Vec<(String, Url)> {
+        let mut specifier_map: Vec<(String, Url)> = vec![];
+
+        if let Some(entries) = value.as_object() {
+            for (specifier_key, address) in entries {
+                if let Some(address) = address.as_str() {
+                    let specifier_key: String =
+                        match resolve_url_like_specifier(base_url, specifier_key) {
+                            Some(url) => url.to_string(),
+                            None => specifier_key.to_string(),
+                        };
+                    specifier_map.push((specifier_key, resolve_url(base_url, address)));
+                }
+            }
+        }
+
+        // Longer keys take precedence when matching prefixes
+        specifier_map.sort_by_key(|(specifier_key, _)| Reverse(specifier_key.len()));
+
+        specifier_map
+    }
+
+    if let Ok(json) = serde_json::from_str::<Value>(import_map) {
+        if let Some(imports) = json.get("imports") {
+            result.imports = parse_specifier_map(imports, base_url);
+        }
+
+        if let Some(scopes) = json.get("scopes").and_then(|s| s.as_object()) {
+            for (scope_prefix, scope_imports) in scopes {
+                result.scopes.push((
+                    resolve_url(base_url, scope_prefix).to_string(),
+                    parse_specifier_map(scope_imports, base_url),
+                ));
+            }
+            result
+                .scopes
+                .sort_by_key(|(scope_prefix, _)| Reverse(scope_prefix.len()));
+        }
+    }
+
+    result
+}
+
+fn resolve_url_like_specifier(base_url: &Url, specifier: &str) -> Option<Url> {
+    if specifier.starts_with('/') || specifier.starts_with("./") || specifier.starts_with("../") {
+        base_url.join(specifier).ok()
+    } else {
+        Url::parse(specifier).ok()
+    }
+}
+
+pub fn resolve_module_specifier(
+    import_map: Option<&ImportMap>,
+    base_url: &Url,
+    specifier: &str,
+) -> Option<Url> {
+    let as_url: Option<Url> = resolve_url_like_specifier(base_url, specifier);
+    let normalized_specifier: String = match &as_url {
+        Some(url) => url.to_string(),
+        None => specifier.to_string(),
+    };
+
+    fn resolve_imports_match(specifier: &str, specifier_map: &[(String, Url)]) -> Option<Url> {
+        for (specifier_key, address) in specifier_map {
+            if specifier_key == specifier {
+                return Some(address.clone());
+            }
+
+            if specifier_key.ends_with('/') && specifier.starts_with(specifier_key.as_str()) {
+                if let Ok(url) = address.join(&specifier[specifier_key.len()..]) {
+                    return Some(url);
+                }
+            }
+        }
+
+        None
+    }
+
+    if let Some(import_map) = import_map {
+        for (scope_prefix, scope_imports) in &import_map.scopes {
+            if base_url.as_str() == scope_prefix
+                || (scope_prefix.ends_with('/') && base_url.as_str().starts_with(scope_prefix))
+            {
+                if let Some(url) = resolve_imports_match(&normalized_specifier, scope_imports) {
+                    return Some(url);
+                }
+            }
+        }
+
+        if let Some(url) = resolve_imports_match(&normalized_specifier, &import_map.imports) {
+            return Some(url);
+        }
+    }
+
+    // Bare specifiers can only be resolved via import maps
+    as_url
+}
+
+pub fn embed_js_module(session: &mut Session, module_url: &Url, source: &str) -> String {
+    let mut module_stack: Vec<String> = vec![clean_url(module_url.clone()).to_string()];
+    let mut embedded_modules: HashMap<String, String> = HashMap::new();
+
+    process_js_module(
+        session,
+        module_url,
+        source,
+        &mut module_stack,
+        &mut embedded_modules,
+    )
+}
+
+fn process_js_module(
+    session: &mut Session,
+    module_url: &Url,
+    source: &str,
+    module_stack: &mut Vec<String>,
+    embedded_modules: &mut HashMap<String, String>,
+) -> String {
+    let static_import_re = Regex::new(STATIC_IMPORT_REGEX).unwrap();
+    let mut result: String = "".to_string();
+    let mut last_offset: usize = 0;
+
+    for captures in static_import_re.captures_iter(source) {
+        let specifier_match = captures
+            .name("double_quoted")
+            .or(captures.name("single_quoted"))
+            .unwrap();
+        let specifier: &str = specifier_match.as_str();
+
+        result.push_str(&source[last_offset..specifier_match.start()]);
+        last_offset = specifier_match.end();
+
+        let import_url: Url =
+            match resolve_module_specifier(session.import_map.as_ref(), module_url, specifier) {
+                Some(import_url) => import_url,
+                None => {
+                    // Leave unresolvable bare specifiers as they are
+                    result.push_str(specifier);
+                    continue;
+                }
+            };
+        result.push_str(&embed_js_module_dependency(
+            session,
+            module_url,
+            &import_url,
+            module_stack,
+            embedded_modules,
+        ));
+    }
+
+    result.push_str(&source[last_offset..]);
+
+    result
+}
+
+pub fn embed_js_module_url(session: &mut Session, parent_url: &Url, module_url: &Url) -> String {
+    embed_js_module_dependency(
+        session,
+        parent_url,
+        module_url,
+        &mut vec![],
+        &mut HashMap::new(),
+    )
+}
+
+fn embed_js_module_dependency(
+    session: &mut Session,
+    parent_url: &Url,
+    import_url: &Url,
+    module_stack: &mut Vec<String>,
+    embedded_modules: &mut HashMap<String, String>,
+) -> String {
+    let import_key: String = clean_url(import_url.clone()).to_string();
+
+    if module_stack.contains(&import_key) {
+        // Circular imports can't be expressed using data URLs
+        return import_url.to_string();
+    }
+
+    if let Some(import_data_url) = embedded_modules.get(&import_key) {
+        return import_data_url.to_string();
+    }
+
+    match session.retrieve_asset(parent_url, import_url, Some(ResourceType::Script)) {
+        Ok((data, final_url, media_type, charset)) => {
+            let module_source: String =
+                if let Some(encoding) = Encoding::for_label(charset.as_bytes()) {
+                    let (string, _, _) = encoding.decode(&data);
+                    string.to_string()
+                } else {
+                    String::from_utf8_lossy(&data).to_string()
+                };
+
+            // JSON and CSS modules get embedded as they are
+            let is_js_module: bool = !media_type.eq_ignore_ascii_case("application/json")
+                && !media_type.eq_ignore_ascii_case("text/css");
+            let module_source: String = if is_js_module {
+                module_stack.push(import_key.clone());
+                let processed = process_js_module(
+                    session,
+                    &final_url,
+                    &module_source,
+                    module_stack,
+                    embedded_modules,
+                );
+                module_stack.pop();
+                processed
+            } else {
+                module_source
+            };
+
+            let import_data_url: String = create_data_url(
+                if is_js_module {
+                    "text/javascript"
+                } else {
+                    &media_type
+                },
+                "utf-8",
+                module_source.as_bytes(),
+                &final_url,
+            )
+            .to_string();
+            embedded_modules.insert(import_key, import_data_url.clone());
+
+            import_data_url
+        }
+        Err(_) => {
+            // Keep remote reference if unable to retrieve the module
+            import_url.to_string()
+        }
+    }
+}
@@ -2,7 +2,16 @@ pub mod cache;
 pub mod cookies;
 pub mod core;
 pub mod css;
+pub mod diff;
+pub mod filters;
+pub mod font;
 pub mod html;
 pub mod js;
+pub mod reader;
+pub mod sanitize;
+pub mod serve;
 pub mod session;
+pub mod svg;
+pub mod unpack;
 pub mod url;
+pub mod verify;
@@ -1,17 +1,29 @@
 use std::fs;
 use std::io::{self, Error as IoError, Read, Write};
+use std::net::TcpListener;
+use std::path::{Path, PathBuf};
 use std::process;
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use tempfile::{Builder, NamedTempFile};
 
 use monolith::cache::Cache;
-use monolith::cookies::{parse_cookie_file_contents, Cookie};
+use monolith::cookies::{
+    parse_cookie_file_contents, parse_cookie_header, parse_json_cookie_file_contents, Cookie,
+};
 use monolith::core::{
     create_monolithic_document, create_monolithic_document_from_data, format_output_path,
-    print_error_message, MonolithOptions, MonolithOutputFormat,
+    print_error_message, IntegrityMismatchPolicy, MonolithOptions, MonolithOutputFormat,
+};
+use monolith::diff::{diff_document_snapshots, take_document_snapshot, DocumentSnapshot};
+use monolith::filters::{parse_url_rule, FilterList};
+use monolith::serve::{
+    detect_saved_document_format, extract_html_document, serve_directory, SavedDocumentFormat,
 };
 use monolith::session::Session;
+use monolith::unpack::{unpack_monolithic_document, UnpackedAsset};
+use monolith::url::Url;
+use monolith::verify::{verify_document, VerificationReport};
 
 const ASCII: &str = " \
  _____    _____________   __________     ___________________    ___
@@ -24,6 +36,7 @@ const ASCII: &str = " \
 ";
 const CACHE_ASSET_FILE_SIZE_THRESHOLD: usize = 1024 * 10; // Minimum file size for on-disk caching (in bytes)
 const DEFAULT_NETWORK_TIMEOUT: u64 = 120; // Maximum time to retrieve each remote asset (in seconds)
+const DEFAULT_SERVE_PORT: u16 = 8080; // Port to serve saved documents on
 const DEFAULT_USER_AGENT: &str =
     "Mozilla/5.0 (X11; Ubuntu; Linux x86_64; rv:135.0) Gecko/20100101 Firefox/135.0";
 
@@ -31,7 +44,12 @@ const DEFAULT_USER_AGENT: &str =
 #[command(name = env!("CARGO_PKG_NAME"))]
 #[command(version)] // Read version from Cargo.toml
 #[command(about = ASCII.to_owned() + "\n" + env!("CARGO_PKG_NAME") + " " + env!("CARGO_PKG_VERSION") + "\n\n" + env!("CARGO_PKG_DESCRIPTION"), long_about = None)]
+#[command(args_conflicts_with_subcommands = true, disable_help_subcommand = true)]
+#[command(subcommand_negates_reqs = true)]
 struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Remove audio sources
     #[arg(short = 'a', long)]
     no_audio: bool,
@@ -48,10 +66,26 @@ struct Cli {
     #[arg(short = 'c', long)]
     no_css: bool,
 
-    /// Specify cookie file
+    /// Specify cookie file (Netscape or JSON)
     #[arg(short = 'C', long, value_name = "cookies.txt")]
     cookie_file: Option<String>,
 
+    /// Send given cookies to the target's host
+    #[arg(long = "cookie", value_name = "name=value")]
+    cookie_headers: Vec<String>,
+
+    /// Save cookies (including ones set by servers) into given file
+    #[arg(long, value_name = "cookies.txt")]
+    cookie_jar: Option<String>,
+
+    /// Compare the new save against given previously saved document
+    #[arg(long, value_name = "previous.html")]
+    diff_against: Option<String>,
+
+    /// File to write the comparison (JSON) into, defaults to STDERR
+    #[arg(long, value_name = "diff.json", requires = "diff_against")]
+    diff_output: Option<String>,
+
     /// Specify domains to use for white/black-listing
     #[arg(short = 'd', long = "domain", value_name = "example.com")]
     domains: Vec<String>,
@@ -64,14 +98,74 @@ struct Cli {
     #[arg(short = 'E', long, value_name = "UTF-8")]
     encoding: Option<String>,
 
+    /// Block requests and hide elements using given filter list (EasyList or uBlock format)
+    #[arg(long = "filter-list", value_name = "easylist.txt")]
+    filter_lists: Vec<String>,
+
     /// Remove frames and iframes
     #[arg(short = 'f', long)]
     no_frames: bool,
 
+    /// Embed iframes using srcdoc instead of data URLs
+    #[arg(long)]
+    frames_as_srcdoc: bool,
+
+    /// Skip CSS rules which only target given media type
+    #[arg(long = "exclude-media", value_name = "print")]
+    excluded_media: Vec<String>,
+
+    /// Don't retrieve assets with URLs matching given glob or regex:pattern, optionally for one type
+    #[arg(long = "exclude-url", value_name = "[type:]pattern")]
+    excluded_urls: Vec<String>,
+
+    /// Only retrieve assets with URLs matching given glob or regex:pattern, optionally for one type
+    #[arg(long = "include-url", value_name = "[type:]pattern")]
+    included_urls: Vec<String>,
+
+    /// What to do with scripts and stylesheets which fail integrity checks
+    #[arg(
+        long,
+        value_name = "drop|embed|keep-remote",
+        value_parser = ["drop", "embed", "keep-remote"]
+    )]
+    integrity_mismatch: Option<String>,
+
+    /// Embed stylesheets using STYLE elements instead of data URLs
+    #[arg(long)]
+    inline_stylesheets: bool,
+
+    /// Recompute and keep integrity hashes of embedded scripts and stylesheets
+    #[arg(long)]
+    keep_integrity: bool,
+
+    /// Limit how deep nested frames get embedded
+    #[arg(long, value_name = "8")]
+    max_frame_depth: Option<usize>,
+
+    /// Minify embedded CSS
+    #[arg(long)]
+    minify_css: bool,
+
     /// Remove fonts
     #[arg(short = 'F', long)]
     no_fonts: bool,
 
+    /// Strip tracking parameters from URLs, remove analytics scripts, tracking pixels and pings
+    #[arg(long)]
+    no_tracking: bool,
+
+    /// Remove CSS rules which don't match anything within the document
+    #[arg(long)]
+    no_unused_css: bool,
+
+    /// Remove fonts which don't cover any text within the document
+    #[arg(long)]
+    no_unused_font_faces: bool,
+
+    /// Reduce embedded fonts to glyphs used within the document
+    #[arg(long)]
+    subset_fonts: bool,
+
     /// Remove images
     #[arg(short = 'i', long)]
     no_images: bool,
@@ -84,6 +178,10 @@ struct Cli {
     #[arg(short = 'j', long)]
     no_js: bool,
 
+    /// Specify attributes to look up lazy-loaded asset URLs in
+    #[arg(long = "lazy-load-attr", value_name = "data-src")]
+    lazy_load_attrs: Vec<String>,
+
     /// Allow invalid X.509 (TLS) certificates
     #[arg(short = 'k', long)]
     insecure: bool,
@@ -104,6 +202,18 @@ struct Cli {
     #[arg(short, long, value_name = "result.html")]
     output: Option<String>,
 
+    /// Keep only image-set() candidates best suited for given pixel density
+    #[arg(long, value_name = "1")]
+    pixel_density: Option<f64>,
+
+    /// Save only the main content of the document (reader mode)
+    #[arg(long)]
+    reader_mode: bool,
+
+    /// Keep only safe elements, attributes and URL schemes (implies -j)
+    #[arg(long)]
+    sanitize: bool,
+
     /// Suppress verbosity
     #[arg(short, long)]
     quiet: bool,
@@ -125,7 +235,38 @@ struct Cli {
     exp_css_prop_assets: bool,
 
     /// URL or file path, use - for STDIN
-    target: String,
+    #[arg(required = true)]
+    target: Option<String>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Serve saved documents (HTML, MHTML, WARC) from a directory over HTTP on localhost
+    Serve {
+        /// Directory containing saved documents
+        #[arg(default_value = ".")]
+        directory: String,
+
+        /// Port to listen on
+        #[arg(short, long, value_name = "8080")]
+        port: Option<u16>,
+    },
+
+    /// Extract assets embedded into a saved document as separate files
+    Unpack {
+        /// Saved document, use - for STDIN
+        file: String,
+
+        /// Directory to write the document and its assets into
+        #[arg(short, long, value_name = "directory")]
+        output: Option<String>,
+    },
+
+    /// Check whether a saved document is self-contained, exit with error if it reaches out to the network
+    Verify {
+        /// Saved document, use - for STDIN
+        file: String,
+    },
 }
 
 pub enum Output {
@@ -170,8 +311,167 @@ pub fn read_stdin() -> Vec<u8> {
     }
 }
 
+fn serve(directory: &str, port: u16) -> ! {
+    if !Path::new(directory).is_dir() {
+        print_error_message(&format!("could not find directory \"{}\"", directory));
+        process::exit(1);
+    }
+
+    let listener: TcpListener = match TcpListener::bind(("127.0.0.1", port)) {
+        Ok(listener) => listener,
+        Err(error) => {
+            print_error_message(&format!("could not listen on port {}: {}", port, error));
+            process::exit(1);
+        }
+    };
+
+    eprintln!("Serving \"{}\" on http://127.0.0.1:{}/", directory, port);
+
+    if let Err(error) = serve_directory(&listener, Path::new(directory)) {
+        print_error_message(&format!("Error: {}", error));
+        process::exit(1);
+    }
+
+    process::exit(0);
+}
+
+fn unpack(file: &str, output: Option<&str>) -> ! {
+    let data: Vec<u8> = if file == "-" {
+        read_stdin()
+    } else {
+        match fs::read(file) {
+            Ok(data) => data,
+            Err(_) => {
+                print_error_message(&format!("could not read file \"{}\"", file));
+                process::exit(1);
+            }
+        }
+    };
+
+    // Unpack next to the saved document, into a directory named after it
+    let directory: PathBuf = match output {
+        Some(output) => PathBuf::from(output),
+        None if file == "-" => PathBuf::from("unpacked"),
+        None => Path::new(file).with_extension(""),
+    };
+    let (document, assets): (Vec<u8>, Vec<UnpackedAsset>) = unpack_monolithic_document(&data);
+
+    let result: Result<(), IoError> = fs::create_dir_all(&directory).and_then(|_| {
+        fs::write(directory.join("index.html"), &document)?;
+        for asset in &assets {
+            fs::write(directory.join(&asset.file_name), &asset.data)?;
+        }
+        Ok(())
+    });
+
+    if let Err(error) = result {
+        print_error_message(&format!(
+            "could not write into directory \"{}\": {}",
+            directory.display(),
+            error
+        ));
+        process::exit(1);
+    }
+
+    process::exit(0);
+}
+
+fn verify(file: &str) -> ! {
+    let data: Vec<u8> = if file == "-" {
+        read_stdin()
+    } else {
+        match fs::read(file) {
+            Ok(data) => data,
+            Err(_) => {
+                print_error_message(&format!("could not read file \"{}\"", file));
+                process::exit(1);
+            }
+        }
+    };
+    let report: VerificationReport = verify_document(&data);
+
+    for reference in &report.remote_references {
+        println!(
+            "Remote reference in {}: {}",
+            reference.location, reference.url
+        );
+    }
+    for reference in &report.broken_data_urls {
+        println!(
+            "Broken data URL in {}: {}",
+            reference.location, reference.url
+        );
+    }
+    match &report.csp {
+        Some(csp) => println!("Content Security Policy: {}", csp),
+        None => println!("Content Security Policy: none"),
+    }
+
+    if report.makes_network_requests() {
+        print_error_message(&format!(
+            "document makes {} network request(s) when opened",
+            report.unblocked_requests.len()
+        ));
+        process::exit(1);
+    }
+
+    println!("Document makes no network requests when opened");
+    process::exit(0);
+}
+
+fn write_document_diff(
+    previous_snapshot: &DocumentSnapshot,
+    document: &[u8],
+    output_format: &MonolithOutputFormat,
+    diff_output: Option<&str>,
+    silent: bool,
+) {
+    let document: Vec<u8> = match output_format {
+        MonolithOutputFormat::MHTML => {
+            extract_html_document(SavedDocumentFormat::MHTML, document).unwrap_or_default()
+        }
+        _ => document.to_vec(),
+    };
+    let diff: String = serde_json::to_string_pretty(&diff_document_snapshots(
+        previous_snapshot,
+        &take_document_snapshot(&document),
+    ))
+    .unwrap_or_default();
+
+    match diff_output {
+        Some(diff_output) if diff_output != "-" => {
+            if fs::write(diff_output, diff + "\n").is_err() && !silent {
+                print_error_message(&format!(
+                    "could not write comparison into file \"{}\"",
+                    diff_output
+                ));
+            }
+        }
+        _ => {
+            if !silent {
+                eprintln!("{}", diff);
+            }
+        }
+    }
+}
+
 fn main() {
     let cli = Cli::parse();
+
+    match &cli.command {
+        Some(Command::Serve { directory, port }) => {
+            serve(directory, port.unwrap_or(DEFAULT_SERVE_PORT));
+        }
+        Some(Command::Unpack { file, output }) => {
+            unpack(file, output.as_deref());
+        }
+        Some(Command::Verify { file }) => {
+            verify(file);
+        }
+        None => {}
+    }
+
+    let target: String = cli.target.unwrap_or_default();
     let cookie_file_path;
     let mut exit_code = 0;
     let mut options: MonolithOptions = MonolithOptions::default();
@@ -181,27 +481,55 @@ fn main() {
     {
         options.base_url = cli.base_url;
         options.blacklist_domains = cli.blacklist_domains;
+        options.cookie_jar = cli.cookie_jar;
         options.encoding = cli.encoding;
         if !cli.domains.is_empty() {
             options.domains = Some(cli.domains);
         }
+        if !cli.excluded_media.is_empty() {
+            options.excluded_media = Some(cli.excluded_media);
+        }
+        options.frames_as_srcdoc = cli.frames_as_srcdoc;
         options.ignore_errors = cli.ignore_errors;
+        options.inline_stylesheets = cli.inline_stylesheets;
         options.insecure = cli.insecure;
+        options.integrity_mismatch = match cli.integrity_mismatch.as_deref() {
+            Some("embed") => IntegrityMismatchPolicy::Embed,
+            Some("keep-remote") => IntegrityMismatchPolicy::KeepRemote,
+            _ => IntegrityMismatchPolicy::Drop,
+        };
         options.isolate = cli.isolate;
+        options.keep_integrity = cli.keep_integrity;
+        if !cli.lazy_load_attrs.is_empty() {
+            options.lazy_load_attrs = Some(cli.lazy_load_attrs);
+        }
+        options.max_frame_depth = cli.max_frame_depth;
+        options.minify_css = cli.minify_css;
         options.no_audio = cli.no_audio;
         options.no_css = cli.no_css;
         options.no_fonts = cli.no_fonts;
         options.no_frames = cli.no_frames;
         options.no_images = cli.no_images;
         options.no_js = cli.no_js;
+        if cli.sanitize {
+            // Sanitized documents can't have any JavaScript
+            options.no_js = true;
+        }
         if cli.mhtml {
             options.output_format = MonolithOutputFormat::MHTML;
             // The MHTML format doesn't allow JavaScript
             options.no_js = true;
         }
         options.no_metadata = cli.no_metadata;
+        options.no_tracking = cli.no_tracking;
+        options.no_unused_css = cli.no_unused_css;
+        options.no_unused_font_faces = cli.no_unused_font_faces;
         options.no_video = cli.no_video;
+        options.pixel_density = cli.pixel_density;
+        options.reader_mode = cli.reader_mode;
+        options.sanitize = cli.sanitize;
         options.silent = cli.quiet;
+        options.subset_fonts = cli.subset_fonts;
         options.timeout = cli.timeout.unwrap_or(DEFAULT_NETWORK_TIMEOUT);
         options.unwrap_noscript = cli.unwrap_noscript;
         if cli.user_agent.is_none() {
@@ -237,29 +565,113 @@ fn main() {
         },
     ));
 
+    // Cookies which don't specify their domains belong to the target's host
+    let target_host: String = Url::parse(&target)
+        .or_else(|_| Url::parse(&format!("http://{}", &target)))
+        .ok()
+        .and_then(|url| url.host_str().map(|host| host.to_string()))
+        .unwrap_or_default();
+
     // Read and parse cookie file
     let mut cookies: Option<Vec<Cookie>> = None;
     if let Some(opt_cookie_file) = cookie_file_path.clone() {
         match fs::read_to_string(&opt_cookie_file) {
-            Ok(str) => match parse_cookie_file_contents(&str) {
-                Ok(parsed_cookies_from_file) => {
-                    cookies = Some(parsed_cookies_from_file);
+            Ok(str) => {
+                // Cookies exported by browser extensions and automation tools come as JSON
+                let parsed_cookies = if str.trim_start().starts_with(['[', '{']) {
+                    parse_json_cookie_file_contents(&str, &target_host)
+                } else {
+                    parse_cookie_file_contents(&str)
+                };
+
+                match parsed_cookies {
+                    Ok(parsed_cookies_from_file) => {
+                        cookies = Some(parsed_cookies_from_file);
+                    }
+                    Err(_) => {
+                        if !options.silent {
+                            print_error_message(&format!(
+                                "could not parse specified cookie file \"{}\"",
+                                opt_cookie_file
+                            ));
+                        }
+                        process::exit(1);
+                    }
                 }
-                Err(_) => {
+            }
+            Err(_) => {
+                if !options.silent {
+                    print_error_message(&format!(
+                        "could not read specified cookie file \"{}\"",
+                        opt_cookie_file
+                    ));
+                }
+                process::exit(1);
+            }
+        }
+    }
+
+    // Read previously saved document to compare the new one against
+    let mut previous_snapshot: Option<DocumentSnapshot> = None;
+    if let Some(diff_against) = &cli.diff_against {
+        let format: SavedDocumentFormat =
+            detect_saved_document_format(diff_against).unwrap_or(SavedDocumentFormat::HTML);
+
+        match fs::read(diff_against)
+            .ok()
+            .and_then(|data| extract_html_document(format, &data))
+        {
+            Some(data) => {
+                previous_snapshot = Some(take_document_snapshot(&data));
+            }
+            None => {
+                if !options.silent {
+                    print_error_message(&format!(
+                        "could not read previously saved document \"{}\"",
+                        diff_against
+                    ));
+                }
+                process::exit(1);
+            }
+        }
+    }
+
+    // Add cookies specified via command-line arguments
+    for cookie_header in &cli.cookie_headers {
+        cookies
+            .get_or_insert_with(Vec::new)
+            .extend(parse_cookie_header(cookie_header, &target_host));
+    }
+
+    // Parse URL rules
+    for (url_rules, is_exclusion) in [(&cli.included_urls, false), (&cli.excluded_urls, true)] {
+        for url_rule in url_rules {
+            match parse_url_rule(url_rule, is_exclusion) {
+                Some(url_rule) => {
+                    options.url_rules.push(url_rule);
+                }
+                None => {
                     if !options.silent {
-                        print_error_message(&format!(
-                            "could not parse specified cookie file \"{}\"",
-                            opt_cookie_file
-                        ));
+                        print_error_message(&format!("could not parse URL rule \"{}\"", url_rule));
                     }
                     process::exit(1);
                 }
-            },
+            }
+        }
+    }
+
+    // Read and parse filter lists
+    let mut filter_list: FilterList = FilterList::default();
+    for filter_list_path in &cli.filter_lists {
+        match fs::read_to_string(filter_list_path) {
+            Ok(str) => {
+                filter_list.add_filters(&str);
+            }
             Err(_) => {
                 if !options.silent {
                     print_error_message(&format!(
-                        "could not read specified cookie file \"{}\"",
-                        opt_cookie_file
+                        "could not read specified filter list \"{}\"",
+                        filter_list_path
                     ));
                 }
                 process::exit(1);
@@ -270,10 +682,13 @@ fn main() {
     // Initiate session
     let output_format = options.output_format.clone();
     let silent = options.silent;
-    let session: Session = Session::new(cache, cookies, options);
+    let mut session: Session = Session::new(cache, cookies, options);
+    if !filter_list.is_empty() {
+        session.filter_list = Some(filter_list);
+    }
 
     // Retrieve target from source and output result
-    if cli.target == "-" {
+    if target == "-" {
         // Read input from pipe (STDIN)
         let data: Vec<u8> = read_stdin();
 
@@ -283,12 +698,23 @@ fn main() {
                 let mut output = Output::new(
                     &destination.unwrap_or(String::new()),
                     &title.unwrap_or_default(),
-                    output_format,
+                    output_format.clone(),
                 )
                 .expect("could not prepare output");
 
                 // Write result into STDOUT or file
                 output.write(&result).expect("could not write output");
+
+                // Compare result against previously saved document
+                if let Some(previous_snapshot) = &previous_snapshot {
+                    write_document_diff(
+                        previous_snapshot,
+                        &result,
+                        &output_format,
+                        cli.diff_output.as_deref(),
+                        silent,
+                    );
+                }
             }
             Err(error) => {
                 if !silent {
@@ -299,18 +725,29 @@ fn main() {
             }
         }
     } else {
-        match create_monolithic_document(session, cli.target) {
+        match create_monolithic_document(session, target) {
             Ok((result, title)) => {
                 // Define output
                 let mut output = Output::new(
                     &destination.unwrap_or(String::new()),
                     &title.unwrap_or_default(),
-                    output_format,
+                    output_format.clone(),
                 )
                 .expect("could not prepare output");
 
                 // Write result into STDOUT or file
                 output.write(&result).expect("could not write output");
+
+                // Compare result against previously saved document
+                if let Some(previous_snapshot) = &previous_snapshot {
+                    write_document_diff(
+                        previous_snapshot,
+                        &result,
+                        &output_format,
+                        cli.diff_output.as_deref(),
+                        silent,
+                    );
+                }
             }
             Err(error) => {
                 if !silent {
@@ -0,0 +1,477 @@
+use html5ever::interface::QualName;
+use html5ever::tendril::format_tendril;
+use html5ever::tree_builder::{create_element, NodeOrText, TreeSink};
+use html5ever::{namespace_url, ns, LocalName};
+use markup5ever_rcdom::{Handle, NodeData, RcDom};
+use std::rc::Rc;
+
+use crate::html::{
+    find_nodes, get_node_attr, get_node_name, get_parent_element, get_text, get_title, html_to_dom,
+};
+
+// Words which class names and IDs of page chrome (or of the content itself) tend to consist of
+const NEGATIVE_CLASS_WORDS: &[&str] = &[
+    "ad",
+    "ads",
+    "advert",
+    "banner",
+    "breadcrumb",
+    "combx",
+    "comment",
+    "community",
+    "cookie",
+    "disqus",
+    "extra",
+    "footer",
+    "footnote",
+    "header",
+    "masthead",
+    "menu",
+    "modal",
+    "nav",
+    "newsletter",
+    "outbrain",
+    "pager",
+    "pagination",
+    "popup",
+    "promo",
+    "related",
+    "remark",
+    "replies",
+    "rss",
+    "share",
+    "shoutbox",
+    "sidebar",
+    "skyscraper",
+    "social",
+    "sponsor",
+    "subscribe",
+    "taboola",
+    "tags",
+    "tool",
+    "widget",
+];
+const POSITIVE_CLASS_WORDS: &[&str] = &[
+    "article", "blog", "body", "content", "entry", "hentry", "main", "page", "post", "story",
+    "text",
+];
+// Elements which never make part of the main content
+const REMOVED_ELEMENTS: &[&str] = &[
+    "aside", "audio", "button", "canvas", "dialog", "embed", "footer", "form", "frame", "iframe",
+    "input", "link", "nav", "noscript", "object", "script", "select", "style", "template",
+    "textarea", "video",
+];
+const REMOVED_ROLES: &[&str] = &[
+    "alert",
+    "alertdialog",
+    "banner",
+    "complementary",
+    "contentinfo",
+    "dialog",
+    "menu",
+    "menubar",
+    "navigation",
+];
+// Elements which get scored based on the amount of text they contain
+const PARAGRAPH_ELEMENTS: &[&str] = &["p", "pre", "td"];
+const BLOCK_ELEMENTS: &[&str] = &[
+    "article",
+    "blockquote",
+    "div",
+    "dl",
+    "figure",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "table",
+    "ul",
+];
+const PRESENTATIONAL_ATTRS: &[&str] = &[
+    "align",
+    "background",
+    "bgcolor",
+    "border",
+    "cellpadding",
+    "cellspacing",
+    "color",
+    "face",
+    "style",
+    "valign",
+];
+const READER_STYLESHEET: &str = "body { max-width: 40em; margin: 0 auto; padding: 1em; \
+font: 1.125em/1.6 Georgia, serif; color: #222; background: #fff; } \
+img, video, figure { max-width: 100%; height: auto; } \
+pre { overflow: auto; }";
+
+pub fn extract_article(document: &Handle) -> RcDom {
+    let title: String = get_title(document)
+        .unwrap_or_default()
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ");
+    let mut article_nodes: Vec<Handle> = vec![];
+
+    if let Some(body) = find_nodes(document, vec!["html", "body"]).first() {
+        remove_unlikely_nodes(body);
+
+        let mut candidates: Vec<(Handle, f64)> = vec![];
+        score_paragraphs(body, &mut candidates);
+        for (candidate, score) in candidates.iter_mut() {
+            *score *= 1.0 - get_link_density(candidate);
+        }
+
+        let top_candidate: Option<(Handle, f64)> = candidates
+            .iter()
+            .filter(|(candidate, _)| !matches!(get_node_name(candidate), Some("body" | "html")))
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .cloned();
+
+        article_nodes = match top_candidate {
+            Some((top_candidate, top_score)) => {
+                collect_article_nodes(&top_candidate, top_score, &candidates)
+            }
+            // Documents with no distinct paragraphs get their whole body treated as the article
+            None => body.children.borrow().clone(),
+        };
+
+        for node in &article_nodes {
+            clean_article_node(node, &candidates);
+        }
+    }
+
+    let dom: RcDom = html_to_dom(
+        &format!(
+            "<html><head><meta charset=\"utf-8\"><title></title><style>{}</style></head>\
+<body><article></article></body></html>",
+            READER_STYLESHEET
+        )
+        .into_bytes(),
+        "utf-8".to_string(),
+    );
+
+    for title_node in find_nodes(&dom.document, vec!["html", "head", "title"]).iter() {
+        dom.append(
+            title_node,
+            NodeOrText::AppendText(format_tendril!("{}", title)),
+        );
+    }
+    for article_node in find_nodes(&dom.document, vec!["html", "body", "article"]).iter() {
+        // Show the title as heading unless the article already has one
+        if !title.is_empty() && !article_nodes.iter().any(|node| has_element(node, "h1")) {
+            let heading_node: Handle = create_element(
+                &dom,
+                QualName::new(None, ns!(html), LocalName::from("h1")),
+                vec![],
+            );
+            dom.append(
+                &heading_node,
+                NodeOrText::AppendText(format_tendril!("{}", title)),
+            );
+            dom.append(article_node, NodeOrText::AppendNode(heading_node));
+        }
+
+        for node in &article_nodes {
+            dom.remove_from_parent(node);
+            dom.append(article_node, NodeOrText::AppendNode(node.clone()));
+        }
+    }
+
+    dom
+}
+
+pub fn get_class_weight(node: &Handle) -> f64 {
+    let mut weight: f64 = 0.0;
+
+    for attr_name in ["class", "id"] {
+        let words: Vec<String> = get_node_attr(node, attr_name)
+            .unwrap_or_default()
+            .to_lowercase()
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(|word| word.to_string())
+            .collect();
+
+        if words.iter().any(|word| is_negative_word(word)) {
+            weight -= 25.0;
+        }
+        if words
+            .iter()
+            .any(|word| POSITIVE_CLASS_WORDS.contains(&word.as_str()))
+        {
+            weight += 25.0;
+        }
+    }
+
+    weight
+}
+
+pub fn get_link_density(node: &Handle) -> f64 {
+    let text_length: usize = get_inner_text(node).chars().count();
+
+    if text_length == 0 {
+        return 0.0;
+    }
+
+    let mut link_nodes: Vec<Handle> = vec![];
+    find_elements(node, "a", &mut link_nodes);
+    let link_text_length: usize = link_nodes
+        .iter()
+        .map(|link_node| get_inner_text(link_node).chars().count())
+        .sum();
+
+    link_text_length as f64 / text_length as f64
+}
+
+fn clean_article_node(node: &Handle, candidates: &[(Handle, f64)]) {
+    let mut children = node.children.borrow_mut();
+
+    children.retain(|child_node| match child_node.data {
+        NodeData::Element { .. } => !is_fishy(child_node, candidates),
+        NodeData::Comment { .. } => false,
+        _ => true,
+    });
+
+    if let NodeData::Element { ref attrs, .. } = node.data {
+        attrs
+            .borrow_mut()
+            .retain(|attr| !PRESENTATIONAL_ATTRS.contains(&attr.name.local.as_ref()));
+    }
+
+    for child_node in children.iter() {
+        clean_article_node(child_node, candidates);
+    }
+}
+
+fn collect_article_nodes(
+    top_candidate: &Handle,
+    top_score: f64,
+    candidates: &[(Handle, f64)],
+) -> Vec<Handle> {
+    let Some(parent_node) = get_parent_element(top_candidate) else {
+        return vec![top_candidate.clone()];
+    };
+    let threshold: f64 = (top_score * 0.2).max(10.0);
+    let top_class: String = get_node_attr(top_candidate, "class").unwrap_or_default();
+
+    // Content is often split into several siblings (e.g. separated by ads which got removed)
+    let siblings: Vec<Handle> = parent_node.children.borrow().clone();
+    siblings
+        .into_iter()
+        .filter(|sibling| {
+            if Rc::ptr_eq(sibling, top_candidate) {
+                return true;
+            }
+            if !matches!(sibling.data, NodeData::Element { .. }) {
+                return false;
+            }
+
+            let mut bonus: f64 = 0.0;
+            if !top_class.is_empty() && get_node_attr(sibling, "class") == Some(top_class.clone()) {
+                bonus += top_score * 0.2;
+            }
+            if get_score(sibling, candidates).is_some_and(|score| score + bonus >= threshold) {
+                return true;
+            }
+
+            if get_node_name(sibling) == Some("p") {
+                let text: String = get_inner_text(sibling);
+                let text_length: usize = text.chars().count();
+                let link_density: f64 = get_link_density(sibling);
+
+                return (text_length > 80 && link_density < 0.25)
+                    || (text_length > 0
+                        && link_density == 0.0
+                        && (text.contains(". ") || text.ends_with('.')));
+            }
+
+            false
+        })
+        .collect()
+}
+
+fn count_elements(node: &Handle, node_name: &str) -> usize {
+    let mut nodes: Vec<Handle> = vec![];
+    find_elements(node, node_name, &mut nodes);
+    nodes.len()
+}
+
+fn find_elements(node: &Handle, node_name: &str, nodes: &mut Vec<Handle>) {
+    for child_node in node.children.borrow().iter() {
+        if get_node_name(child_node) == Some(node_name) {
+            nodes.push(child_node.clone());
+        }
+        find_elements(child_node, node_name, nodes);
+    }
+}
+
+fn get_inner_text(node: &Handle) -> String {
+    get_text(node)
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
+fn get_score(node: &Handle, candidates: &[(Handle, f64)]) -> Option<f64> {
+    candidates
+        .iter()
+        .find(|(candidate, _)| Rc::ptr_eq(candidate, node))
+        .map(|(_, score)| *score)
+}
+
+fn has_element(node: &Handle, node_name: &str) -> bool {
+    get_node_name(node) == Some(node_name) || count_elements(node, node_name) > 0
+}
+
+fn initialize_score(node: &Handle) -> f64 {
+    let score: f64 = match get_node_name(node).unwrap_or_default() {
+        "article" | "main" => 10.0,
+        "div" => 5.0,
+        "blockquote" | "pre" | "td" => 3.0,
+        "address" | "dd" | "dl" | "dt" | "li" | "ol" | "ul" => -3.0,
+        "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "th" => -5.0,
+        _ => 0.0,
+    };
+
+    score + get_class_weight(node)
+}
+
+fn is_fishy(node: &Handle, candidates: &[(Handle, f64)]) -> bool {
+    let node_name: &str = get_node_name(node).unwrap_or_default();
+
+    match node_name {
+        "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+            get_class_weight(node) < 0.0 || get_link_density(node) > 0.33
+        }
+        "div" | "ol" | "section" | "table" | "ul" => {
+            let weight: f64 = get_class_weight(node);
+            if weight + get_score(node, candidates).unwrap_or_default() < 0.0 {
+                return true;
+            }
+
+            let text: String = get_inner_text(node);
+            // Plenty of commas indicate prose
+            if text.matches(',').count() >= 10 {
+                return false;
+            }
+
+            let paragraph_count: usize = count_elements(node, "p");
+            let image_count: usize = count_elements(node, "img");
+            let list_item_count: usize = count_elements(node, "li");
+            let text_length: usize = text.chars().count();
+            let link_density: f64 = get_link_density(node);
+            let is_list: bool = node_name == "ol" || node_name == "ul";
+
+            (image_count > 1 && (paragraph_count as f64 / image_count as f64) < 0.5)
+                || (!is_list && list_item_count > paragraph_count + 100)
+                || (text_length < 25 && (image_count == 0 || image_count > 2))
+                || (weight < 25.0 && link_density > 0.2 && !is_list)
+                || link_density > 0.5
+        }
+        _ => false,
+    }
+}
+
+fn is_negative_word(word: &str) -> bool {
+    NEGATIVE_CLASS_WORDS.iter().any(|negative_word| {
+        // Short words only count when they match entirely (e.g. "ad", but not "add" or "header")
+        if negative_word.len() <= 3 {
+            word == *negative_word
+        } else {
+            word.starts_with(negative_word)
+        }
+    })
+}
+
+fn is_unlikely(node: &Handle) -> bool {
+    let node_name: &str = get_node_name(node).unwrap_or_default();
+
+    if REMOVED_ELEMENTS.contains(&node_name)
+        || get_node_attr(node, "hidden").is_some()
+        || get_node_attr(node, "aria-hidden").as_deref() == Some("true")
+        || get_node_attr(node, "role")
+            .is_some_and(|role| REMOVED_ROLES.contains(&role.trim().to_lowercase().as_str()))
+    {
+        return true;
+    }
+
+    // Elements which are meant to contain the content don't get judged by their class names
+    if matches!(node_name, "article" | "body" | "html" | "main") {
+        return false;
+    }
+
+    get_class_weight(node) < 0.0
+}
+
+fn remove_unlikely_nodes(node: &Handle) {
+    let mut children = node.children.borrow_mut();
+
+    children.retain(|child_node| match child_node.data {
+        NodeData::Element { .. } => !is_unlikely(child_node),
+        NodeData::Comment { .. } => false,
+        _ => true,
+    });
+
+    for child_node in children.iter() {
+        remove_unlikely_nodes(child_node);
+    }
+}
+
+fn score_paragraphs(node: &Handle, candidates: &mut Vec<(Handle, f64)>) {
+    for child_node in node.children.borrow().iter() {
+        let node_name: &str = get_node_name(child_node).unwrap_or_default();
+
+        // DIV elements with no block-level children are paragraphs too
+        let is_paragraph: bool = PARAGRAPH_ELEMENTS.contains(&node_name)
+            || (node_name == "div"
+                && !child_node.children.borrow().iter().any(|grandchild_node| {
+                    BLOCK_ELEMENTS.contains(&get_node_name(grandchild_node).unwrap_or_default())
+                }));
+
+        if is_paragraph {
+            let text: String = get_inner_text(child_node);
+            let text_length: usize = text.chars().count();
+
+            if text_length >= 25 {
+                let score: f64 =
+                    1.0 + text.matches(',').count() as f64 + (text_length / 100).min(3) as f64;
+
+                // Pass the score up to ancestors, less and less of it the further up they are
+                let mut ancestor: Option<Handle> = get_parent_element(child_node);
+                let mut level: usize = 0;
+                while let Some(ancestor_node) = ancestor {
+                    if level == 3 {
+                        break;
+                    }
+
+                    let divider: f64 = match level {
+                        0 => 1.0,
+                        1 => 2.0,
+                        _ => level as f64 * 3.0,
+                    };
+                    match candidates
+                        .iter_mut()
+                        .find(|(candidate, _)| Rc::ptr_eq(candidate, &ancestor_node))
+                    {
+                        Some((_, candidate_score)) => *candidate_score += score / divider,
+                        None => candidates.push((
+                            ancestor_node.clone(),
+                            initialize_score(&ancestor_node) + score / divider,
+                        )),
+                    }
+
+                    ancestor = get_parent_element(&ancestor_node);
+                    level += 1;
+                }
+            }
+        }
+
+        score_paragraphs(child_node, candidates);
+    }
+}
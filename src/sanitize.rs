@@ -0,0 +1,477 @@
+use html5ever::{namespace_url, ns};
+use markup5ever_rcdom::{Handle, NodeData};
+use std::rc::Rc;
+
+use crate::html::{get_node_attr, parse_link_type, parse_srcset, set_node_attr, LinkType};
+
+const ALLOWED_ATTRS: &[&str] = &[
+    "class",
+    "dir",
+    "hidden",
+    "id",
+    "lang",
+    "role",
+    "style",
+    "tabindex",
+    "title",
+    "translate",
+];
+const ALLOWED_ELEMENT_ATTRS: &[(&str, &[&str])] = &[
+    ("a", &["href", "hreflang", "name", "rel", "target", "type"]),
+    ("area", &["alt", "coords", "href", "shape", "target"]),
+    ("audio", &["controls", "loop", "muted", "preload", "src"]),
+    ("blockquote", &["cite"]),
+    ("button", &["disabled", "name", "type", "value"]),
+    ("col", &["span"]),
+    ("colgroup", &["span"]),
+    ("data", &["value"]),
+    ("del", &["cite", "datetime"]),
+    ("details", &["open"]),
+    ("iframe", &["height", "name", "src", "srcdoc", "width"]),
+    (
+        "img",
+        &[
+            "alt", "decoding", "height", "loading", "sizes", "src", "srcset", "usemap", "width",
+        ],
+    ),
+    (
+        "input",
+        &[
+            "checked",
+            "disabled",
+            "max",
+            "maxlength",
+            "min",
+            "name",
+            "placeholder",
+            "readonly",
+            "size",
+            "step",
+            "type",
+            "value",
+        ],
+    ),
+    ("ins", &["cite", "datetime"]),
+    ("label", &["for"]),
+    ("li", &["value"]),
+    ("link", &["href", "media", "rel", "sizes", "type"]),
+    ("map", &["name"]),
+    ("meta", &["charset", "content", "http-equiv", "name"]),
+    ("meter", &["high", "low", "max", "min", "optimum", "value"]),
+    ("ol", &["reversed", "start", "type"]),
+    ("optgroup", &["disabled", "label"]),
+    ("option", &["disabled", "label", "selected", "value"]),
+    ("progress", &["max", "value"]),
+    ("q", &["cite"]),
+    ("select", &["disabled", "multiple", "name", "size"]),
+    ("source", &["media", "sizes", "src", "srcset", "type"]),
+    ("style", &["media"]),
+    ("td", &["colspan", "headers", "rowspan"]),
+    (
+        "textarea",
+        &[
+            "cols",
+            "disabled",
+            "name",
+            "placeholder",
+            "readonly",
+            "rows",
+        ],
+    ),
+    ("th", &["abbr", "colspan", "headers", "rowspan", "scope"]),
+    ("time", &["datetime"]),
+    ("track", &["default", "kind", "label", "src", "srclang"]),
+    (
+        "video",
+        &[
+            "controls",
+            "height",
+            "loop",
+            "muted",
+            "playsinline",
+            "poster",
+            "preload",
+            "src",
+            "width",
+        ],
+    ),
+];
+const ALLOWED_ELEMENTS: &[&str] = &[
+    "a",
+    "abbr",
+    "address",
+    "area",
+    "article",
+    "aside",
+    "audio",
+    "b",
+    "bdi",
+    "bdo",
+    "blockquote",
+    "body",
+    "br",
+    "button",
+    "caption",
+    "cite",
+    "code",
+    "col",
+    "colgroup",
+    "data",
+    "dd",
+    "del",
+    "details",
+    "dfn",
+    "div",
+    "dl",
+    "dt",
+    "em",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "head",
+    "header",
+    "hgroup",
+    "hr",
+    "html",
+    "i",
+    "iframe",
+    "img",
+    "input",
+    "ins",
+    "kbd",
+    "label",
+    "legend",
+    "li",
+    "link",
+    "main",
+    "map",
+    "mark",
+    "menu",
+    "meta",
+    "meter",
+    "nav",
+    "ol",
+    "optgroup",
+    "option",
+    "p",
+    "picture",
+    "pre",
+    "progress",
+    "q",
+    "rp",
+    "rt",
+    "ruby",
+    "s",
+    "samp",
+    "section",
+    "select",
+    "small",
+    "source",
+    "span",
+    "strong",
+    "style",
+    "sub",
+    "summary",
+    "sup",
+    "table",
+    "tbody",
+    "td",
+    "textarea",
+    "tfoot",
+    "th",
+    "thead",
+    "time",
+    "title",
+    "tr",
+    "track",
+    "u",
+    "ul",
+    "var",
+    "video",
+    "wbr",
+];
+// Content Security Policy only restricts what documents can do
+const ALLOWED_HTTP_EQUIV_VALUES: &[&str] = &["content-security-policy", "content-type"];
+const ALLOWED_SVG_ATTRS: &[&str] = &[
+    "class",
+    "clip-path",
+    "clip-rule",
+    "clipPathUnits",
+    "color",
+    "cx",
+    "cy",
+    "d",
+    "dx",
+    "dy",
+    "fill",
+    "fill-opacity",
+    "fill-rule",
+    "filter",
+    "flood-color",
+    "flood-opacity",
+    "font-family",
+    "font-size",
+    "font-style",
+    "font-weight",
+    "fx",
+    "fy",
+    "gradientTransform",
+    "gradientUnits",
+    "height",
+    "href",
+    "id",
+    "in",
+    "in2",
+    "marker-end",
+    "marker-mid",
+    "marker-start",
+    "markerHeight",
+    "markerUnits",
+    "markerWidth",
+    "mask",
+    "maskContentUnits",
+    "maskUnits",
+    "mode",
+    "offset",
+    "opacity",
+    "operator",
+    "orient",
+    "patternContentUnits",
+    "patternTransform",
+    "patternUnits",
+    "points",
+    "preserveAspectRatio",
+    "r",
+    "refX",
+    "refY",
+    "result",
+    "role",
+    "rx",
+    "ry",
+    "spreadMethod",
+    "stdDeviation",
+    "stop-color",
+    "stop-opacity",
+    "stroke",
+    "stroke-dasharray",
+    "stroke-dashoffset",
+    "stroke-linecap",
+    "stroke-linejoin",
+    "stroke-miterlimit",
+    "stroke-opacity",
+    "stroke-width",
+    "style",
+    "text-anchor",
+    "transform",
+    "type",
+    "values",
+    "viewBox",
+    "visibility",
+    "width",
+    "x",
+    "x1",
+    "x2",
+    "xmlns",
+    "y",
+    "y1",
+    "y2",
+];
+// Animation elements (which can set attributes to anything) and foreign objects aren't there
+const ALLOWED_SVG_ELEMENTS: &[&str] = &[
+    "a",
+    "circle",
+    "clipPath",
+    "defs",
+    "desc",
+    "ellipse",
+    "feBlend",
+    "feColorMatrix",
+    "feComposite",
+    "feFlood",
+    "feGaussianBlur",
+    "feMerge",
+    "feMergeNode",
+    "feOffset",
+    "filter",
+    "g",
+    "image",
+    "line",
+    "linearGradient",
+    "marker",
+    "mask",
+    "path",
+    "pattern",
+    "polygon",
+    "polyline",
+    "radialGradient",
+    "rect",
+    "stop",
+    "style",
+    "svg",
+    "symbol",
+    "text",
+    "title",
+    "tspan",
+    "use",
+];
+const ALLOWED_URL_SCHEMES: &[&str] = &["data", "http", "https", "mailto", "tel"];
+// Elements which get removed along with their contents (others only get unwrapped),
+// contents of NOSCRIPT elements are kept as text which sanitizing can't reach into
+const REMOVED_ELEMENTS: &[&str] = &[
+    "applet", "base", "embed", "frame", "frameset", "noscript", "object", "param", "script",
+    "template",
+];
+const URL_ATTRS: &[&str] = &["cite", "href", "poster", "src"];
+
+enum ElementTreatment {
+    Keep,
+    Remove,
+    Unwrap,
+}
+
+pub fn is_allowed_url(url: &str) -> bool {
+    // Browsers ignore whitespace and control characters within schemes
+    let url: String = url
+        .chars()
+        .filter(|c| !c.is_ascii_whitespace() && !c.is_ascii_control())
+        .collect::<String>()
+        .to_lowercase();
+
+    match url.find(':') {
+        Some(colon_index) if !url[..colon_index].contains(['/', '?', '#']) => {
+            ALLOWED_URL_SCHEMES.contains(&&url[..colon_index])
+        }
+        // Relative URLs don't change the scheme
+        _ => true,
+    }
+}
+
+pub fn sanitize_document(node: &Handle) {
+    let child_nodes: Vec<Handle> = node.children.take();
+    let mut sanitized_child_nodes: Vec<Handle> = vec![];
+
+    for child_node in child_nodes {
+        match child_node.data {
+            NodeData::Element { .. } => match get_element_treatment(&child_node) {
+                ElementTreatment::Keep => {
+                    sanitize_attrs(&child_node);
+                    sanitize_document(&child_node);
+                    sanitized_child_nodes.push(child_node);
+                }
+                ElementTreatment::Remove => {}
+                ElementTreatment::Unwrap => {
+                    sanitize_document(&child_node);
+                    for grandchild_node in child_node.children.take() {
+                        grandchild_node.parent.set(Some(Rc::downgrade(node)));
+                        sanitized_child_nodes.push(grandchild_node);
+                    }
+                }
+            },
+            // Comments may hide markup from parsers, but not from all browsers
+            NodeData::Comment { .. } | NodeData::ProcessingInstruction { .. } => {}
+            _ => {
+                sanitized_child_nodes.push(child_node);
+            }
+        }
+    }
+
+    *node.children.borrow_mut() = sanitized_child_nodes;
+}
+
+fn get_element_treatment(node: &Handle) -> ElementTreatment {
+    let NodeData::Element { ref name, .. } = node.data else {
+        return ElementTreatment::Remove;
+    };
+    let element_name: &str = &name.local;
+
+    if name.ns == ns!(svg) {
+        return if ALLOWED_SVG_ELEMENTS.contains(&element_name) {
+            ElementTreatment::Keep
+        } else {
+            ElementTreatment::Remove
+        };
+    } else if name.ns != ns!(html) || REMOVED_ELEMENTS.contains(&element_name) {
+        // MathML has its own ways of embedding links and scripts
+        return ElementTreatment::Remove;
+    }
+
+    match element_name {
+        "link" => {
+            let link_types: Vec<LinkType> =
+                parse_link_type(&get_node_attr(node, "rel").unwrap_or_default());
+
+            // Other types of links make browsers retrieve or prefetch things
+            if link_types.contains(&LinkType::Stylesheet)
+                || link_types.contains(&LinkType::Favicon)
+                || link_types.contains(&LinkType::AppleTouchIcon)
+            {
+                ElementTreatment::Keep
+            } else {
+                ElementTreatment::Remove
+            }
+        }
+        "meta" => match get_node_attr(node, "http-equiv") {
+            // Refresh and set-cookie directives act as soon as documents get opened
+            Some(http_equiv)
+                if !ALLOWED_HTTP_EQUIV_VALUES
+                    .contains(&http_equiv.trim().to_lowercase().as_str()) =>
+            {
+                ElementTreatment::Remove
+            }
+            _ => ElementTreatment::Keep,
+        },
+        _ if ALLOWED_ELEMENTS.contains(&element_name) => ElementTreatment::Keep,
+        _ => ElementTreatment::Unwrap,
+    }
+}
+
+fn sanitize_attrs(node: &Handle) {
+    let NodeData::Element {
+        ref name,
+        ref attrs,
+        ..
+    } = node.data
+    else {
+        return;
+    };
+    let element_name: &str = &name.local;
+    let is_svg: bool = name.ns == ns!(svg);
+    let element_attrs: &[&str] = ALLOWED_ELEMENT_ATTRS
+        .iter()
+        .find(|(name, _)| *name == element_name)
+        .map(|(_, attr_names)| *attr_names)
+        .unwrap_or_default();
+
+    attrs.borrow_mut().retain(|attr| {
+        let attr_name: &str = &attr.name.local;
+        let is_allowed: bool = if is_svg {
+            ALLOWED_SVG_ATTRS.contains(&attr_name)
+        } else {
+            ALLOWED_ATTRS.contains(&attr_name)
+                || element_attrs.contains(&attr_name)
+                || attr_name.starts_with("aria-")
+                || attr_name.starts_with("data-")
+        };
+
+        is_allowed
+            && match attr_name {
+                "srcset" => parse_srcset(&attr.value)
+                    .iter()
+                    .all(|srcset_item| is_allowed_url(srcset_item.path)),
+                _ if URL_ATTRS.contains(&attr_name) => is_allowed_url(&attr.value),
+                _ => true,
+            }
+    });
+
+    // Frames can't do anything on their own, even if something slipped through
+    if element_name == "iframe" && !is_svg {
+        set_node_attr(node, "sandbox", Some("".to_string()));
+    }
+}
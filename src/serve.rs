@@ -0,0 +1,477 @@
+use std::fs;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+use base64::{prelude::BASE64_STANDARD, Engine};
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+
+use crate::html::{get_title, parse_html_document, parse_metadata_tag};
+
+const FILE_NAME_ENCODE_SET: &AsciiSet = &NON_ALPHANUMERIC.remove(b'-').remove(b'.').remove(b'_');
+const REQUEST_TIMEOUT: u64 = 10; // Maximum time to wait for clients to send their requests (in seconds)
+
+type Headers = Vec<(String, String)>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SavedDocumentFormat {
+    HTML,
+    MHTML,
+    WARC,
+}
+
+#[derive(Debug)]
+pub struct SavedDocument {
+    pub file_name: String,
+    pub format: SavedDocumentFormat,
+    pub title: Option<String>,
+    pub source: Option<String>,
+    pub saved_at: Option<String>,
+}
+
+pub fn create_index_page(documents: &[SavedDocument]) -> String {
+    let mut rows: String = String::new();
+
+    for document in documents {
+        let source: String = match &document.source {
+            Some(source) if source.starts_with("http://") || source.starts_with("https://") => {
+                format!("<a href=\"{0}\">{0}</a>", escape_html(source))
+            }
+            Some(source) => escape_html(source),
+            None => String::new(),
+        };
+
+        rows.push_str(&format!(
+            "<tr><td><a href=\"/{}\">{}</a></td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+            utf8_percent_encode(&document.file_name, FILE_NAME_ENCODE_SET),
+            escape_html(document.title.as_deref().unwrap_or(&document.file_name)),
+            source,
+            escape_html(document.saved_at.as_deref().unwrap_or_default()),
+            escape_html(&document.file_name),
+        ));
+    }
+
+    format!(
+        "<!DOCTYPE html>\n\
+<html>\n\
+<head>\n\
+<meta charset=\"utf-8\">\n\
+<title>Saved documents</title>\n\
+</head>\n\
+<body>\n\
+<table>\n\
+<thead><tr><th>Title</th><th>Saved from</th><th>Saved at</th><th>File</th></tr></thead>\n\
+<tbody>\n\
+{}</tbody>\n\
+</table>\n\
+</body>\n\
+</html>\n",
+        rows
+    )
+}
+
+pub fn detect_saved_document_format(file_name: &str) -> Option<SavedDocumentFormat> {
+    let (_, extension) = file_name.rsplit_once('.')?;
+
+    match extension.to_lowercase().as_str() {
+        "htm" | "html" => Some(SavedDocumentFormat::HTML),
+        "mht" | "mhtml" => Some(SavedDocumentFormat::MHTML),
+        "warc" => Some(SavedDocumentFormat::WARC),
+        _ => None,
+    }
+}
+
+pub fn extract_html_document(format: SavedDocumentFormat, data: &[u8]) -> Option<Vec<u8>> {
+    match format {
+        SavedDocumentFormat::HTML => Some(data.to_vec()),
+        SavedDocumentFormat::MHTML => extract_mhtml_html_part(data),
+        SavedDocumentFormat::WARC => find_warc_html_response(data).map(|(_, payload)| payload),
+    }
+}
+
+pub fn read_saved_document(file_name: &str, data: &[u8]) -> Option<SavedDocument> {
+    let format: SavedDocumentFormat = detect_saved_document_format(file_name)?;
+    let (html, warc_headers): (Vec<u8>, Headers) = if format == SavedDocumentFormat::WARC {
+        let (warc_headers, payload) = find_warc_html_response(data)?;
+        (payload, warc_headers)
+    } else {
+        (extract_html_document(format, data)?, vec![])
+    };
+
+    let (dom, _) = parse_html_document(&html, "");
+    let title: Option<String> = get_title(&dom.document)
+        .map(|title| title.trim().to_string())
+        .filter(|title| !title.is_empty());
+    let (source, saved_at) = match parse_metadata_tag(&String::from_utf8_lossy(&html)) {
+        Some((source, saved_at)) => (Some(source), Some(saved_at)),
+        // Fall back to what the WARC record says about where and when it was captured
+        None => (
+            get_header(&warc_headers, "WARC-Target-URI"),
+            get_header(&warc_headers, "WARC-Date"),
+        ),
+    };
+
+    Some(SavedDocument {
+        file_name: file_name.to_string(),
+        format,
+        title,
+        source,
+        saved_at,
+    })
+}
+
+pub fn read_saved_documents(directory: &Path) -> Result<Vec<SavedDocument>, io::Error> {
+    let mut documents: Vec<SavedDocument> = vec![];
+
+    for entry in fs::read_dir(directory)? {
+        let path: PathBuf = entry?.path();
+        let Some(file_name) = path.file_name().and_then(|file_name| file_name.to_str()) else {
+            continue;
+        };
+
+        if !path.is_file() || detect_saved_document_format(file_name).is_none() {
+            continue;
+        }
+
+        if let Some(document) = fs::read(&path)
+            .ok()
+            .and_then(|data| read_saved_document(file_name, &data))
+        {
+            documents.push(document);
+        }
+    }
+
+    documents.sort_by(|a, b| a.file_name.cmp(&b.file_name));
+
+    Ok(documents)
+}
+
+pub fn serve_directory(listener: &TcpListener, directory: &Path) -> Result<(), io::Error> {
+    for stream in listener.incoming() {
+        let stream: TcpStream = stream?;
+        let directory: PathBuf = directory.to_path_buf();
+
+        thread::spawn(move || {
+            // Clients which go away mid-response are none of the server's concern
+            let _ = handle_request(stream, &directory);
+        });
+    }
+
+    Ok(())
+}
+
+fn decode_chunked_body(data: &[u8]) -> Option<Vec<u8>> {
+    let mut body: Vec<u8> = vec![];
+    let mut rest: &[u8] = data;
+
+    loop {
+        let line_end: usize = rest.windows(2).position(|window| window == b"\r\n")?;
+        let size_line: String = String::from_utf8_lossy(&rest[..line_end]).to_string();
+        let size: usize = usize::from_str_radix(size_line.split(';').next()?.trim(), 16).ok()?;
+        rest = &rest[line_end + 2..];
+
+        if size == 0 {
+            return Some(body);
+        }
+
+        body.extend_from_slice(rest.get(..size)?);
+        rest = rest.get(size + 2..).unwrap_or_default();
+    }
+}
+
+fn decode_quoted_printable(data: &[u8]) -> Vec<u8> {
+    let mut decoded: Vec<u8> = Vec::with_capacity(data.len());
+    let mut i: usize = 0;
+
+    while i < data.len() {
+        if data[i] == b'=' {
+            if data[i + 1..].starts_with(b"\r\n") {
+                // Soft line break
+                i += 3;
+                continue;
+            } else if data[i + 1..].starts_with(b"\n") {
+                i += 2;
+                continue;
+            } else if let Some(byte) = data
+                .get(i + 1..i + 3)
+                .and_then(|hex| std::str::from_utf8(hex).ok())
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            {
+                decoded.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+
+        decoded.push(data[i]);
+        i += 1;
+    }
+
+    decoded
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn extract_mhtml_html_part(data: &[u8]) -> Option<Vec<u8>> {
+    let (headers, body) = split_headers(data)?;
+    let content_type: String = get_header(&headers, "Content-Type")?;
+    let boundary: String = content_type.split(';').find_map(|param| {
+        let (name, value) = param.split_once('=')?;
+        if name.trim().eq_ignore_ascii_case("boundary") {
+            Some(value.trim().trim_matches('"').to_string())
+        } else {
+            None
+        }
+    })?;
+    let delimiter: Vec<u8> = format!("--{}", boundary).into_bytes();
+
+    // Everything before the first delimiter is a preamble
+    for part in split_bytes(body, &delimiter).into_iter().skip(1) {
+        if part.starts_with(b"--") {
+            // Closing delimiter
+            break;
+        }
+
+        let Some((part_headers, part_body)) = split_headers(part) else {
+            continue;
+        };
+
+        if !get_header(&part_headers, "Content-Type")
+            .unwrap_or_default()
+            .trim()
+            .to_lowercase()
+            .starts_with("text/html")
+        {
+            continue;
+        }
+
+        // Line break preceding the delimiter belongs to the delimiter
+        let part_body: &[u8] = part_body
+            .strip_suffix(b"\r\n")
+            .or(part_body.strip_suffix(b"\n"))
+            .unwrap_or(part_body);
+
+        return match get_header(&part_headers, "Content-Transfer-Encoding")
+            .unwrap_or_default()
+            .trim()
+            .to_lowercase()
+            .as_str()
+        {
+            "base64" => {
+                let encoded: Vec<u8> = part_body
+                    .iter()
+                    .filter(|byte| !byte.is_ascii_whitespace())
+                    .cloned()
+                    .collect();
+                BASE64_STANDARD.decode(encoded).ok()
+            }
+            "quoted-printable" => Some(decode_quoted_printable(part_body)),
+            _ => Some(part_body.to_vec()),
+        };
+    }
+
+    None
+}
+
+fn find_warc_html_response(data: &[u8]) -> Option<(Headers, Vec<u8>)> {
+    let mut rest: &[u8] = data;
+
+    while !rest.is_empty() {
+        let (warc_headers, block) = split_headers(rest)?;
+        let content_length: usize = get_header(&warc_headers, "Content-Length")?
+            .trim()
+            .parse()
+            .ok()?;
+        let record: &[u8] = block.get(..content_length)?;
+        rest = block[content_length..].trim_ascii_start();
+
+        if !get_header(&warc_headers, "WARC-Type")
+            .unwrap_or_default()
+            .eq_ignore_ascii_case("response")
+        {
+            continue;
+        }
+
+        let Some((http_headers, payload)) = split_headers(record) else {
+            continue;
+        };
+
+        if get_header(&http_headers, "Content-Type")
+            .unwrap_or_default()
+            .trim()
+            .to_lowercase()
+            .starts_with("text/html")
+        {
+            let payload: Vec<u8> = if get_header(&http_headers, "Transfer-Encoding")
+                .unwrap_or_default()
+                .eq_ignore_ascii_case("chunked")
+            {
+                decode_chunked_body(payload)?
+            } else {
+                payload.to_vec()
+            };
+
+            return Some((warc_headers, payload));
+        }
+    }
+
+    None
+}
+
+fn get_header(headers: &[(String, String)], name: &str) -> Option<String> {
+    headers
+        .iter()
+        .find(|(header_name, _)| header_name.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.to_string())
+}
+
+fn handle_request(mut stream: TcpStream, directory: &Path) -> Result<(), io::Error> {
+    stream.set_read_timeout(Some(Duration::from_secs(REQUEST_TIMEOUT)))?;
+
+    let mut reader: BufReader<&TcpStream> = BufReader::new(&stream);
+    let mut request_line: String = String::new();
+    reader.read_line(&mut request_line)?;
+
+    // Skip request headers, they don't affect the response
+    loop {
+        let mut header_line: String = String::new();
+        if reader.read_line(&mut header_line)? == 0 || header_line.trim().is_empty() {
+            break;
+        }
+    }
+
+    let mut request_line_parts = request_line.split_whitespace();
+    let method: &str = request_line_parts.next().unwrap_or_default();
+    let target: &str = request_line_parts.next().unwrap_or_default();
+    let path: &str = target.split(['?', '#']).next().unwrap_or_default();
+    let head_only: bool = method == "HEAD";
+
+    if method != "GET" && method != "HEAD" {
+        return write_response(
+            &mut stream,
+            "405 Method Not Allowed",
+            "text/plain",
+            b"Method Not Allowed",
+            head_only,
+        );
+    }
+
+    if path == "/" {
+        let documents: Vec<SavedDocument> = read_saved_documents(directory)?;
+
+        return write_response(
+            &mut stream,
+            "200 OK",
+            "text/html; charset=utf-8",
+            create_index_page(&documents).as_bytes(),
+            head_only,
+        );
+    }
+
+    // Only documents located directly within the served directory are accessible
+    let file_name: String = percent_decode_str(path.trim_start_matches('/'))
+        .decode_utf8_lossy()
+        .to_string();
+    let document: Option<Vec<u8>> = if file_name.contains(['/', '\\']) || file_name.starts_with('.')
+    {
+        None
+    } else {
+        detect_saved_document_format(&file_name).and_then(|format| {
+            fs::read(directory.join(&file_name))
+                .ok()
+                .and_then(|data| extract_html_document(format, &data))
+        })
+    };
+
+    match document {
+        // Saved documents specify their own charsets
+        Some(document) => write_response(&mut stream, "200 OK", "text/html", &document, head_only),
+        None => write_response(
+            &mut stream,
+            "404 Not Found",
+            "text/plain",
+            b"Not Found",
+            head_only,
+        ),
+    }
+}
+
+fn split_bytes<'a>(data: &'a [u8], delimiter: &[u8]) -> Vec<&'a [u8]> {
+    let mut parts: Vec<&[u8]> = vec![];
+    let mut start: usize = 0;
+    let mut i: usize = 0;
+
+    while i + delimiter.len() <= data.len() {
+        if data[i..].starts_with(delimiter) {
+            parts.push(&data[start..i]);
+            i += delimiter.len();
+            start = i;
+        } else {
+            i += 1;
+        }
+    }
+    parts.push(&data[start..]);
+
+    parts
+}
+
+fn split_headers(data: &[u8]) -> Option<(Headers, &[u8])> {
+    // Skip the remainder of the line which precedes headers (e.g. MIME delimiter)
+    let data: &[u8] = data
+        .strip_prefix(b"\r\n")
+        .or(data.strip_prefix(b"\n"))
+        .unwrap_or(data);
+    let (headers_end, body_start) = match (
+        data.windows(4).position(|window| window == b"\r\n\r\n"),
+        data.windows(2).position(|window| window == b"\n\n"),
+    ) {
+        (Some(crlf), Some(lf)) if lf < crlf => (lf, lf + 2),
+        (Some(crlf), _) => (crlf, crlf + 4),
+        (None, Some(lf)) => (lf, lf + 2),
+        (None, None) => return None,
+    };
+    let mut headers: Headers = vec![];
+
+    for line in String::from_utf8_lossy(&data[..headers_end]).lines() {
+        if line.starts_with([' ', '\t']) {
+            // Folded header lines continue values of preceding headers
+            if let Some((_, value)) = headers.last_mut() {
+                value.push(' ');
+                value.push_str(line.trim());
+            }
+        } else if let Some((name, value)) = line.split_once(':') {
+            headers.push((name.trim().to_string(), value.trim().to_string()));
+        }
+    }
+
+    Some((headers, &data[body_start..]))
+}
+
+fn write_response(
+    stream: &mut TcpStream,
+    status: &str,
+    content_type: &str,
+    body: &[u8],
+    head_only: bool,
+) -> Result<(), io::Error> {
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nCache-Control: no-cache\r\nConnection: close\r\n\r\n",
+        status,
+        content_type,
+        body.len()
+    )?;
+
+    if !head_only {
+        stream.write_all(body)?;
+    }
+
+    stream.flush()
+}
@@ -1,22 +1,40 @@
+use std::collections::HashSet;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::time::Duration;
 
+use encoding_rs::{Encoding, UTF_8};
 use reqwest::blocking::Client;
-use reqwest::header::{HeaderMap, HeaderValue, CONTENT_TYPE, COOKIE, REFERER, USER_AGENT};
+use reqwest::header::{
+    HeaderMap, HeaderValue, CONTENT_TYPE, COOKIE, LOCATION, REFERER, SET_COOKIE, USER_AGENT,
+};
+use reqwest::redirect::Policy;
 
 use crate::cache::Cache;
-use crate::cookies::Cookie;
+use crate::cookies::{parse_set_cookie_header, store_cookie, Cookie, CookieSameSite};
 use crate::core::{
     detect_media_type, parse_content_type, print_error_message, print_info_message, MonolithOptions,
 };
-use crate::url::{clean_url, domain_is_within_domain, get_referer_url, parse_data_url, Url};
+use crate::filters::{guess_resource_type, rules_allow_url, FilterList, ResourceType};
+use crate::js::ImportMap;
+use crate::url::{
+    clean_url, domain_is_within_domain, get_referer_url, is_same_site, parse_data_url, Url,
+};
+
+pub const DEFAULT_MAX_FRAME_DEPTH: usize = 8;
+const MAX_REDIRECTS: usize = 10;
 
 pub struct Session {
     cache: Option<Cache>,
     client: Client,
-    cookies: Option<Vec<Cookie>>,
+    cookies: Vec<Cookie>, // Cookies from cookie file along with ones set by servers
+    pub document_chars: HashSet<char>, // Characters found within text of processed documents
+    encodings: Vec<&'static Encoding>, // Encodings of documents and stylesheets being processed
+    pub filter_list: Option<FilterList>, // Network and element hiding filters (e.g. EasyList)
+    frames: Vec<Url>,     // Stack of documents being processed, starting with the top-level one
+    pub import_map: Option<ImportMap>, // Import map of the document being processed
     pub options: MonolithOptions,
+    parent_import_maps: Vec<Option<ImportMap>>, // Import maps of documents containing current frame
     urls: Vec<String>,
 }
 
@@ -42,30 +60,191 @@ impl Session {
                 600 // 10 minutes in seconds
             }))
             .danger_accept_invalid_certs(options.insecure)
+            .redirect(Policy::none())
             .default_headers(header_map)
             .build()
             .expect("Failed to initialize HTTP client");
 
         Session {
             cache,
-            cookies,
+            cookies: cookies.unwrap_or_default(),
             client,
+            document_chars: HashSet::new(),
+            encodings: Vec::new(),
+            filter_list: None,
+            frames: Vec::new(),
+            import_map: None,
             options,
+            parent_import_maps: Vec::new(),
             urls: Vec::new(),
         }
     }
 
+    fn allows_request(
+        &self,
+        parent_url: &Url,
+        url: &Url,
+        resource_type: Option<ResourceType>,
+        is_target_document: bool,
+    ) -> bool {
+        let cache_key: String = clean_url(url.clone()).as_str().to_string();
+
+        // Target documents are never excluded
+        if !is_target_document && !rules_allow_url(&self.options.url_rules, url, resource_type) {
+            if !self.options.silent {
+                print_error_message(&format!("{} (excluded by URL rules)", &cache_key));
+            }
+
+            return false;
+        }
+
+        if url.scheme() == "file" {
+            return true;
+        }
+
+        // Requests made by the target document itself are left alone
+        if let Some(filter_list) = &self.filter_list {
+            let document_url: &Url = self.get_document_url().unwrap_or(parent_url);
+            if !is_target_document && filter_list.blocks_request(url, document_url, resource_type) {
+                if !self.options.silent {
+                    print_error_message(&format!("{} (blocked by filter list)", &cache_key));
+                }
+
+                return false;
+            }
+        }
+
+        if let Some(domains) = &self.options.domains {
+            let domain_matches = domains
+                .iter()
+                .any(|d| domain_is_within_domain(url.host_str().unwrap_or_default(), d.trim()));
+            if (self.options.blacklist_domains && domain_matches)
+                || (!self.options.blacklist_domains && !domain_matches)
+            {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    pub fn can_enter_frame(&self, url: &Url) -> bool {
+        // Prevent runaway recursion caused by deeply nested frames
+        if self.frames.len()
+            > self
+                .options
+                .max_frame_depth
+                .unwrap_or(DEFAULT_MAX_FRAME_DEPTH)
+        {
+            return false;
+        }
+
+        // Prevent documents from embedding themselves (directly or via other frames);
+        // srcdoc documents are never fetched, so they can't form cycles
+        if url.as_str() == "about:srcdoc" {
+            return true;
+        }
+
+        // Query strings are ignored, otherwise pages which frame themselves
+        // with ever-changing query parameters would only be stopped by the depth limit
+        let frame_key = |url: &Url| -> Url {
+            let mut url: Url = clean_url(url.clone());
+            url.set_query(None);
+            url
+        };
+        let url: Url = frame_key(url);
+        !self
+            .frames
+            .iter()
+            .any(|frame_url| frame_key(frame_url) == url)
+    }
+
+    pub fn enter_encoding(&mut self, encoding: &'static Encoding) {
+        self.encodings.push(encoding);
+    }
+
+    pub fn enter_frame(&mut self, url: &Url) {
+        self.frames.push(clean_url(url.clone()));
+        // Every document gets to have its own import map
+        self.parent_import_maps.push(self.import_map.take());
+    }
+
+    pub fn exit_encoding(&mut self) {
+        self.encodings.pop();
+    }
+
+    pub fn exit_frame(&mut self) {
+        self.frames.pop();
+        self.import_map = self.parent_import_maps.pop().flatten();
+    }
+
+    fn get_cookie_header_value(&self, site_url: &Url, url: &Url) -> Option<String> {
+        // SameSite cookies don't get sent along with requests made by other sites
+        let is_same_site: bool = is_same_site(site_url, url);
+        let mut cookies: Vec<&Cookie> = self
+            .cookies
+            .iter()
+            .filter(|cookie| {
+                !cookie.is_expired()
+                    && cookie.matches_url(url.as_str())
+                    && (is_same_site || cookie.same_site == CookieSameSite::None)
+            })
+            .collect();
+
+        if cookies.is_empty() {
+            return None;
+        }
+
+        // Cookies with more specific paths go first
+        cookies.sort_by_key(|cookie| std::cmp::Reverse(cookie.path.len()));
+
+        Some(
+            cookies
+                .iter()
+                .map(|cookie| format!("{}={}", cookie.name, cookie.value))
+                .collect::<Vec<String>>()
+                .join("; "),
+        )
+    }
+
+    pub fn get_cookies(&self) -> &[Cookie] {
+        &self.cookies
+    }
+
+    fn get_document_url(&self) -> Option<&Url> {
+        // Documents within srcdoc attributes share URL with their parent document
+        self.frames
+            .iter()
+            .rev()
+            .find(|frame_url| frame_url.as_str() != "about:srcdoc")
+    }
+
+    pub fn get_environment_encoding(&self) -> &'static Encoding {
+        // Nested stylesheets fall back to encoding of whatever refers to them
+        self.encodings.last().copied().unwrap_or(UTF_8)
+    }
+
     pub fn retrieve_asset(
         &mut self,
         parent_url: &Url,
         url: &Url,
+        resource_type: Option<ResourceType>,
     ) -> Result<(Vec<u8>, Url, String, String), reqwest::Error> {
         let cache_key: String = clean_url(url.clone()).as_str().to_string();
+        // Fall back to file extensions when callers can't tell what kind of asset it is
+        let resource_type: Option<ResourceType> =
+            resource_type.or_else(|| guess_resource_type(url));
 
         if !self.urls.contains(&url.as_str().to_string()) {
             self.urls.push(url.as_str().to_string());
         }
 
+        if url.scheme() != "data"
+            && !self.allows_request(parent_url, url, resource_type, parent_url == url)
+        {
+            return Err(self.client.get("").send().unwrap_err());
+        }
+
         if url.scheme() == "data" {
             let (media_type, charset, data) = parse_data_url(url);
             Ok((data, url.clone(), media_type, charset))
@@ -131,36 +310,81 @@ impl Session {
                 self.cache.as_ref().unwrap().get(&cache_key).unwrap().2,
             ))
         } else {
-            if let Some(domains) = &self.options.domains {
-                let domain_matches = domains
-                    .iter()
-                    .any(|d| domain_is_within_domain(url.host_str().unwrap(), d.trim()));
-                if (self.options.blacklist_domains && domain_matches)
-                    || (!self.options.blacklist_domains && !domain_matches)
+            // URL not in cache, we retrieve the file
+            // (redirects are followed manually to keep cookies which get set along the way)
+            let mut request_url: Url = url.clone();
+            let mut redirect_count: usize = 0;
+            let response = loop {
+                let mut headers = HeaderMap::new();
+                // Target documents are requested as top-level navigations, which count as same-site
+                let site_url: &Url = if parent_url == url {
+                    &request_url
+                } else {
+                    parent_url
+                };
+                if let Some(cookie_header_value) =
+                    self.get_cookie_header_value(site_url, &request_url)
                 {
-                    return Err(self.client.get("").send().unwrap_err());
+                    if let Ok(header_value) = HeaderValue::from_str(&cookie_header_value) {
+                        headers.insert(COOKIE, header_value);
+                    }
+                }
+                // Add referer header for page resource requests
+                if ["https", "http"].contains(&parent_url.scheme()) && parent_url != url {
+                    headers.insert(
+                        REFERER,
+                        HeaderValue::from_str(get_referer_url(parent_url.clone()).as_str())
+                            .unwrap(),
+                    );
                 }
-            }
 
-            // URL not in cache, we retrieve the file
-            let mut headers = HeaderMap::new();
-            if self.cookies.is_some() && !self.cookies.as_ref().unwrap().is_empty() {
-                for cookie in self.cookies.as_ref().unwrap() {
-                    if !cookie.is_expired() && cookie.matches_url(url.as_str()) {
-                        let cookie_header_value: String = cookie.name.clone() + "=" + &cookie.value;
-                        headers
-                            .insert(COOKIE, HeaderValue::from_str(&cookie_header_value).unwrap());
+                match self
+                    .client
+                    .get(request_url.as_str())
+                    .headers(headers)
+                    .send()
+                {
+                    Ok(response) => {
+                        for set_cookie_header in response.headers().get_all(SET_COOKIE) {
+                            if let Some(cookie) = set_cookie_header
+                                .to_str()
+                                .ok()
+                                .and_then(|value| parse_set_cookie_header(value, &request_url))
+                            {
+                                store_cookie(&mut self.cookies, cookie);
+                            }
+                        }
+
+                        let location: Option<Url> = response
+                            .headers()
+                            .get(LOCATION)
+                            .and_then(|header| header.to_str().ok())
+                            .and_then(|location| request_url.join(location).ok());
+                        match location {
+                            Some(location)
+                                if response.status().is_redirection()
+                                    && redirect_count < MAX_REDIRECTS =>
+                            {
+                                // Redirects must not lead to places which are off-limits
+                                if !self.allows_request(
+                                    parent_url,
+                                    &location,
+                                    resource_type,
+                                    parent_url == url,
+                                ) {
+                                    return Err(self.client.get("").send().unwrap_err());
+                                }
+
+                                request_url = location;
+                                redirect_count += 1;
+                            }
+                            _ => break Ok(response),
+                        }
                     }
+                    Err(error) => break Err(error),
                 }
-            }
-            // Add referer header for page resource requests
-            if ["https", "http"].contains(&parent_url.scheme()) && parent_url != url {
-                headers.insert(
-                    REFERER,
-                    HeaderValue::from_str(get_referer_url(parent_url.clone()).as_str()).unwrap(),
-                );
-            }
-            match self.client.get(url.as_str()).headers(headers).send() {
+            };
+            match response {
                 Ok(response) => {
                     if !self.options.ignore_errors && response.status() != reqwest::StatusCode::OK {
                         if !self.options.silent {
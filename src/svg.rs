@@ -0,0 +1,198 @@
+use regex::{Captures, Regex};
+
+use crate::css::{create_stylesheet_data_url, embed_css, embed_stylesheet};
+use crate::filters::ResourceType;
+use crate::session::Session;
+use crate::url::{clean_url, create_data_url, resolve_url, Url};
+
+const SVG_ELEMENTS_WITH_ASSET_HREFS: &[&str] = &["feImage", "image", "use"];
+// Matches things within SVG documents which may reference external resources
+const SVG_MARKUP_REGEX: &str = r"(?s)<!\[CDATA\[.*?\]\]>|<!--.*?-->|<\?xml-stylesheet\b.*?\?>|<style\b[^>]*>.*?</style>|<[A-Za-z][^>]*>";
+const SVG_ATTR_REGEX: &str =
+    r#"(?P<prefix>\s)(?P<name>(?:xlink:)?href|style)\s*=\s*(?P<value>"[^"]*"|'[^']*')"#;
+
+pub fn embed_svg(session: &mut Session, svg_url: &Url, svg: &[u8]) -> Vec<u8> {
+    let mut svg_stack: Vec<String> = vec![];
+
+    process_svg(session, svg_url, svg, &mut svg_stack)
+}
+
+fn process_svg(
+    session: &mut Session,
+    svg_url: &Url,
+    svg: &[u8],
+    svg_stack: &mut Vec<String>,
+) -> Vec<u8> {
+    // SVG documents that aren't UTF-8 get embedded as they are
+    let svg_string: String = match String::from_utf8(svg.to_vec()) {
+        Ok(svg_string) => svg_string,
+        Err(_) => return svg.to_vec(),
+    };
+    let svg_key: String = clean_url(svg_url.clone()).to_string();
+
+    // Prevent SVG documents from embedding themselves
+    if svg_stack.contains(&svg_key) {
+        return svg.to_vec();
+    }
+    svg_stack.push(svg_key);
+
+    let markup_re = Regex::new(SVG_MARKUP_REGEX).unwrap();
+    let result: String = markup_re
+        .replace_all(&svg_string, |captures: &Captures| {
+            let markup: &str = &captures[0];
+
+            if markup.starts_with("<![CDATA[") || markup.starts_with("<!--") {
+                markup.to_string()
+            } else if markup.starts_with("<?xml-stylesheet") {
+                embed_svg_attrs(session, svg_url, markup, true, svg_stack)
+            } else if markup.starts_with("<style") {
+                let content_start: usize = markup.find('>').unwrap() + 1;
+                let content_end: usize = markup.len() - "</style>".len();
+                let opening_tag: &str = &markup[..content_start];
+                let content: &str = &markup[content_start..content_end];
+
+                let embedded_content: String = if let Some(cdata) = content
+                    .trim()
+                    .strip_prefix("<![CDATA[")
+                    .and_then(|c| c.strip_suffix("]]>"))
+                {
+                    format!("<![CDATA[{}]]>", embed_css(session, svg_url, cdata))
+                } else {
+                    escape_xml(&embed_css(session, svg_url, &unescape_xml(content)), None)
+                };
+
+                format!(
+                    "{}{}</style>",
+                    embed_svg_attrs(session, svg_url, opening_tag, false, svg_stack),
+                    embedded_content
+                )
+            } else {
+                embed_svg_attrs(session, svg_url, markup, false, svg_stack)
+            }
+        })
+        .to_string();
+
+    svg_stack.pop();
+
+    result.into_bytes()
+}
+
+fn embed_svg_attrs(
+    session: &mut Session,
+    svg_url: &Url,
+    tag: &str,
+    is_stylesheet_instruction: bool,
+    svg_stack: &mut Vec<String>,
+) -> String {
+    let tag_name: &str = tag
+        .trim_start_matches('<')
+        .split(|c: char| c.is_whitespace() || c == '>' || c == '/')
+        .next()
+        .unwrap_or_default();
+    let attr_re = Regex::new(SVG_ATTR_REGEX).unwrap();
+
+    attr_re
+        .replace_all(tag, |captures: &Captures| {
+            let attr_name: &str = &captures["name"];
+            let quoted_attr_value: &str = &captures["value"];
+            let quote: char = quoted_attr_value.chars().next().unwrap();
+            let attr_value: String =
+                unescape_xml(&quoted_attr_value[1..quoted_attr_value.len() - 1]);
+
+            let embedded_attr_value: Option<String> = if attr_name == "style" {
+                Some(embed_css(session, svg_url, &attr_value))
+            } else if is_stylesheet_instruction || SVG_ELEMENTS_WITH_ASSET_HREFS.contains(&tag_name)
+            {
+                embed_svg_href(
+                    session,
+                    svg_url,
+                    &attr_value,
+                    is_stylesheet_instruction,
+                    svg_stack,
+                )
+            } else {
+                None
+            };
+
+            match embedded_attr_value {
+                Some(embedded_attr_value) => format!(
+                    "{}{}={}{}{}",
+                    &captures["prefix"],
+                    attr_name,
+                    quote,
+                    escape_xml(&embedded_attr_value, Some(quote)),
+                    quote
+                ),
+                None => captures[0].to_string(),
+            }
+        })
+        .to_string()
+}
+
+fn embed_svg_href(
+    session: &mut Session,
+    svg_url: &Url,
+    href: &str,
+    is_stylesheet: bool,
+    svg_stack: &mut Vec<String>,
+) -> Option<String> {
+    let href: &str = href.trim();
+
+    // Local references and data URLs don't need to be embedded
+    if href.is_empty() || href.starts_with('#') || href.starts_with("data:") {
+        return None;
+    }
+
+    let resolved_url: Url = resolve_url(svg_url, href);
+
+    let resource_type: ResourceType = if is_stylesheet {
+        ResourceType::Stylesheet
+    } else {
+        ResourceType::Image
+    };
+
+    match session.retrieve_asset(svg_url, &resolved_url, Some(resource_type)) {
+        Ok((data, final_url, media_type, charset)) => {
+            let mut data_url: Url = if is_stylesheet || media_type == "text/css" {
+                let css: String = embed_stylesheet(session, &final_url, &data, &charset);
+                create_stylesheet_data_url("text/css", &css, &final_url)
+            } else if media_type == "image/svg+xml" {
+                let svg: Vec<u8> = process_svg(session, &final_url, &data, svg_stack);
+                create_data_url(&media_type, &charset, &svg, &final_url)
+            } else {
+                create_data_url(&media_type, &charset, &data, &final_url)
+            };
+            data_url.set_fragment(resolved_url.fragment());
+
+            Some(data_url.to_string())
+        }
+        Err(_) => {
+            if resolved_url.scheme() == "http" || resolved_url.scheme() == "https" {
+                // Keep remote reference if unable to retrieve the asset
+                Some(resolved_url.to_string())
+            } else {
+                None
+            }
+        }
+    }
+}
+
+fn escape_xml(text: &str, quote: Option<char>) -> String {
+    let mut result: String = text.replace('&', "&amp;").replace('<', "&lt;");
+
+    match quote {
+        Some('"') => result = result.replace('"', "&quot;"),
+        Some('\'') => result = result.replace('\'', "&apos;"),
+        _ => {}
+    }
+
+    result
+}
+
+fn unescape_xml(text: &str) -> String {
+    text.replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
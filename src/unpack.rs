@@ -0,0 +1,223 @@
+use cssparser::{ParseError, Parser, ParserInput, SourcePosition, Token};
+use encoding_rs::UTF_8;
+use html5ever::tendril::format_tendril;
+use markup5ever_rcdom::{Handle, NodeData};
+use sha2::{Digest, Sha256};
+
+use crate::core::{detect_media_type, get_file_extension_by_media_type, MonolithOptions};
+use crate::css::{decode_stylesheet, format_quoted_string};
+use crate::html::{get_node_name, parse_html_document, parse_srcset, serialize_document};
+use crate::url::{parse_data_url, Url};
+
+pub struct UnpackedAsset {
+    pub file_name: String,
+    pub data: Vec<u8>,
+}
+
+pub fn unpack_css(css: &str, assets: &mut Vec<UnpackedAsset>) -> String {
+    let mut input = ParserInput::new(css);
+    let mut parser = Parser::new(&mut input);
+
+    unpack_css_tokens(&mut parser, assets)
+}
+
+pub fn unpack_data_url(data_url: &str, assets: &mut Vec<UnpackedAsset>) -> Option<String> {
+    let url: Url = Url::parse(data_url.trim())
+        .ok()
+        .filter(|url| url.scheme() == "data")?;
+    let (media_type, charset, data) = parse_data_url(&url);
+
+    // Data URLs which don't specify media type default to text/plain
+    let media_type: String = if media_type.eq_ignore_ascii_case("text/plain")
+        || media_type.eq_ignore_ascii_case("application/octet-stream")
+    {
+        let detected_media_type: String = detect_media_type(&data, &url);
+        if detected_media_type.is_empty() {
+            media_type
+        } else {
+            detected_media_type
+        }
+    } else {
+        media_type
+    };
+
+    // Stylesheets and frames may contain data URLs of their own
+    let data: Vec<u8> = match media_type.to_lowercase().as_str() {
+        "text/css" => {
+            let (css, _) = decode_stylesheet(&data, &charset, UTF_8);
+            unpack_css(&css, assets).into_bytes()
+        }
+        "text/html" | "application/xhtml+xml" => unpack_html_document(&data, &charset, assets),
+        _ => data,
+    };
+
+    let mut file_name: String = format!("{:x}", Sha256::digest(&data))[..16].to_string();
+    let extension: &str = get_file_extension_by_media_type(&media_type);
+    if !extension.is_empty() {
+        file_name.push('.');
+        file_name.push_str(extension);
+    }
+
+    // Identical assets get written out only once
+    if !assets.iter().any(|asset| asset.file_name == file_name) {
+        assets.push(UnpackedAsset {
+            file_name: file_name.clone(),
+            data,
+        });
+    }
+
+    // Fragments point to parts of assets (e.g. SVG symbols)
+    Some(match url.fragment() {
+        Some(fragment) => format!("{}#{}", file_name, fragment),
+        None => file_name,
+    })
+}
+
+pub fn unpack_html_document(
+    data: &[u8],
+    charset: &str,
+    assets: &mut Vec<UnpackedAsset>,
+) -> Vec<u8> {
+    let (dom, document_encoding) = parse_html_document(data, charset);
+
+    unpack_node(&dom.document, assets);
+
+    serialize_document(dom, document_encoding, &MonolithOptions::default())
+}
+
+pub fn unpack_monolithic_document(data: &[u8]) -> (Vec<u8>, Vec<UnpackedAsset>) {
+    let mut assets: Vec<UnpackedAsset> = vec![];
+    let document: Vec<u8> = unpack_html_document(data, "", &mut assets);
+
+    (document, assets)
+}
+
+pub fn unpack_srcset(srcset: &str, assets: &mut Vec<UnpackedAsset>) -> String {
+    parse_srcset(srcset)
+        .iter()
+        .map(|srcset_item| {
+            let path: String = if is_data_url(srcset_item.path) {
+                unpack_data_url(srcset_item.path, assets)
+                    .unwrap_or_else(|| srcset_item.path.to_string())
+            } else {
+                srcset_item.path.to_string()
+            };
+
+            if srcset_item.descriptor.is_empty() {
+                path
+            } else {
+                format!("{} {}", path, srcset_item.descriptor)
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+fn is_data_url(value: &str) -> bool {
+    value
+        .trim_start()
+        .get(..5)
+        .is_some_and(|scheme| scheme.eq_ignore_ascii_case("data:"))
+}
+
+fn unpack_css_tokens(parser: &mut Parser, assets: &mut Vec<UnpackedAsset>) -> String {
+    let mut result: String = "".to_string();
+    let mut token_offset: SourcePosition;
+
+    loop {
+        token_offset = parser.position();
+        let token: Token = match parser.next_including_whitespace_and_comments() {
+            Ok(token) => token.clone(),
+            Err(_) => {
+                break;
+            }
+        };
+
+        match token {
+            // Quoted strings which are data URLs can only be found within url(), image-set() and @import
+            Token::UnquotedUrl(ref value) | Token::QuotedString(ref value)
+                if is_data_url(value) =>
+            {
+                match unpack_data_url(value, assets) {
+                    Some(file_name) if matches!(token, Token::UnquotedUrl(_)) => {
+                        result.push_str(&format!("url({})", format_quoted_string(&file_name)));
+                    }
+                    Some(file_name) => {
+                        result.push_str(&format_quoted_string(&file_name));
+                    }
+                    None => {
+                        result.push_str(parser.slice_from(token_offset));
+                    }
+                }
+            }
+            Token::Function(_)
+            | Token::ParenthesisBlock
+            | Token::SquareBracketBlock
+            | Token::CurlyBracketBlock => {
+                let closing_char: char = match token {
+                    Token::SquareBracketBlock => ']',
+                    Token::CurlyBracketBlock => '}',
+                    _ => ')',
+                };
+
+                result.push_str(parser.slice_from(token_offset));
+                result.push_str(
+                    &parser
+                        .parse_nested_block(|parser| -> Result<String, ParseError<()>> {
+                            Ok(unpack_css_tokens(parser, assets))
+                        })
+                        .unwrap_or_default(),
+                );
+                result.push(closing_char);
+            }
+            _ => {
+                result.push_str(parser.slice_from(token_offset));
+            }
+        }
+    }
+
+    result
+}
+
+fn unpack_node(node: &Handle, assets: &mut Vec<UnpackedAsset>) {
+    if let NodeData::Element { ref attrs, .. } = node.data {
+        for attr in attrs.borrow_mut().iter_mut() {
+            let attr_value: String = attr.value.to_string();
+            let unpacked_attr_value: Option<String> = match attr.name.local.as_ref() {
+                "imagesrcset" | "srcset" if attr_value.contains("data:") => {
+                    Some(unpack_srcset(&attr_value, assets))
+                }
+                "srcdoc" if attr_value.contains("data:") => Some(
+                    String::from_utf8_lossy(&unpack_html_document(
+                        attr_value.as_bytes(),
+                        "utf-8",
+                        assets,
+                    ))
+                    .to_string(),
+                ),
+                "style" if attr_value.contains("data:") => Some(unpack_css(&attr_value, assets)),
+                _ if is_data_url(&attr_value) => unpack_data_url(&attr_value, assets),
+                _ => None,
+            };
+
+            if let Some(unpacked_attr_value) = unpacked_attr_value {
+                attr.value = format_tendril!("{}", unpacked_attr_value);
+            }
+        }
+
+        if get_node_name(node) == Some("style") {
+            for child_node in node.children.borrow().iter() {
+                if let NodeData::Text { ref contents } = child_node.data {
+                    let css: String = contents.borrow().to_string();
+                    if css.contains("data:") {
+                        *contents.borrow_mut() = format_tendril!("{}", unpack_css(&css, assets));
+                    }
+                }
+            }
+        }
+    }
+
+    for child_node in node.children.borrow().iter() {
+        unpack_node(child_node, assets);
+    }
+}
@@ -6,6 +6,51 @@ use crate::core::{detect_media_type, parse_content_type};
 
 pub const EMPTY_IMAGE_DATA_URL: &str = "data:image/png,\
 %89PNG%0D%0A%1A%0A%00%00%00%0DIHDR%00%00%00%0D%00%00%00%0D%08%04%00%00%00%D8%E2%2C%F7%00%00%00%11IDATx%DAcd%C0%09%18G%A5%28%96%02%00%0A%F8%00%0E%CB%8A%EB%16%00%00%00%00IEND%AEB%60%82";
+// Query parameters used for attributing visits to campaigns and ad clicks (along with "utm_*" ones)
+const TRACKING_QUERY_PARAMS: &[&str] = &[
+    "_ga",
+    "_gl",
+    "_hsenc",
+    "_hsmi",
+    "dclid",
+    "fbclid",
+    "gbraid",
+    "gclid",
+    "gclsrc",
+    "igshid",
+    "mc_cid",
+    "mc_eid",
+    "mkt_tok",
+    "msclkid",
+    "oly_anon_id",
+    "oly_enc_id",
+    "ttclid",
+    "twclid",
+    "vero_id",
+    "wbraid",
+    "yclid",
+];
+// Hosts (and paths) which serve nothing but analytics scripts and tracking pixels
+pub const TRACKERS: &[&str] = &[
+    ".analytics.tiktok.com",
+    ".bat.bing.com",
+    ".clarity.ms",
+    ".connect.facebook.net",
+    ".doubleclick.net",
+    ".facebook.com/tr",
+    ".google-analytics.com",
+    ".googletagmanager.com",
+    ".hotjar.com",
+    ".mc.yandex.ru",
+    ".mxpnl.com",
+    ".px.ads.linkedin.com",
+    ".quantserve.com",
+    ".scorecardresearch.com",
+    ".snap.licdn.com",
+    ".static.ads-twitter.com",
+    ".stats.wp.com",
+    ".t.co/i/adsct",
+];
 
 pub fn clean_url(url: Url) -> Url {
     let mut url = url.clone();
@@ -102,6 +147,48 @@ pub fn domain_is_within_domain(domain: &str, domain_to_match_against: &str) -> b
     ok
 }
 
+pub fn is_same_site(url1: &Url, url2: &Url) -> bool {
+    let (host1, host2) = match (url1.host_str(), url2.host_str()) {
+        (Some(host1), Some(host2)) => (host1.to_lowercase(), host2.to_lowercase()),
+        // Documents which aren't served over network don't belong to any site
+        _ => return true,
+    };
+
+    if host1 == host2 {
+        return true;
+    }
+
+    // Without a list of public suffixes, the last two labels are as close to the site as it gets
+    let get_site = |host: &str| -> String {
+        if host.parse::<std::net::IpAddr>().is_ok() || host.starts_with('[') {
+            return host.to_string();
+        }
+
+        let labels: Vec<&str> = host.trim_end_matches('.').rsplit('.').take(2).collect();
+        labels.into_iter().rev().collect::<Vec<&str>>().join(".")
+    };
+
+    get_site(&host1) == get_site(&host2)
+}
+
+pub fn is_tracker_url(url: &Url) -> bool {
+    let Some(host) = url.host_str() else {
+        return false;
+    };
+
+    TRACKERS.iter().any(|tracker| {
+        let (domain, path) = tracker.split_once('/').unwrap_or((tracker, ""));
+
+        domain_is_within_domain(host, domain)
+            && (path.is_empty()
+                || url
+                    .path()
+                    .strip_prefix('/')
+                    .and_then(|url_path| url_path.strip_prefix(path))
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with('/')))
+    })
+}
+
 pub fn is_url_and_has_protocol(input: &str) -> bool {
     match Url::parse(input) {
         Ok(parsed_url) => !parsed_url.scheme().is_empty(),
@@ -120,12 +207,12 @@ pub fn parse_data_url(url: &Url) -> (String, String, Vec<u8>) {
     // Parse meta data
     let (media_type, charset, is_base64) = parse_content_type(&content_type);
 
-    // Parse raw data into vector of bytes
-    let text: String = percent_decode_str(&data).decode_utf8_lossy().to_string();
+    // Parse raw data into vector of bytes (percent-encoded data doesn't have to be UTF-8)
     let blob: Vec<u8> = if is_base64 {
+        let text: String = percent_decode_str(&data).decode_utf8_lossy().to_string();
         BASE64_STANDARD.decode(&text).unwrap_or_default()
     } else {
-        text.as_bytes().to_vec()
+        percent_decode_str(&data).collect()
     };
 
     (media_type, charset, blob)
@@ -142,6 +229,27 @@ pub fn get_referer_url(url: Url) -> Url {
     url
 }
 
+pub fn remove_tracking_params(url: Url) -> Url {
+    let mut url = url.clone();
+
+    if let Some(query) = url.query() {
+        // Rest of the query is kept the way it was, re-encoding it could change its meaning
+        let query: String = query
+            .split('&')
+            .filter(|param| {
+                let name: String = param.split('=').next().unwrap_or_default().to_lowercase();
+
+                !name.starts_with("utm_") && !TRACKING_QUERY_PARAMS.contains(&name.as_str())
+            })
+            .collect::<Vec<&str>>()
+            .join("&");
+
+        url.set_query(if query.is_empty() { None } else { Some(&query) });
+    }
+
+    url
+}
+
 pub fn resolve_url(from: &Url, to: &str) -> Url {
     match Url::parse(to) {
         Ok(parsed_url) => parsed_url,
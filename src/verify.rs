@@ -0,0 +1,319 @@
+use base64::alphabet::STANDARD;
+use base64::engine::{DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig};
+use base64::Engine;
+use encoding_rs::UTF_8;
+use markup5ever_rcdom::{Handle, NodeData};
+use percent_encoding::percent_decode_str;
+
+use crate::core::{parse_content_type, MonolithOptions};
+use crate::css::{decode_stylesheet, get_css_urls};
+use crate::html::{
+    compose_csp, find_nodes, get_base_url, get_node_attr, get_node_name, get_parent_element,
+    parse_html_document, parse_link_type, parse_srcset, LinkType,
+};
+use crate::url::{parse_data_url, resolve_url, Url};
+
+// Attributes which only get followed once the user interacts with the document
+const NAVIGATION_ATTRS: &[&str] = &["action", "cite", "formaction", "ping"];
+const NAVIGATION_ELEMENTS: &[&str] = &["a", "area", "base"];
+const REMOTE_SCHEMES: &[&str] = &["ftp", "http", "https", "ws", "wss"];
+const URL_ATTRS: &[&str] = &[
+    "action",
+    "background",
+    "cite",
+    "data",
+    "formaction",
+    "href",
+    "manifest",
+    "ping",
+    "poster",
+    "src",
+];
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ReferenceKind {
+    Link,       // Only gets followed once the user interacts with the document
+    Navigation, // Gets followed by the browser on its own, not governed by CSP
+    Subresource,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct DocumentReference {
+    pub location: String,
+    pub url: String,
+}
+
+#[derive(Debug, Default)]
+pub struct VerificationReport {
+    pub broken_data_urls: Vec<DocumentReference>,
+    pub csp: Option<String>,
+    is_csp_in_effect: bool, // Content Security Policy only applies to elements which come after it
+    pub network_requests: Vec<DocumentReference>,
+    pub remote_references: Vec<DocumentReference>,
+    pub unblocked_requests: Vec<DocumentReference>, // Requests which CSP doesn't prevent
+}
+
+impl VerificationReport {
+    pub fn is_isolated(&self) -> bool {
+        self.csp.as_ref().is_some_and(|csp| is_isolation_csp(csp))
+    }
+
+    pub fn makes_network_requests(&self) -> bool {
+        !self.unblocked_requests.is_empty()
+    }
+}
+
+pub fn verify_document(data: &[u8]) -> VerificationReport {
+    let mut report: VerificationReport = VerificationReport::default();
+    let (dom, _) = parse_html_document(data, "");
+
+    for meta_node in find_nodes(&dom.document, vec!["html", "head", "meta"]).iter() {
+        if get_node_attr(meta_node, "http-equiv")
+            .is_some_and(|http_equiv| http_equiv.eq_ignore_ascii_case("content-security-policy"))
+        {
+            report.csp = get_node_attr(meta_node, "content");
+            break;
+        }
+    }
+
+    verify_html_document(&mut report, &dom.document, "document");
+
+    report
+}
+
+fn check_css(report: &mut VerificationReport, base_url: Option<&Url>, location: &str, css: &str) {
+    for reference in get_css_urls(css) {
+        check_reference(
+            report,
+            base_url,
+            location,
+            &reference,
+            ReferenceKind::Subresource,
+        );
+    }
+}
+
+fn check_data_url(report: &mut VerificationReport, location: &str, url: &Url) {
+    let path: &str = url.path();
+    let is_decodable: bool = match path.find(',') {
+        Some(comma_loc) => {
+            let (_, _, is_base64) = parse_content_type(&path[..comma_loc]);
+            let forgiving_base64: GeneralPurpose = GeneralPurpose::new(
+                &STANDARD,
+                GeneralPurposeConfig::new()
+                    .with_decode_padding_mode(DecodePaddingMode::Indifferent),
+            );
+            let data: String = percent_decode_str(&path[comma_loc + 1..])
+                .decode_utf8_lossy()
+                .chars()
+                .filter(|c| !c.is_ascii_whitespace())
+                .collect();
+
+            !is_base64 || forgiving_base64.decode(data).is_ok()
+        }
+        None => false,
+    };
+
+    if !is_decodable {
+        report.broken_data_urls.push(DocumentReference {
+            location: location.to_string(),
+            url: url.as_str().chars().take(64).collect(),
+        });
+        return;
+    }
+
+    // Stylesheets and frames embedded as data URLs may refer to remote resources too
+    let (media_type, charset, data) = parse_data_url(url);
+    match media_type.to_lowercase().as_str() {
+        "text/css" => {
+            let (css, _) = decode_stylesheet(&data, &charset, UTF_8);
+            check_css(report, None, location, &css);
+        }
+        "text/html" | "application/xhtml+xml" => {
+            let (dom, _) = parse_html_document(&data, &charset);
+            verify_html_document(report, &dom.document, location);
+        }
+        _ => {}
+    }
+}
+
+fn check_reference(
+    report: &mut VerificationReport,
+    base_url: Option<&Url>,
+    location: &str,
+    reference: &str,
+    kind: ReferenceKind,
+) {
+    let reference: &str = reference.trim();
+    if reference.is_empty() || reference.starts_with('#') {
+        return;
+    }
+
+    let url: Option<Url> = match Url::parse(reference) {
+        Ok(url) => Some(url),
+        Err(_) => match base_url {
+            Some(base_url) => Some(resolve_url(base_url, reference)),
+            // Scheme-relative URLs point to remote hosts when documents get served over HTTP
+            None if reference.starts_with("//") => Url::parse(&format!("https:{}", reference)).ok(),
+            None => None,
+        },
+    };
+
+    match url {
+        Some(url) if url.scheme() == "data" => check_data_url(report, location, &url),
+        Some(url) if REMOTE_SCHEMES.contains(&url.scheme()) => {
+            let document_reference = || DocumentReference {
+                location: location.to_string(),
+                url: url.to_string(),
+            };
+
+            if kind != ReferenceKind::Link {
+                report.network_requests.push(document_reference());
+
+                // Content Security Policy of isolated documents blocks all remote subresources
+                if kind == ReferenceKind::Navigation || !report.is_csp_in_effect {
+                    report.unblocked_requests.push(document_reference());
+                }
+            }
+            report.remote_references.push(document_reference());
+        }
+        _ => {}
+    }
+}
+
+fn is_isolation_csp(csp: &str) -> bool {
+    let isolation_csp: String = compose_csp(&MonolithOptions {
+        isolate: true,
+        ..Default::default()
+    });
+
+    csp.contains(&isolation_csp)
+}
+
+fn get_reference_kind(node: &Handle, attr_name: &str) -> ReferenceKind {
+    let node_name: &str = get_node_name(node).unwrap_or_default();
+
+    if NAVIGATION_ATTRS.contains(&attr_name)
+        || (attr_name == "href" && NAVIGATION_ELEMENTS.contains(&node_name))
+    {
+        return ReferenceKind::Link;
+    }
+
+    // Links such as rel="canonical" don't make browsers retrieve anything
+    if node_name == "link"
+        && attr_name == "href"
+        && parse_link_type(&get_node_attr(node, "rel").unwrap_or_default())
+            .iter()
+            .all(|link_type| *link_type == LinkType::Alternate)
+    {
+        return ReferenceKind::Link;
+    }
+
+    ReferenceKind::Subresource
+}
+
+fn verify_html_document(report: &mut VerificationReport, document: &Handle, location: &str) {
+    let base_url: Option<Url> = get_base_url(document)
+        .and_then(|base_href| Url::parse(&base_href).ok())
+        .filter(|base_url| REMOTE_SCHEMES.contains(&base_url.scheme()));
+
+    verify_node(report, base_url.as_ref(), document, location);
+}
+
+fn verify_node(
+    report: &mut VerificationReport,
+    base_url: Option<&Url>,
+    node: &Handle,
+    parent_location: &str,
+) {
+    if let NodeData::Element { ref attrs, .. } = node.data {
+        let node_name: &str = get_node_name(node).unwrap_or_default();
+        let attrs: Vec<(String, String)> = attrs
+            .borrow()
+            .iter()
+            .map(|attr| (attr.name.local.to_string(), attr.value.to_string()))
+            .collect();
+
+        for (attr_name, attr_value) in attrs {
+            let location: String = if parent_location == "document" {
+                format!("{}[{}]", node_name, attr_name)
+            } else {
+                format!("{} > {}[{}]", parent_location, node_name, attr_name)
+            };
+
+            match attr_name.as_str() {
+                "imagesrcset" | "srcset" => {
+                    for srcset_item in parse_srcset(&attr_value) {
+                        check_reference(
+                            report,
+                            base_url,
+                            &location,
+                            srcset_item.path,
+                            ReferenceKind::Subresource,
+                        );
+                    }
+                }
+                "srcdoc" => {
+                    let (dom, _) = parse_html_document(attr_value.as_bytes(), "utf-8");
+                    verify_html_document(report, &dom.document, &location);
+                }
+                "style" => {
+                    check_css(report, base_url, &location, &attr_value);
+                }
+                // Refresh directives redirect as soon as documents get opened
+                "content"
+                    if node_name == "meta"
+                        && get_node_attr(node, "http-equiv").is_some_and(|http_equiv| {
+                            http_equiv.eq_ignore_ascii_case("refresh")
+                        }) =>
+                {
+                    if let Some((_, refresh_url)) = attr_value.split_once('=') {
+                        let refresh_url: &str = refresh_url.trim().trim_matches(['"', '\'']);
+                        check_reference(
+                            report,
+                            base_url,
+                            &location,
+                            refresh_url,
+                            ReferenceKind::Navigation,
+                        );
+                    }
+                }
+                _ if URL_ATTRS.contains(&attr_name.as_str()) => {
+                    let kind: ReferenceKind = get_reference_kind(node, &attr_name);
+                    check_reference(report, base_url, &location, &attr_value, kind);
+                }
+                _ => {}
+            }
+        }
+
+        // Elements which come after the isolation policy can't reach the network
+        if node_name == "meta"
+            && parent_location == "document"
+            && get_parent_element(node).is_some_and(|parent| get_node_name(&parent) == Some("head"))
+            && get_node_attr(node, "http-equiv").is_some_and(|http_equiv| {
+                http_equiv.eq_ignore_ascii_case("content-security-policy")
+            })
+            && get_node_attr(node, "content").is_some_and(|csp| is_isolation_csp(&csp))
+        {
+            report.is_csp_in_effect = true;
+        }
+
+        if node_name == "style" {
+            let location: String = if parent_location == "document" {
+                node_name.to_string()
+            } else {
+                format!("{} > {}", parent_location, node_name)
+            };
+
+            for child_node in node.children.borrow().iter() {
+                if let NodeData::Text { ref contents } = child_node.data {
+                    check_css(report, base_url, &location, &contents.borrow());
+                }
+            }
+        }
+    }
+
+    for child_node in node.children.borrow().iter() {
+        verify_node(report, base_url, child_node, parent_location);
+    }
+}
@@ -0,0 +1,5 @@
+import "../main.js";
+
+export function greet(name) {
+    console.log("Hello, " + name);
+}
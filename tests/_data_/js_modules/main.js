@@ -0,0 +1,5 @@
+import { greet } from "./lib/greet.js";
+import config from './config.json' with { type: "json" };
+export * from "./lib/greet.js";
+
+greet(config.name);
@@ -243,6 +243,29 @@ mod failing {
         out.assert().code(1);
     }
 
+    #[test]
+    fn bad_url_rule() {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        let out = cmd
+            .arg("--exclude-url")
+            .arg("img:*.png")
+            .arg("https://example.com")
+            .output()
+            .unwrap();
+
+        // STDERR should contain error description
+        assert_eq!(
+            String::from_utf8_lossy(&out.stderr),
+            "could not parse URL rule \"img:*.png\"\n"
+        );
+
+        // STDOUT should be empty
+        assert_eq!(String::from_utf8_lossy(&out.stdout), "");
+
+        // Exit code should be 1
+        out.assert().code(1);
+    }
+
     #[test]
     fn unsupported_scheme() {
         let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
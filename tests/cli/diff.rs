@@ -0,0 +1,89 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use assert_cmd::prelude::*;
+    use assert_cmd::Command;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn diff_output_file() {
+        let dir = tempdir().unwrap();
+        let previous_path = dir.path().join("previous.html");
+        let diff_path = dir.path().join("diff.json");
+        fs::write(
+            &previous_path,
+            "<html><head><title>Policy</title></head><body><p>Old text</p></body></html>",
+        )
+        .unwrap();
+
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        let out = cmd
+            .arg("-M")
+            .arg("--diff-against")
+            .arg(&previous_path)
+            .arg("--diff-output")
+            .arg(&diff_path)
+            .arg("-")
+            .write_stdin(
+                "<html><head><title>Policy</title></head><body><p>New text</p></body></html>",
+            )
+            .output()
+            .unwrap();
+
+        // STDERR should be empty
+        assert_eq!(String::from_utf8_lossy(&out.stderr), "");
+
+        // The comparison should be written into the given file
+        let diff: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&diff_path).unwrap()).unwrap();
+        assert_eq!(diff["title"], serde_json::Value::Null);
+        assert_eq!(diff["text"]["removed"], serde_json::json!(["Old text"]));
+        assert_eq!(diff["text"]["added"], serde_json::json!(["New text"]));
+
+        // Exit code should be 0
+        out.assert().code(0);
+    }
+}
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use assert_cmd::prelude::*;
+    use assert_cmd::Command;
+
+    #[test]
+    fn missing_previous_document() {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        let out = cmd
+            .arg("--diff-against")
+            .arg("does-not-exist.html")
+            .arg("-")
+            .write_stdin("<html></html>")
+            .output()
+            .unwrap();
+
+        // STDERR should contain error description
+        assert_eq!(
+            String::from_utf8_lossy(&out.stderr),
+            "could not read previously saved document \"does-not-exist.html\"\n"
+        );
+
+        // STDOUT should be empty
+        assert_eq!(String::from_utf8_lossy(&out.stdout), "");
+
+        // Exit code should be 1
+        out.assert().code(1);
+    }
+}
@@ -380,8 +380,10 @@ document.body.style.color = "red";
                 r#"{file}{cwd}/tests/_data_/integrity/index.html
 {file}{cwd}/tests/_data_/integrity/style.css
 {file}{cwd}/tests/_data_/integrity/style.css
+{file}{cwd}/tests/_data_/integrity/style.css (integrity mismatch)
 {file}{cwd}/tests/_data_/integrity/script.js
 {file}{cwd}/tests/_data_/integrity/script.js
+{file}{cwd}/tests/_data_/integrity/script.js (integrity mismatch)
 "#,
                 file = file_url_protocol,
                 cwd = cwd_normalized,
@@ -416,4 +418,187 @@ document.body.style.color = "red";
         // Exit code should be 0
         out.assert().code(0);
     }
+
+    #[test]
+    fn keep_integrity_for_local_files() {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        let out = cmd
+            .arg("-M")
+            .arg("-i")
+            .arg("-q")
+            .arg("--keep-integrity")
+            .arg("--integrity-mismatch")
+            .arg("embed")
+            .arg("tests/_data_/integrity/index.html")
+            .output()
+            .unwrap();
+
+        // STDOUT should contain HTML with integrity attributes matching embedded assets
+        assert_eq!(
+            String::from_utf8_lossy(&out.stdout),
+            r##"<!DOCTYPE html><html lang="en"><head><meta http-equiv="Content-Security-Policy" content="img-src data:;"></meta>
+        <title>Local HTML file</title>
+        <link href="data:text/css;base64,Ym9keSB7CiAgICBiYWNrZ3JvdW5kLWNvbG9yOiAjMDAwOwogICAgY29sb3I6ICNGRkY7Cn0K" rel="stylesheet" type="text/css" crossorigin="anonymous" integrity="sha512-IWaCTORHkRhOWzcZeILSVmV6V6gPTHgNem6o6rsFAyaKTieDFkeeMrWjtO0DuWrX3bqZY46CVTZXUu0mia0qXQ==">
+        <link href="data:text/css;base64,Ym9keSB7CiAgICBiYWNrZ3JvdW5kLWNvbG9yOiAjMDAwOwogICAgY29sb3I6ICNGRkY7Cn0K" rel="stylesheet" type="text/css" crossorigin="anonymous" data-integrity-mismatch="sha512-vWBzl4NE9oIg8NFOPAyOZbaam0UXWr6aDHPaY2kodSzAFl+mKoj/RMNc6C31NDqK4mE2i68IWxYWqWJPLCgPOw==" integrity="sha512-IWaCTORHkRhOWzcZeILSVmV6V6gPTHgNem6o6rsFAyaKTieDFkeeMrWjtO0DuWrX3bqZY46CVTZXUu0mia0qXQ==">
+    <meta name="robots" content="none"></meta></head>
+
+    <body>
+        <p>
+            This page should have black background and white foreground, but
+            only when served via http: (not via file:)
+        </p>
+        <script src="data:text/javascript;base64,ZnVuY3Rpb24gbm9vcCgpIHsKICBjb25zb2xlLmxvZygiPC9zY3JpcHQ+Iik7Cn0K" integrity="sha256-B8CIe6TRGtUNifdy1eY4C9iK46VgAsS5URTNMjjL6+c="></script>
+        <script src="data:text/javascript;base64,ZnVuY3Rpb24gbm9vcCgpIHsKICBjb25zb2xlLmxvZygiPC9zY3JpcHQ+Iik7Cn0K" data-integrity-mismatch="sha256-6idk9dK0bOkVdG7Oz4/0YLXSJya8xZHqbRZKMhYrt6o=" integrity="sha256-B8CIe6TRGtUNifdy1eY4C9iK46VgAsS5URTNMjjL6+c="></script>
+    "##.to_owned() + r##"
+
+</body></html>
+"##
+        );
+
+        // Exit code should be 0
+        out.assert().code(0);
+    }
+
+    #[test]
+    fn exclude_local_assets_by_url_rules() {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        let cwd_normalized: String = env::current_dir()
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .replace("\\", "/");
+        let out = cmd
+            .arg("-M")
+            .arg("--exclude-url")
+            .arg("script:*/local-script.js")
+            .arg("--include-url")
+            .arg("css:/*/local-style.css")
+            .arg(format!(
+                "tests{s}_data_{s}basic{s}local-file.html",
+                s = MAIN_SEPARATOR
+            ))
+            .output()
+            .unwrap();
+        let file_url_protocol: &str = if cfg!(windows) { "file:///" } else { "file://" };
+
+        // STDERR should list assets excluded by URL rules
+        assert_eq!(
+            String::from_utf8_lossy(&out.stderr),
+            format!(
+                r#"{file}{cwd}/tests/_data_/basic/local-file.html
+{file}{cwd}/tests/_data_/basic/local-style.css
+{file}{cwd}/tests/_data_/basic/local-style-does-not-exist.css (excluded by URL rules)
+{file}{cwd}/tests/_data_/basic/monolith.png (file not found)
+{file}{cwd}/tests/_data_/basic/local-script.js (excluded by URL rules)
+"#,
+                file = file_url_protocol,
+                cwd = cwd_normalized
+            )
+        );
+
+        // STDOUT should contain HTML without the excluded script
+        assert!(!String::from_utf8_lossy(&out.stdout).contains("backgroundColor"));
+
+        // Exit code should be 0
+        out.assert().code(0);
+    }
+
+    #[test]
+    fn exclude_local_fonts_by_url_rules() {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        let cwd_normalized: String = env::current_dir()
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .replace("\\", "/");
+        let out = cmd
+            .arg("-M")
+            .arg("--exclude-url")
+            .arg("font:*")
+            .arg(format!(
+                "tests{s}_data_{s}fonts{s}font-face.html",
+                s = MAIN_SEPARATOR
+            ))
+            .output()
+            .unwrap();
+        let file_url_protocol: &str = if cfg!(windows) { "file:///" } else { "file://" };
+
+        // STDERR should list the font referenced by @font-face as excluded
+        assert_eq!(
+            String::from_utf8_lossy(&out.stderr),
+            format!(
+                r#"{file}{cwd}/tests/_data_/fonts/font-face.html
+{file}{cwd}/tests/_data_/fonts/font-face.css
+{file}{cwd}/tests/_data_/fonts/open-sans-regular.woff2 (excluded by URL rules)
+"#,
+                file = file_url_protocol,
+                cwd = cwd_normalized
+            )
+        );
+
+        // Exit code should be 0
+        out.assert().code(0);
+    }
+
+    #[test]
+    fn keep_local_fonts_excluding_images_by_url_rules() {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        let cwd_normalized: String = env::current_dir()
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .replace("\\", "/");
+        let out = cmd
+            .arg("-M")
+            .arg("--exclude-url")
+            .arg("image:*")
+            .arg(format!(
+                "tests{s}_data_{s}fonts{s}font-face.html",
+                s = MAIN_SEPARATOR
+            ))
+            .output()
+            .unwrap();
+        let file_url_protocol: &str = if cfg!(windows) { "file:///" } else { "file://" };
+
+        // STDERR should list the font as retrieved
+        assert_eq!(
+            String::from_utf8_lossy(&out.stderr),
+            format!(
+                r#"{file}{cwd}/tests/_data_/fonts/font-face.html
+{file}{cwd}/tests/_data_/fonts/font-face.css
+{file}{cwd}/tests/_data_/fonts/open-sans-regular.woff2
+"#,
+                file = file_url_protocol,
+                cwd = cwd_normalized
+            )
+        );
+
+        // STDOUT should contain the stylesheet with the font embedded
+        assert!(String::from_utf8_lossy(&out.stdout).contains("data:text/css;base64,"));
+
+        // Exit code should be 0
+        out.assert().code(0);
+    }
+
+    #[test]
+    fn reader_mode_keeps_utf8_charset_for_local_files() {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        let out = cmd
+            .arg("-M")
+            .arg("--reader-mode")
+            .arg(format!(
+                "tests{s}_data_{s}reader{s}article.html",
+                s = MAIN_SEPARATOR
+            ))
+            .output()
+            .unwrap();
+        let stdout = String::from_utf8_lossy(&out.stdout);
+
+        // STDOUT should contain the article with its charset declared
+        assert!(stdout.starts_with("<html><head><meta charset=\"utf-8\">"));
+        assert!(stdout.contains("Über Zeichensätze"));
+
+        // Exit code should be 0
+        out.assert().code(0);
+    }
 }
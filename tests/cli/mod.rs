@@ -1,6 +1,8 @@
 mod base_url;
 mod basic;
 mod data_url;
+mod diff;
 mod local_files;
 mod noscript;
 mod unusual_encodings;
+mod verify;
@@ -0,0 +1,80 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use assert_cmd::prelude::*;
+    use assert_cmd::Command;
+
+    #[test]
+    fn self_contained_document() {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        let out = cmd
+            .arg("verify")
+            .arg("-")
+            .write_stdin(
+                "<html><head><link rel=\"icon\" href=\"data:image/png;base64,iVBORw0KGgo=\"></head>\
+<body><a href=\"https://example.com/\">link</a></body></html>",
+            )
+            .output()
+            .unwrap();
+
+        // STDERR should be empty
+        assert_eq!(String::from_utf8_lossy(&out.stderr), "");
+
+        // STDOUT should list remote references which don't get retrieved automatically
+        assert_eq!(
+            String::from_utf8_lossy(&out.stdout),
+            "Remote reference in a[href]: https://example.com/\n\
+Content Security Policy: none\n\
+Document makes no network requests when opened\n"
+        );
+
+        // Exit code should be 0
+        out.assert().code(0);
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use assert_cmd::prelude::*;
+    use assert_cmd::Command;
+
+    #[test]
+    fn remote_image() {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        let out = cmd
+            .arg("verify")
+            .arg("-")
+            .write_stdin("<html><body><img src=\"https://example.com/image.png\"></body></html>")
+            .output()
+            .unwrap();
+
+        // STDERR should report the leak
+        assert_eq!(
+            String::from_utf8_lossy(&out.stderr),
+            "document makes 1 network request(s) when opened\n"
+        );
+
+        // STDOUT should list the remote reference
+        assert_eq!(
+            String::from_utf8_lossy(&out.stdout),
+            "Remote reference in img[src]: https://example.com/image.png\n\
+Content Security Policy: none\n"
+        );
+
+        // Exit code should be 1
+        out.assert().code(1);
+    }
+}
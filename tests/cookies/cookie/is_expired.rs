@@ -19,6 +19,8 @@ mod passing {
             expires: 0,
             name: String::from(""),
             value: String::from(""),
+            http_only: false,
+            same_site: cookies::CookieSameSite::None,
         };
 
         assert!(!cookie.is_expired());
@@ -34,6 +36,8 @@ mod passing {
             expires: 9999999999,
             name: String::from(""),
             value: String::from(""),
+            http_only: false,
+            same_site: cookies::CookieSameSite::None,
         };
 
         assert!(!cookie.is_expired());
@@ -61,6 +65,8 @@ mod failing {
             expires: 1,
             name: String::from(""),
             value: String::from(""),
+            http_only: false,
+            same_site: cookies::CookieSameSite::None,
         };
 
         assert!(cookie.is_expired());
@@ -19,6 +19,8 @@ mod passing {
             expires: 0,
             name: String::from(""),
             value: String::from(""),
+            http_only: false,
+            same_site: cookies::CookieSameSite::None,
         };
         assert!(cookie.matches_url("https://127.0.0.1/something"));
     }
@@ -33,6 +35,8 @@ mod passing {
             expires: 0,
             name: String::from(""),
             value: String::from(""),
+            http_only: false,
+            same_site: cookies::CookieSameSite::None,
         };
         assert!(cookie.matches_url("http://127.0.0.1/something"));
     }
@@ -47,9 +51,43 @@ mod passing {
             expires: 0,
             name: String::from(""),
             value: String::from(""),
+            http_only: false,
+            same_site: cookies::CookieSameSite::None,
         };
         assert!(cookie.matches_url("https://cdn.somethingsomething.com/something"));
     }
+
+    #[test]
+    fn parent_domain() {
+        let cookie = cookies::Cookie {
+            domain: String::from(".somethingsomething.com"),
+            include_subdomains: true,
+            path: String::from("/"),
+            https_only: false,
+            expires: 0,
+            name: String::from(""),
+            value: String::from(""),
+            http_only: false,
+            same_site: cookies::CookieSameSite::None,
+        };
+        assert!(cookie.matches_url("http://somethingsomething.com/"));
+    }
+
+    #[test]
+    fn sub_path() {
+        let cookie = cookies::Cookie {
+            domain: String::from("127.0.0.1"),
+            include_subdomains: false,
+            path: String::from("/docs"),
+            https_only: false,
+            expires: 0,
+            name: String::from(""),
+            value: String::from(""),
+            http_only: false,
+            same_site: cookies::CookieSameSite::None,
+        };
+        assert!(cookie.matches_url("http://127.0.0.1/docs/index.html"));
+    }
 }
 
 //  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
@@ -73,6 +111,8 @@ mod failing {
             expires: 0,
             name: String::from(""),
             value: String::from(""),
+            http_only: false,
+            same_site: cookies::CookieSameSite::None,
         };
         assert!(!cookie.matches_url(""));
     }
@@ -87,6 +127,8 @@ mod failing {
             expires: 0,
             name: String::from(""),
             value: String::from(""),
+            http_only: false,
+            same_site: cookies::CookieSameSite::None,
         };
         assert!(!cookie.matches_url("http://0.0.0.0/"));
     }
@@ -101,7 +143,25 @@ mod failing {
             expires: 0,
             name: String::from(""),
             value: String::from(""),
+            http_only: false,
+            same_site: cookies::CookieSameSite::None,
         };
         assert!(!cookie.matches_url("http://0.0.0.0/path"));
     }
+
+    #[test]
+    fn partial_path_segment() {
+        let cookie = cookies::Cookie {
+            domain: String::from("127.0.0.1"),
+            include_subdomains: false,
+            path: String::from("/docs"),
+            https_only: false,
+            expires: 0,
+            name: String::from(""),
+            value: String::from(""),
+            http_only: false,
+            same_site: cookies::CookieSameSite::None,
+        };
+        assert!(!cookie.matches_url("http://127.0.0.1/docsearch"));
+    }
 }
@@ -1,2 +1,7 @@
 mod cookie;
 mod parse_cookie_file_contents;
+mod parse_cookie_header;
+mod parse_json_cookie_file_contents;
+mod parse_set_cookie_header;
+mod store_cookie;
+mod write_cookie_file_contents;
@@ -48,6 +48,18 @@ mod passing {
         assert_eq!(result[1].name, "USER_TOKEN");
         assert_eq!(result[1].value, "out");
     }
+
+    #[test]
+    fn parse_http_only() {
+        let file_contents = "# Netscape HTTP Cookie File\n\
+            #HttpOnly_.example.com\tTRUE\t/\tTRUE\t0\tsession\tabc\n\
+            # Comment\n";
+        let result = cookies::parse_cookie_file_contents(file_contents).unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].domain, ".example.com");
+        assert!(result[0].http_only);
+        assert_eq!(result[0].name, "session");
+    }
 }
 
 //  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
@@ -0,0 +1,50 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::cookies;
+
+    #[test]
+    fn name_value_pairs() {
+        let result = cookies::parse_cookie_header("sid=abc; theme=dark", "Example.com");
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].domain, "example.com");
+        assert!(!result[0].include_subdomains);
+        assert_eq!(result[0].path, "/");
+        assert_eq!(result[0].name, "sid");
+        assert_eq!(result[0].value, "abc");
+        assert_eq!(result[1].name, "theme");
+        assert_eq!(result[1].value, "dark");
+    }
+
+    #[test]
+    fn whole_header() {
+        let result = cookies::parse_cookie_header("Cookie: sid=a=b", "example.com");
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].name, "sid");
+        assert_eq!(result[0].value, "a=b");
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::cookies;
+
+    #[test]
+    fn no_pairs() {
+        assert!(cookies::parse_cookie_header("", "example.com").is_empty());
+        assert!(cookies::parse_cookie_header("sid; =1", "example.com").is_empty());
+    }
+}
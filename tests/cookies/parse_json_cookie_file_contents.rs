@@ -0,0 +1,134 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::cookies::{self, CookieSameSite};
+
+    #[test]
+    fn edit_this_cookie() {
+        let file_contents = r#"[
+    {
+        "domain": ".example.com",
+        "expirationDate": 1999999999.5,
+        "hostOnly": false,
+        "httpOnly": true,
+        "name": "sid",
+        "path": "/",
+        "sameSite": "no_restriction",
+        "secure": true,
+        "session": false,
+        "storeId": "0",
+        "value": "abc",
+        "id": 1
+    },
+    {
+        "domain": "www.example.com",
+        "hostOnly": true,
+        "httpOnly": false,
+        "name": "theme",
+        "path": "/app",
+        "sameSite": "lax",
+        "secure": false,
+        "session": true,
+        "value": "dark",
+        "id": 2
+    }
+]"#;
+        let result = cookies::parse_json_cookie_file_contents(file_contents, "").unwrap();
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].domain, ".example.com");
+        assert!(result[0].include_subdomains);
+        assert!(result[0].https_only);
+        assert_eq!(result[0].expires, 1999999999);
+        assert!(result[0].http_only);
+        assert_eq!(result[0].same_site, CookieSameSite::None);
+        assert_eq!(result[1].domain, "www.example.com");
+        assert!(!result[1].include_subdomains);
+        assert_eq!(result[1].path, "/app");
+        assert_eq!(result[1].expires, 0);
+        assert_eq!(result[1].same_site, CookieSameSite::Lax);
+    }
+
+    #[test]
+    fn puppeteer() {
+        let file_contents = r#"[{"name":"sid","value":"abc","domain":"example.com","path":"/","expires":-1,"size":6,"httpOnly":false,"secure":false,"session":true,"sameSite":"Strict"}]"#;
+        let result = cookies::parse_json_cookie_file_contents(file_contents, "").unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].domain, "example.com");
+        assert!(!result[0].include_subdomains);
+        assert_eq!(result[0].expires, 0);
+        assert_eq!(result[0].same_site, CookieSameSite::Strict);
+    }
+
+    #[test]
+    fn playwright() {
+        let file_contents = r#"{"cookies":[{"name":"sid","value":"abc","domain":".example.com","path":"/","expires":1999999999,"httpOnly":true,"secure":true,"sameSite":"Lax"}],"origins":[]}"#;
+        let result = cookies::parse_json_cookie_file_contents(file_contents, "").unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].domain, ".example.com");
+        assert!(result[0].include_subdomains);
+        assert_eq!(result[0].expires, 1999999999);
+    }
+
+    #[test]
+    fn httpie_session() {
+        let file_contents = r#"{
+    "__meta__": {"about": "HTTPie session file", "httpie": "3.2.2"},
+    "auth": {"password": null, "type": null, "username": null},
+    "cookies": [
+        {"domain": "", "expires": null, "name": "sid", "path": "/", "secure": false, "value": "abc"}
+    ],
+    "headers": []
+}"#;
+        let result =
+            cookies::parse_json_cookie_file_contents(file_contents, "example.com").unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].domain, "example.com");
+        assert_eq!(result[0].name, "sid");
+        assert_eq!(result[0].value, "abc");
+    }
+
+    #[test]
+    fn legacy_httpie_session() {
+        let file_contents = r#"{
+    "auth": {"password": null, "type": null, "username": null},
+    "cookies": {
+        "sid": {"expires": null, "path": "/", "secure": true, "value": "abc"}
+    },
+    "headers": {}
+}"#;
+        let result =
+            cookies::parse_json_cookie_file_contents(file_contents, "example.com").unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].domain, "example.com");
+        assert!(result[0].https_only);
+        assert_eq!(result[0].name, "sid");
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::cookies;
+
+    #[test]
+    fn not_json() {
+        assert!(cookies::parse_json_cookie_file_contents("[sid=abc]", "").is_err());
+    }
+
+    #[test]
+    fn no_cookies() {
+        assert!(cookies::parse_json_cookie_file_contents("{\"sid\": \"abc\"}", "").is_err());
+    }
+}
@@ -0,0 +1,112 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::cookies::{self, CookieSameSite};
+    use monolith::url::Url;
+
+    #[test]
+    fn host_only() {
+        let url: Url = Url::parse("https://www.example.com/account/login").unwrap();
+        let cookie = cookies::parse_set_cookie_header("sid=abc123", &url).unwrap();
+
+        assert_eq!(cookie.domain, "www.example.com");
+        assert!(!cookie.include_subdomains);
+        assert_eq!(cookie.path, "/account");
+        assert!(!cookie.https_only);
+        assert_eq!(cookie.expires, 0);
+        assert_eq!(cookie.name, "sid");
+        assert_eq!(cookie.value, "abc123");
+        assert!(!cookie.http_only);
+        assert_eq!(cookie.same_site, CookieSameSite::Lax);
+    }
+
+    #[test]
+    fn attributes() {
+        let url: Url = Url::parse("https://www.example.com/").unwrap();
+        let cookie = cookies::parse_set_cookie_header(
+            "token=x=y; Domain=.Example.com; Path=/app; Secure; HttpOnly; SameSite=Strict; \
+             Expires=Wed, 21 Oct 2065 07:28:00 GMT",
+            &url,
+        )
+        .unwrap();
+
+        assert_eq!(cookie.domain, ".example.com");
+        assert!(cookie.include_subdomains);
+        assert_eq!(cookie.path, "/app");
+        assert!(cookie.https_only);
+        assert_eq!(cookie.expires, 3023335680);
+        assert_eq!(cookie.value, "x=y");
+        assert!(cookie.http_only);
+        assert_eq!(cookie.same_site, CookieSameSite::Strict);
+    }
+
+    #[test]
+    fn max_age_over_expires() {
+        let url: Url = Url::parse("http://127.0.0.1/").unwrap();
+        let cookie = cookies::parse_set_cookie_header(
+            "sid=; Max-Age=0; Expires=Wed, 21 Oct 2065 07:28:00 GMT",
+            &url,
+        )
+        .unwrap();
+
+        assert!(cookie.is_expired());
+    }
+
+    #[test]
+    fn domain_equal_to_single_label_host() {
+        let url: Url = Url::parse("http://localhost/").unwrap();
+        let cookie = cookies::parse_set_cookie_header("sid=1; Domain=localhost", &url).unwrap();
+
+        assert_eq!(cookie.domain, "localhost");
+        assert!(!cookie.include_subdomains);
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::cookies;
+    use monolith::url::Url;
+
+    #[test]
+    fn foreign_domain() {
+        let url: Url = Url::parse("https://example.com/").unwrap();
+
+        assert!(cookies::parse_set_cookie_header("sid=1; Domain=example.org", &url).is_none());
+    }
+
+    #[test]
+    fn top_level_domain() {
+        let url: Url = Url::parse("https://example.com/").unwrap();
+
+        assert!(cookies::parse_set_cookie_header("sid=1; Domain=com", &url).is_none());
+        assert!(cookies::parse_set_cookie_header("sid=1; Domain=.com", &url).is_none());
+    }
+
+    #[test]
+    fn partial_ip_address() {
+        let url: Url = Url::parse("http://127.0.0.1/").unwrap();
+
+        assert!(cookies::parse_set_cookie_header("sid=1; Domain=0.0.1", &url).is_none());
+    }
+
+    #[test]
+    fn no_name() {
+        let url: Url = Url::parse("https://example.com/").unwrap();
+
+        assert!(cookies::parse_set_cookie_header("=1", &url).is_none());
+        assert!(cookies::parse_set_cookie_header("sid", &url).is_none());
+    }
+}
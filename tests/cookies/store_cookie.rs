@@ -0,0 +1,52 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::cookies::{self, Cookie};
+    use monolith::url::Url;
+
+    #[test]
+    fn replace_existing() {
+        let url: Url = Url::parse("https://example.com/").unwrap();
+        let mut jar: Vec<Cookie> = vec![];
+
+        cookies::store_cookie(
+            &mut jar,
+            cookies::parse_set_cookie_header("sid=1", &url).unwrap(),
+        );
+        cookies::store_cookie(
+            &mut jar,
+            cookies::parse_set_cookie_header("theme=dark", &url).unwrap(),
+        );
+        cookies::store_cookie(
+            &mut jar,
+            cookies::parse_set_cookie_header("sid=2", &url).unwrap(),
+        );
+
+        assert_eq!(jar.len(), 2);
+        assert_eq!(jar[0].name, "theme");
+        assert_eq!(jar[1].value, "2");
+    }
+
+    #[test]
+    fn remove_expired() {
+        let url: Url = Url::parse("https://example.com/").unwrap();
+        let mut jar: Vec<Cookie> = vec![];
+
+        cookies::store_cookie(
+            &mut jar,
+            cookies::parse_set_cookie_header("sid=1", &url).unwrap(),
+        );
+        cookies::store_cookie(
+            &mut jar,
+            cookies::parse_set_cookie_header("sid=; Max-Age=0", &url).unwrap(),
+        );
+
+        assert!(jar.is_empty());
+    }
+}
@@ -0,0 +1,33 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::cookies::{self, Cookie};
+
+    #[test]
+    fn round_trip() {
+        let file_contents = "# Netscape HTTP Cookie File\n\
+            127.0.0.1\tFALSE\t/\tFALSE\t0\tUSER_TOKEN\tin\n\
+            #HttpOnly_.example.com\tTRUE\t/app\tTRUE\t9999999999\tsid\tabc\n";
+        let jar: Vec<Cookie> = cookies::parse_cookie_file_contents(file_contents).unwrap();
+
+        assert_eq!(cookies::write_cookie_file_contents(&jar), file_contents);
+    }
+
+    #[test]
+    fn skip_expired() {
+        let file_contents = "# Netscape HTTP Cookie File\n\
+            127.0.0.1\tFALSE\t/\tFALSE\t1\tUSER_TOKEN\tout\n";
+        let jar: Vec<Cookie> = cookies::parse_cookie_file_contents(file_contents).unwrap();
+
+        assert_eq!(
+            cookies::write_cookie_file_contents(&jar),
+            "# Netscape HTTP Cookie File\n"
+        );
+    }
+}
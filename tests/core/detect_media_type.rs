@@ -74,6 +74,12 @@ mod passing {
         assert_eq!(detect_media_type(b"", &https_url), "image/svg+xml");
     }
 
+    #[test]
+    fn text_vtt_filename() {
+        let https_url: Url = Url::parse("https://some-site.com/captions/en.vtt").unwrap();
+        assert_eq!(detect_media_type(b"WEBVTT", &https_url), "text/vtt");
+    }
+
     #[test]
     fn audio_mpeg() {
         let dummy_url: Url = Url::parse("data:,").unwrap();
@@ -0,0 +1,63 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::core;
+
+    #[test]
+    fn image() {
+        assert_eq!(core::get_file_extension_by_media_type("image/png"), "png");
+        assert_eq!(core::get_file_extension_by_media_type("image/jpeg"), "jpg");
+        assert_eq!(
+            core::get_file_extension_by_media_type("image/svg+xml"),
+            "svg"
+        );
+    }
+
+    #[test]
+    fn font() {
+        assert_eq!(
+            core::get_file_extension_by_media_type("font/woff2"),
+            "woff2"
+        );
+        assert_eq!(
+            core::get_file_extension_by_media_type("application/font-woff"),
+            "woff"
+        );
+    }
+
+    #[test]
+    fn case_insensitive() {
+        assert_eq!(core::get_file_extension_by_media_type("Text/CSS"), "css");
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::core;
+
+    #[test]
+    fn unknown_media_type() {
+        assert_eq!(
+            core::get_file_extension_by_media_type("application/octet-stream"),
+            ""
+        );
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(core::get_file_extension_by_media_type(""), "");
+    }
+}
@@ -1,4 +1,5 @@
 mod detect_media_type;
 mod format_output_path;
+mod get_file_extension_by_media_type;
 mod options;
 mod parse_content_type;
@@ -0,0 +1,96 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use encoding_rs::{SHIFT_JIS, UTF_8, WINDOWS_1251};
+
+    use monolith::css::decode_stylesheet;
+
+    #[test]
+    fn protocol_charset() {
+        let data: Vec<u8> = SHIFT_JIS.encode("p::before{content:\"日本\"}").0.to_vec();
+        let (css, encoding) = decode_stylesheet(&data, "Shift_JIS", UTF_8);
+
+        assert_eq!(css, "p::before{content:\"日本\"}");
+        assert_eq!(encoding, SHIFT_JIS);
+    }
+
+    #[test]
+    fn charset_rule() {
+        let data: Vec<u8> = WINDOWS_1251
+            .encode("@charset \"windows-1251\";\nh1::after{content:\"Привет\"}")
+            .0
+            .to_vec();
+        let (css, encoding) = decode_stylesheet(&data, "US-ASCII", UTF_8);
+
+        assert_eq!(css, "\nh1::after{content:\"Привет\"}");
+        assert_eq!(encoding, WINDOWS_1251);
+    }
+
+    #[test]
+    fn protocol_charset_over_charset_rule() {
+        let data: Vec<u8> = "@charset \"windows-1251\";p{}".as_bytes().to_vec();
+        let (css, encoding) = decode_stylesheet(&data, "utf-8", WINDOWS_1251);
+
+        assert_eq!(css, "p{}");
+        assert_eq!(encoding, UTF_8);
+    }
+
+    #[test]
+    fn utf_16_charset_rule() {
+        let data: Vec<u8> = "@charset \"utf-16\";p::before{content:\"é\"}"
+            .as_bytes()
+            .to_vec();
+        let (css, encoding) = decode_stylesheet(&data, "", SHIFT_JIS);
+
+        assert_eq!(css, "p::before{content:\"é\"}");
+        assert_eq!(encoding, UTF_8);
+    }
+
+    #[test]
+    fn byte_order_mark() {
+        let mut data: Vec<u8> = vec![0xEF, 0xBB, 0xBF];
+        data.extend_from_slice("p::before{content:\"é\"}".as_bytes());
+        let (css, encoding) = decode_stylesheet(&data, "windows-1251", SHIFT_JIS);
+
+        assert_eq!(css, "p::before{content:\"é\"}");
+        assert_eq!(encoding, UTF_8);
+    }
+
+    #[test]
+    fn environment_encoding() {
+        let data: Vec<u8> = WINDOWS_1251.encode("p::before{content:\"Мир\"}").0.to_vec();
+        let (css, encoding) = decode_stylesheet(&data, "", WINDOWS_1251);
+
+        assert_eq!(css, "p::before{content:\"Мир\"}");
+        assert_eq!(encoding, WINDOWS_1251);
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use encoding_rs::UTF_8;
+
+    use monolith::css::decode_stylesheet;
+
+    #[test]
+    fn charset_rule_with_single_quotes() {
+        // Only the exact @charset "…"; form counts
+        let (css, encoding) = decode_stylesheet(b"@charset 'windows-1251';p{}", "", UTF_8);
+
+        assert_eq!(css, "@charset 'windows-1251';p{}");
+        assert_eq!(encoding, UTF_8);
+    }
+}
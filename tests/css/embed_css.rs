@@ -8,6 +8,8 @@
 #[cfg(test)]
 mod passing {
     use reqwest::Url;
+    use std::env;
+    use std::fs;
 
     use monolith::core::MonolithOptions;
     use monolith::css;
@@ -327,4 +329,249 @@ mod passing {
 
         assert_eq!(css::embed_css(&mut session, &document_url, CSS), CSS_OUT);
     }
+
+    #[test]
+    fn image_set_pixel_density() {
+        let document_url: Url = Url::parse("https://doesntmatter.local/").unwrap();
+        let mut options = MonolithOptions::default();
+        options.no_images = true;
+        options.pixel_density = Some(2.0);
+        options.silent = true;
+        let mut session: Session = Session::new(None, None, options);
+
+        const CSS: &str = "\
+            background-image: image-set(url(a.png) 1x, url(b.png) 2x);\
+            background-image: -webkit-image-set(\"a.png\" 1x, \"b.png\" 2x);\
+            ";
+
+        assert_eq!(
+            css::embed_css(&mut session, &document_url, CSS),
+            format!(
+                "\
+                background-image: image-set(url(\"{empty_image}\") 2x);\
+                background-image: -webkit-image-set(\"{empty_image}\" 2x);\
+                ",
+                empty_image = EMPTY_IMAGE_DATA_URL
+            )
+        );
+    }
+
+    #[test]
+    fn font_face_best_source() {
+        let document_url: Url = Url::parse("data:,").unwrap();
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+        let mut session: Session = Session::new(None, None, options);
+
+        const CSS: &str = "\
+            @font-face {\n\
+                font-family: \"Font\";\n\
+                src: url(data:font/eot;base64,AAAA);\n\
+                src: url(data:font/ttf;base64,AAAA) format(\"truetype\"), url(data:font/woff2;base64,AAAA) format(\"woff2\");\n\
+            }\n\
+            ";
+        const CSS_OUT: &str = "\
+            @font-face {\n\
+                font-family: \"Font\";\n\
+                src: url(\"data:font/woff2;base64,AAAA\") format(\"woff2\");\n\
+            }\n\
+            ";
+
+        assert_eq!(css::embed_css(&mut session, &document_url, CSS), CSS_OUT);
+    }
+
+    #[test]
+    fn unused_font_faces() {
+        let document_url: Url = Url::parse("data:,").unwrap();
+        let mut options = MonolithOptions::default();
+        options.no_unused_font_faces = true;
+        options.silent = true;
+        let mut session: Session = Session::new(None, None, options);
+        session.document_chars.extend("Hello".chars());
+
+        const CSS: &str = "\
+            @font-face {\n\
+                font-family: \"Font\";\n\
+                src: url(data:font/woff2;base64,AAAA);\n\
+                unicode-range: U+4E00-9FFF;\n\
+            }\n\
+            @font-face {\n\
+                font-family: \"Font\";\n\
+                src: url(data:font/woff2;base64,AAAA);\n\
+                unicode-range: U+0000-00FF;\n\
+            }\n\
+            ";
+        const CSS_OUT: &str = "\
+            \n\
+            @font-face {\n\
+                font-family: \"Font\";\n\
+                src: url(\"data:font/woff2;base64,AAAA\");\n\
+                unicode-range: U+0000-00FF;\n\
+            }\n\
+            ";
+
+        assert_eq!(css::embed_css(&mut session, &document_url, CSS), CSS_OUT);
+    }
+
+    #[test]
+    fn subset_fonts() {
+        let file_url_protocol: &str = if cfg!(windows) { "file:///" } else { "file://" };
+        let cwd = env::current_dir().unwrap();
+        let document_url: Url = Url::parse(&format!(
+            "{file}{cwd}/tests/_data_/fonts/index.html",
+            file = file_url_protocol,
+            cwd = cwd.to_str().unwrap()
+        ))
+        .unwrap();
+        let mut options = MonolithOptions::default();
+        options.subset_fonts = true;
+        options.silent = true;
+        let mut session: Session = Session::new(None, None, options);
+        session.document_chars.extend("Hello".chars());
+
+        const CSS: &str = "@font-face { src: url(open-sans-regular.woff2) format(\"woff2\"); }";
+
+        let css: String = css::embed_css(&mut session, &document_url, CSS);
+        assert!(css.starts_with("@font-face { src: url(\"data:font/ttf;base64,"));
+        assert!(
+            css.len()
+                < fs::read("tests/_data_/fonts/open-sans-regular.woff2")
+                    .unwrap()
+                    .len()
+        );
+    }
+
+    #[test]
+    fn minify() {
+        let document_url: Url = Url::parse("data:,").unwrap();
+        let mut options = MonolithOptions::default();
+        options.minify_css = true;
+        options.silent = true;
+        let mut session: Session = Session::new(None, None, options);
+
+        const CSS: &str = "\
+            /* Navigation */\n\
+            nav > ul li a:hover ,\n\
+            nav .active {\n\
+                color: rgba( 0, 0, 0, 0.5 ) !important;\n\
+                width: calc(100% - 10px);\n\
+                content: \"a  b\";\n\
+            }\n\
+            @media screen and (max-width: 600px) {\n\
+                nav { display: none; }\n\
+            }\n\
+            @media print {\n\
+                nav { display: none; }\n\
+            }\n\
+            ";
+        const CSS_OUT: &str = "\
+            nav>ul li a:hover,nav .active{\
+            color:rgba(0,0,0,0.5) !important;\
+            width:calc(100% - 10px);\
+            content:\"a  b\"\
+            }\
+            @media screen and (max-width:600px){nav{display:none}}\
+            ";
+
+        assert_eq!(css::embed_css(&mut session, &document_url, CSS), CSS_OUT);
+    }
+
+    #[test]
+    fn excluded_media() {
+        let document_url: Url = Url::parse("data:,").unwrap();
+        let mut options = MonolithOptions::default();
+        options.excluded_media = Some(vec!["screen".to_string()]);
+        options.silent = true;
+        let mut session: Session = Session::new(None, None, options);
+
+        const CSS: &str = "\
+            @media screen and (min-width: 600px) { nav { display: none; } }\n\
+            @media print { nav { display: none; } }\n\
+            @media not screen { nav { display: none; } }\n\
+            ";
+        const CSS_OUT: &str = "\
+            \n\
+            @media print { nav { display: none; } }\n\
+            @media not screen { nav { display: none; } }\n\
+            ";
+
+        assert_eq!(css::embed_css(&mut session, &document_url, CSS), CSS_OUT);
+    }
+
+    #[test]
+    fn inline_imports() {
+        let document_url: Url = Url::parse("data:,").unwrap();
+        let mut options = MonolithOptions::default();
+        options.inline_stylesheets = true;
+        options.silent = true;
+        let mut session: Session = Session::new(None, None, options);
+
+        const CSS: &str = "\
+            @import url(\"data:text/css,nav{display:grid}\") supports(display: grid) screen;\n\
+            body{margin:0}\n\
+            ";
+        const CSS_OUT: &str = "\
+            @media screen {\n\
+            @supports (display: grid) {\n\
+            nav{display:grid}\n\
+            }\n\
+            }\n\
+            body{margin:0}\n\
+            ";
+
+        assert_eq!(css::embed_css(&mut session, &document_url, CSS), CSS_OUT);
+    }
+
+    #[test]
+    fn import_excluded_media() {
+        let document_url: Url = Url::parse("data:,").unwrap();
+        let mut options = MonolithOptions::default();
+        options.excluded_media = Some(vec!["print".to_string()]);
+        options.silent = true;
+        let mut session: Session = Session::new(None, None, options);
+
+        const CSS: &str = "\
+            @import url(\"https://doesntmatter.local/print.css\") layer(print) print;\n\
+            @import url(\"data:text/css,nav{display:grid}\") layer(base) supports(display: grid) screen, print;\n\
+            ";
+        const CSS_OUT: &str = "\
+            \n\
+            @import url(\"data:text/css;base64,bmF2e2Rpc3BsYXk6Z3JpZH0=\") layer(base) supports(display: grid) screen, print;\n\
+            ";
+
+        assert_eq!(css::embed_css(&mut session, &document_url, CSS), CSS_OUT);
+    }
+
+    #[test]
+    fn inline_imports_supports_condition() {
+        let document_url: Url = Url::parse("data:,").unwrap();
+        let mut options = MonolithOptions::default();
+        options.inline_stylesheets = true;
+        options.silent = true;
+        let mut session: Session = Session::new(None, None, options);
+
+        const CSS: &str = "@import \"data:text/css,a{color:red}\" layer supports(selector(a > b));";
+        const CSS_OUT: &str = "\
+            @supports (selector(a > b)) {\n\
+            @layer {\n\
+            a{color:red}\n\
+            }\n\
+            }";
+
+        assert_eq!(css::embed_css(&mut session, &document_url, CSS), CSS_OUT);
+    }
+
+    #[test]
+    fn import_charset() {
+        let document_url: Url = Url::parse("data:,").unwrap();
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+        let mut session: Session = Session::new(None, None, options);
+
+        const CSS: &str = "@import url(\"data:text/css,%40charset%20%22windows-1251%22%3B%0Ah1%3A%3Aafter%7Bcontent%3A%22%CF%F0%E8%E2%E5%F2%22%7D\");";
+        const CSS_OUT: &str =
+            "@import url(\"data:text/css;charset=UTF-8;base64,CmgxOjphZnRlcntjb250ZW50OiLQn9GA0LjQstC10YIifQ==\");";
+
+        assert_eq!(css::embed_css(&mut session, &document_url, CSS), CSS_OUT);
+    }
 }
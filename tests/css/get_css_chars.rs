@@ -0,0 +1,58 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use std::collections::HashSet;
+
+    use monolith::css;
+
+    #[test]
+    fn content_escapes() {
+        let mut chars: HashSet<char> = "abc".chars().collect();
+
+        css::get_css_chars(".i::before { content: \"\\2603\"; }", &mut chars);
+
+        assert!(chars.contains(&'☃'));
+        assert_eq!(chars.len(), 4);
+    }
+
+    #[test]
+    fn text_transform() {
+        let mut chars: HashSet<char> = "abc".chars().collect();
+
+        css::get_css_chars("p { text-transform: uppercase; }", &mut chars);
+
+        assert_eq!(chars, "abcABC".chars().collect());
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use std::collections::HashSet;
+
+    use monolith::css;
+
+    #[test]
+    fn no_text_transform() {
+        let mut chars: HashSet<char> = "abc".chars().collect();
+
+        css::get_css_chars(
+            "p { text-transform: none; } q { quotes: none; }",
+            &mut chars,
+        );
+
+        assert_eq!(chars, "abc".chars().collect());
+    }
+}
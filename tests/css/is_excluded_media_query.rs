@@ -0,0 +1,73 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::css::is_excluded_media_query;
+
+    #[test]
+    fn print() {
+        assert!(is_excluded_media_query("print", &["print".to_string()]));
+    }
+
+    #[test]
+    fn only_print_with_features() {
+        assert!(is_excluded_media_query(
+            "only PRINT and (orientation: landscape)",
+            &["print".to_string()]
+        ));
+    }
+
+    #[test]
+    fn multiple_excluded_media_types() {
+        assert!(is_excluded_media_query(
+            "print, speech",
+            &["print".to_string(), "speech".to_string()]
+        ));
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::css::is_excluded_media_query;
+
+    #[test]
+    fn empty() {
+        assert!(!is_excluded_media_query("", &["print".to_string()]));
+    }
+
+    #[test]
+    fn partially_excluded() {
+        assert!(!is_excluded_media_query(
+            "print, screen",
+            &["print".to_string()]
+        ));
+    }
+
+    #[test]
+    fn negated() {
+        assert!(!is_excluded_media_query(
+            "not print",
+            &["print".to_string()]
+        ));
+    }
+
+    #[test]
+    fn media_features_only() {
+        assert!(!is_excluded_media_query(
+            "(min-width: 600px)",
+            &["print".to_string()]
+        ));
+    }
+}
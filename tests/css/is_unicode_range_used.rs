@@ -0,0 +1,60 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use std::collections::HashSet;
+
+    use monolith::css::is_unicode_range_used;
+
+    #[test]
+    fn single_code_point() {
+        let chars: HashSet<char> = "Hello".chars().collect();
+        assert!(is_unicode_range_used("U+48", &chars));
+    }
+
+    #[test]
+    fn multiple_ranges() {
+        let chars: HashSet<char> = "Привет".chars().collect();
+        assert!(is_unicode_range_used(
+            "U+0000-00FF, U+0131, U+0400-045F",
+            &chars
+        ));
+    }
+
+    #[test]
+    fn wildcard_range() {
+        let chars: HashSet<char> = "日本".chars().collect();
+        assert!(is_unicode_range_used("U+65??", &chars));
+    }
+
+    #[test]
+    fn malformed_range() {
+        let chars: HashSet<char> = HashSet::new();
+        assert!(is_unicode_range_used("latin", &chars));
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use std::collections::HashSet;
+
+    use monolith::css::is_unicode_range_used;
+
+    #[test]
+    fn unused_range() {
+        let chars: HashSet<char> = "Hello, world!".chars().collect();
+        assert!(!is_unicode_range_used("U+4E00-9FFF, U+3000-303F", &chars));
+    }
+}
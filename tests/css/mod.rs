@@ -1,2 +1,11 @@
+mod decode_stylesheet;
 mod embed_css;
+mod get_css_chars;
+mod is_excluded_media_query;
 mod is_image_url_prop;
+mod is_unicode_range_used;
+mod parse_import_prelude;
+mod remove_unreferenced_css_at_rules;
+mod remove_unused_css_rules;
+mod select_font_face_sources;
+mod select_image_set_candidate;
@@ -0,0 +1,86 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::css::{parse_import_prelude, CssImport};
+
+    #[test]
+    fn quoted_string() {
+        let import: CssImport = parse_import_prelude(" 'style.css'").unwrap();
+
+        assert_eq!(import.url, "style.css");
+        assert_eq!(import.layer, None);
+        assert_eq!(import.supports, None);
+        assert_eq!(import.media, "");
+    }
+
+    #[test]
+    fn url_with_media() {
+        let import: CssImport =
+            parse_import_prelude("url(print.css) print, (orientation: landscape)").unwrap();
+
+        assert_eq!(import.url, "print.css");
+        assert_eq!(import.media, "print, (orientation: landscape)");
+    }
+
+    #[test]
+    fn anonymous_layer() {
+        let import: CssImport = parse_import_prelude("url(\"reset.css\") layer").unwrap();
+
+        assert_eq!(import.url, "reset.css");
+        assert_eq!(import.layer, Some("".to_string()));
+    }
+
+    #[test]
+    fn layer_supports_and_media() {
+        let import: CssImport = parse_import_prelude(
+            "url(\"grid.css\") layer(base.grid) supports(display: grid) screen and (min-width: 600px)",
+        )
+        .unwrap();
+
+        assert_eq!(import.url, "grid.css");
+        assert_eq!(import.layer, Some("base.grid".to_string()));
+        assert_eq!(import.supports, Some("display: grid".to_string()));
+        assert_eq!(import.media, "screen and (min-width: 600px)");
+    }
+
+    #[test]
+    fn supports_condition() {
+        let import: CssImport = parse_import_prelude(
+            "URL(nested.css) Supports(not selector(a > b)) only screen, print",
+        )
+        .unwrap();
+
+        assert_eq!(import.url, "nested.css");
+        assert_eq!(import.layer, None);
+        assert_eq!(import.supports, Some("not selector(a > b)".to_string()));
+        assert_eq!(import.media, "only screen, print");
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::css::parse_import_prelude;
+
+    #[test]
+    fn empty() {
+        assert!(parse_import_prelude("").is_none());
+    }
+
+    #[test]
+    fn no_url() {
+        assert!(parse_import_prelude("screen").is_none());
+    }
+}
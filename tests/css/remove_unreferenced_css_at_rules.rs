@@ -0,0 +1,76 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use std::collections::HashSet;
+
+    use monolith::css::{get_css_references, remove_unreferenced_css_at_rules};
+
+    #[test]
+    fn font_faces() {
+        let css = "@font-face{font-family:\"Open Sans\";src:url(a.woff2)}\n\
+                   @font-face{font-family:Unused;src:url(b.woff2)}\n\
+                   body{font:12px/1.5 'Open  Sans', sans-serif}";
+        let mut font_families: Vec<String> = vec![];
+        let mut animation_names: HashSet<String> = HashSet::new();
+        get_css_references(css, &mut font_families, &mut animation_names);
+
+        assert_eq!(
+            remove_unreferenced_css_at_rules(css, &font_families, &animation_names),
+            "@font-face{font-family:\"Open Sans\";src:url(a.woff2)}\n\
+             body{font:12px/1.5 'Open  Sans', sans-serif}"
+        );
+    }
+
+    #[test]
+    fn keyframes() {
+        let css = "@keyframes spin{to{rotate:1turn}}\n\
+                   @-webkit-keyframes fade{to{opacity:0}}\n\
+                   @media screen{@keyframes blink{50%{opacity:0}}}\n\
+                   .icon{animation:spin 1s infinite}";
+        let mut font_families: Vec<String> = vec![];
+        let mut animation_names: HashSet<String> = HashSet::new();
+        get_css_references(css, &mut font_families, &mut animation_names);
+
+        assert_eq!(
+            remove_unreferenced_css_at_rules(css, &font_families, &animation_names),
+            "@keyframes spin{to{rotate:1turn}}\n.icon{animation:spin 1s infinite}"
+        );
+    }
+
+    #[test]
+    fn referenced_by_custom_properties() {
+        let css = "@font-face{font-family:\"Inter\";src:url(a.woff2)}\n\
+                   @keyframes slide{to{translate:0}}\n\
+                   :root{--body-font:\"Inter\", sans-serif;--entrance:slide 1s}\n\
+                   body{font-family:var(--body-font);animation:var(--entrance)}";
+        let mut font_families: Vec<String> = vec![];
+        let mut animation_names: HashSet<String> = HashSet::new();
+        get_css_references(css, &mut font_families, &mut animation_names);
+
+        assert_eq!(
+            remove_unreferenced_css_at_rules(css, &font_families, &animation_names),
+            css
+        );
+    }
+
+    #[test]
+    fn referenced_by_data_url_imports() {
+        let css = "@import url(\"data:text/css;base64,Ym9keXtmb250LWZhbWlseTpJbnRlcjthbmltYXRpb246c2xpZGUgMXN9\");\n\
+                   @font-face{font-family:Inter;src:url(a.woff2)}\n\
+                   @keyframes slide{to{translate:0}}";
+        let mut font_families: Vec<String> = vec![];
+        let mut animation_names: HashSet<String> = HashSet::new();
+        get_css_references(css, &mut font_families, &mut animation_names);
+
+        assert_eq!(
+            remove_unreferenced_css_at_rules(css, &font_families, &animation_names),
+            css
+        );
+    }
+}
@@ -0,0 +1,121 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use markup5ever_rcdom::{Handle, NodeData, RcDom};
+
+    use monolith::css::remove_unused_css_rules;
+    use monolith::html;
+
+    // The DOM has to outlive elements, otherwise they lose their children
+    fn get_elements(html: &str) -> (RcDom, Vec<Handle>) {
+        fn collect_elements(node: &Handle, elements: &mut Vec<Handle>) {
+            if let NodeData::Element { .. } = node.data {
+                elements.push(node.clone());
+            }
+            for child_node in node.children.borrow().iter() {
+                collect_elements(child_node, elements);
+            }
+        }
+
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+        let mut elements: Vec<Handle> = vec![];
+        collect_elements(&dom.document, &mut elements);
+
+        (dom, elements)
+    }
+
+    #[test]
+    fn simple_selectors() {
+        let (_dom, elements) =
+            get_elements("<p id=\"intro\" class=\"lead text\" lang=\"en-US\">Hi</p>");
+
+        assert_eq!(
+            remove_unused_css_rules(
+                "p{margin:0}\nh1{margin:0}\n#intro{color:red}\n#outro{color:red}\n\
+                 .lead.text{font-size:2em}\n.lead.note{font-size:2em}\n\
+                 [lang|=en]{quotes:none}\n[lang^=fr]{quotes:none}",
+                &elements
+            ),
+            "p{margin:0}\n#intro{color:red}\n.lead.text{font-size:2em}\n[lang|=en]{quotes:none}"
+        );
+    }
+
+    #[test]
+    fn combinators() {
+        let (_dom, elements) =
+            get_elements("<div class=\"a\"><p>One</p><span>Two</span></div><ul><li></li></ul>");
+
+        assert_eq!(
+            remove_unused_css_rules(
+                ".a p{}\n.a>span{}\np+span{}\np~span{}\nul>p{}\nspan+p{}\nbody li{}\ndiv li{}",
+                &elements
+            ),
+            ".a p{}\n.a>span{}\np+span{}\np~span{}\nbody li{}"
+        );
+    }
+
+    #[test]
+    fn selector_lists() {
+        let (_dom, elements) = get_elements("<em>Hi</em>");
+
+        assert_eq!(
+            remove_unused_css_rules("strong, em{font-weight:bold}\nstrong, b{}", &elements),
+            "strong, em{font-weight:bold}"
+        );
+    }
+
+    #[test]
+    fn pseudo_classes_and_elements() {
+        let (_dom, elements) = get_elements("<a href=\"#\">Link</a>");
+
+        assert_eq!(
+            remove_unused_css_rules(
+                "a:hover{}\na::before{}\na:not(.x){}\nbutton:focus{}",
+                &elements
+            ),
+            "a:hover{}\na::before{}\na:not(.x){}"
+        );
+    }
+
+    #[test]
+    fn grouping_rules() {
+        let (_dom, elements) = get_elements("<p>Hi</p>");
+
+        assert_eq!(
+            remove_unused_css_rules(
+                "@media screen{p{}\nh1{}}\n@supports (display:grid){h1{}}\n@layer base{h1{}}",
+                &elements
+            ),
+            "@media screen{p{}}\n@layer base{}"
+        );
+    }
+
+    #[test]
+    fn at_rules_and_comments() {
+        let (_dom, elements) = get_elements("<p>Hi</p>");
+
+        assert_eq!(
+            remove_unused_css_rules(
+                "@charset \"UTF-8\";\n/* Note */\n@font-face{font-family:A}\nh1{}",
+                &elements
+            ),
+            "@charset \"UTF-8\";\n/* Note */\n@font-face{font-family:A}"
+        );
+    }
+
+    #[test]
+    fn unsupported_selectors() {
+        let (_dom, elements) = get_elements("<p>Hi</p>");
+
+        assert_eq!(
+            remove_unused_css_rules("svg|a{}\n& h1{}\n> h1{}", &elements),
+            "svg|a{}\n& h1{}\n> h1{}"
+        );
+    }
+}
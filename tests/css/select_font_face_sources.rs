@@ -0,0 +1,77 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::css::select_font_face_sources;
+
+    #[test]
+    fn prefer_woff2() {
+        assert_eq!(
+            select_font_face_sources(
+                "url(font.eot?#iefix) format(\"embedded-opentype\"), url(font.woff) format(\"woff\"), url(font.woff2) format(\"woff2\"), url(font.ttf) format(\"truetype\")"
+            ),
+            "url(font.woff2) format(\"woff2\")"
+        );
+    }
+
+    #[test]
+    fn prefer_woff_over_truetype() {
+        assert_eq!(
+            select_font_face_sources(
+                "url('font.ttf') format('truetype'), url('font.woff') format('woff')"
+            ),
+            "url('font.woff') format('woff')"
+        );
+    }
+
+    #[test]
+    fn format_from_file_extension() {
+        assert_eq!(
+            select_font_face_sources("url(/fonts/font.svg#font), url(/fonts/font.otf?v=2)"),
+            "url(/fonts/font.otf?v=2)"
+        );
+    }
+
+    #[test]
+    fn keep_local_sources() {
+        assert_eq!(
+            select_font_face_sources(
+                "local(\"Font\"), url(font.ttf), local(Font-Regular), url(font.woff2)"
+            ),
+            "local(\"Font\"), local(Font-Regular), url(font.woff2)"
+        );
+    }
+
+    #[test]
+    fn variations_and_ident_formats() {
+        assert_eq!(
+            select_font_face_sources("url(font.ttf) format(truetype-variations), url(font.woff2) format(woff2-variations)"),
+            "url(font.woff2) format(woff2-variations)"
+        );
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::css::select_font_face_sources;
+
+    #[test]
+    fn unknown_formats() {
+        assert_eq!(
+            select_font_face_sources("url(font) format(\"collection\"), url(font.bin)"),
+            "url(font) format(\"collection\")"
+        );
+    }
+}
@@ -0,0 +1,87 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::css::select_image_set_candidate;
+
+    #[test]
+    fn exact_density() {
+        assert_eq!(
+            select_image_set_candidate("url(a.png) 1x, url(b.png) 2x, url(c.png) 3x", 2.0),
+            Some("url(b.png) 2x")
+        );
+    }
+
+    #[test]
+    fn next_higher_density() {
+        assert_eq!(
+            select_image_set_candidate("\"a.png\" 1x, \"c.png\" 3x, \"b.png\" 2x", 1.5),
+            Some("\"b.png\" 2x")
+        );
+    }
+
+    #[test]
+    fn highest_available_density() {
+        assert_eq!(
+            select_image_set_candidate("url(a.png) 1x, url(b.png) 2x", 4.0),
+            Some("url(b.png) 2x")
+        );
+    }
+
+    #[test]
+    fn implicit_density() {
+        assert_eq!(
+            select_image_set_candidate("url(a.png), url(b.png) 2x", 1.0),
+            Some("url(a.png)")
+        );
+    }
+
+    #[test]
+    fn other_resolution_units() {
+        assert_eq!(
+            select_image_set_candidate("url(a.png) 96dpi, url(b.png) 2dppx", 2.0),
+            Some("url(b.png) 2dppx")
+        );
+    }
+
+    #[test]
+    fn first_supported_type() {
+        assert_eq!(
+            select_image_set_candidate(
+                "url(a.jxl) type(\"image/jxl\"), url(a.avif) type(\"image/avif\"), url(a.jpg) type('image/jpeg')",
+                1.0
+            ),
+            Some("url(a.avif) type(\"image/avif\")")
+        );
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::css::select_image_set_candidate;
+
+    #[test]
+    fn empty() {
+        assert_eq!(select_image_set_candidate("", 1.0), None);
+    }
+
+    #[test]
+    fn only_unsupported_types() {
+        assert_eq!(
+            select_image_set_candidate("url(a.jxl) type(\"image/jxl\") 1x", 1.0),
+            None
+        );
+    }
+}
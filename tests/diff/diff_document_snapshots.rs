@@ -0,0 +1,140 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use serde_json::json;
+
+    use monolith::diff;
+
+    #[test]
+    fn no_changes() {
+        let document = b"<html><head><title>Policy</title></head><body>\
+<p>Text</p><img src=\"data:image/png;base64,AAAA\"></body></html>";
+
+        assert_eq!(
+            diff::diff_document_snapshots(
+                &diff::take_document_snapshot(document),
+                &diff::take_document_snapshot(document)
+            ),
+            json!({
+                "title": null,
+                "metadata": null,
+                "text": { "removed": [], "added": [] },
+                "assets": { "added": [], "removed": [], "changed": [] },
+            })
+        );
+    }
+
+    #[test]
+    fn changes() {
+        let previous = diff::take_document_snapshot(
+            b"<!-- Saved from https://example.com/ at 2025-01-01T00:00:00Z using monolith v2.10.1 -->\
+<html><head><title>Policy</title></head><body><p>Text</p>\
+<img src=\"data:image/png;base64,AAAA\">\
+<img src=\"data:image/png;base64,AAAB\"></body></html>",
+        );
+        let current = diff::take_document_snapshot(
+            b"<!-- Saved from https://example.com/policy at 2025-02-01T00:00:00Z using monolith v2.10.1 -->\
+<html><head><title>Updated policy</title></head><body><p>Text</p><p>New text</p>\
+<img src=\"data:image/png;base64,AAAC\"></body></html>",
+        );
+
+        assert_eq!(
+            diff::diff_document_snapshots(&previous, &current),
+            json!({
+                "title": { "previous": "Policy", "current": "Updated policy" },
+                "metadata": {
+                    "previous": { "source": "https://example.com/", "saved_at": "2025-01-01T00:00:00Z" },
+                    "current": { "source": "https://example.com/policy", "saved_at": "2025-02-01T00:00:00Z" },
+                },
+                "text": { "removed": [], "added": ["New text"] },
+                "assets": {
+                    "added": [],
+                    "removed": [{
+                        "location": "img[src] #2",
+                        "hash": "cf7605ed1bc735f6c825554154627467e1cac9df54cee8699218ed434603c568",
+                        "media_type": "image/png",
+                        "size": 3,
+                    }],
+                    "changed": [{
+                        "location": "img[src] #1",
+                        "previous": {
+                            "hash": "709e80c88487a2411e1ee4dfb9f22a861492d20c4765150c0c794abd70f8147c",
+                            "media_type": "image/png",
+                            "size": 3,
+                        },
+                        "current": {
+                            "hash": "65d27a48dfef406db8f5f437423bfa5f9c83d77bfc12f8a14b8ce3ede5892fb5",
+                            "media_type": "image/png",
+                            "size": 3,
+                        },
+                    }],
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn resaved_document_has_no_metadata_changes() {
+        let previous = diff::take_document_snapshot(
+            b"<!-- Saved from https://example.com/ at 2025-01-01T00:00:00Z using monolith v2.10.1 -->\
+<html><body><p>Text</p></body></html>",
+        );
+        let current = diff::take_document_snapshot(
+            b"<!-- Saved from https://example.com/ at 2025-01-01T00:00:02Z using monolith v2.10.1 -->\
+<html><body><p>Text</p></body></html>",
+        );
+
+        assert_eq!(
+            diff::diff_document_snapshots(&previous, &current)["metadata"],
+            json!(null)
+        );
+    }
+
+    #[test]
+    fn moved_asset_is_not_reported() {
+        let previous = diff::take_document_snapshot(
+            b"<html><body><img src=\"data:image/png;base64,AAAA\"></body></html>",
+        );
+        let current = diff::take_document_snapshot(
+            b"<html><body><div style=\"background: url(data:image/png;base64,AAAA)\"></div></body></html>",
+        );
+
+        assert_eq!(
+            diff::diff_document_snapshots(&previous, &current)["assets"],
+            json!({ "added": [], "removed": [], "changed": [] })
+        );
+    }
+
+    #[test]
+    fn asset_inserted_before_unchanged_ones() {
+        let previous = diff::take_document_snapshot(
+            b"<html><body><img src=\"data:image/png;base64,AAAA\">\
+<img src=\"data:image/png;base64,AAAB\"></body></html>",
+        );
+        let current = diff::take_document_snapshot(
+            b"<html><body><img src=\"data:image/png;base64,AAAC\">\
+<img src=\"data:image/png;base64,AAAA\">\
+<img src=\"data:image/png;base64,AAAB\"></body></html>",
+        );
+
+        assert_eq!(
+            diff::diff_document_snapshots(&previous, &current)["assets"],
+            json!({
+                "added": [{
+                    "location": "img[src] #1",
+                    "hash": "65d27a48dfef406db8f5f437423bfa5f9c83d77bfc12f8a14b8ce3ede5892fb5",
+                    "media_type": "image/png",
+                    "size": 3,
+                }],
+                "removed": [],
+                "changed": [],
+            })
+        );
+    }
+}
@@ -0,0 +1,63 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::diff;
+
+    fn blocks(texts: &[&str]) -> Vec<String> {
+        texts.iter().map(|text| text.to_string()).collect()
+    }
+
+    #[test]
+    fn identical() {
+        let text = blocks(&["One", "Two", "Three"]);
+
+        assert_eq!(diff::diff_text_blocks(&text, &text), (vec![], vec![]));
+    }
+
+    #[test]
+    fn changed_block() {
+        assert_eq!(
+            diff::diff_text_blocks(
+                &blocks(&["One", "Two", "Three"]),
+                &blocks(&["One", "2", "Three"])
+            ),
+            (blocks(&["Two"]), blocks(&["2"]))
+        );
+    }
+
+    #[test]
+    fn added_and_removed_blocks() {
+        assert_eq!(
+            diff::diff_text_blocks(
+                &blocks(&["Intro", "Old clause", "Terms", "Outro"]),
+                &blocks(&["Intro", "Terms", "New clause", "Outro", "Footer"])
+            ),
+            (blocks(&["Old clause"]), blocks(&["New clause", "Footer"]))
+        );
+    }
+
+    #[test]
+    fn scattered_changes() {
+        assert_eq!(
+            diff::diff_text_blocks(
+                &blocks(&["A", "B", "C", "D", "E", "F", "G", "H", "I"]),
+                &blocks(&["A", "C", "D", "X", "E", "G", "H", "Y", "I"])
+            ),
+            (blocks(&["B", "F"]), blocks(&["X", "Y"]))
+        );
+    }
+
+    #[test]
+    fn empty_previous() {
+        assert_eq!(
+            diff::diff_text_blocks(&[], &blocks(&["One"])),
+            (vec![], blocks(&["One"]))
+        );
+    }
+}
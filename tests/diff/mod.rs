@@ -0,0 +1,3 @@
+mod diff_document_snapshots;
+mod diff_text_blocks;
+mod take_document_snapshot;
@@ -0,0 +1,81 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::diff;
+
+    #[test]
+    fn title_and_metadata() {
+        let snapshot = diff::take_document_snapshot(
+            b"<!-- Saved from https://example.com/policy at 2025-01-01T00:00:00Z using monolith v2.10.1 -->\
+<html><head><title>  Privacy\n  policy </title></head><body></body></html>",
+        );
+
+        assert_eq!(snapshot.title, Some("Privacy policy".to_string()));
+        assert_eq!(
+            snapshot.metadata,
+            Some((
+                "https://example.com/policy".to_string(),
+                "2025-01-01T00:00:00Z".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn text_blocks() {
+        let snapshot = diff::take_document_snapshot(
+            b"<html><head><title>Title</title><style>p { color: red }</style></head><body>\
+<h1>Heading</h1><p>Some   <b>bold</b>\ntext</p><script>var a = 1;</script>\
+<ul><li>One</li><li>Two</li></ul>Trailing</body></html>",
+        );
+
+        assert_eq!(
+            snapshot.text,
+            vec!["Heading", "Some bold text", "One", "Two", "Trailing"]
+        );
+    }
+
+    #[test]
+    fn assets() {
+        let snapshot = diff::take_document_snapshot(
+            b"<html><head>\
+<link rel=\"stylesheet\" href=\"data:text/css,body{background:url(data:image/png;base64,AAAA)}\">\
+</head><body>\
+<img src=\"data:image/gif;base64,R0lGODlhAQABAAAAACw=\">\
+<img src=\"https://example.com/remote.png\">\
+<iframe src=\"data:text/html,%3Cimg%20src%3D%22data:image/svg+xml,%3Csvg/%3E%22%3E\"></iframe>\
+</body></html>",
+        );
+        let summary: Vec<(&str, &str, usize)> = snapshot
+            .assets
+            .iter()
+            .map(|asset| {
+                (
+                    asset.location.as_str(),
+                    asset.media_type.as_str(),
+                    asset.size,
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            summary,
+            vec![
+                ("link[href]", "text/css", 48),
+                ("link[href] > url()", "image/png", 3),
+                ("img[src]", "image/gif", 14),
+                ("iframe[src]", "text/html", 37),
+                ("iframe[src] > img[src]", "image/svg+xml", 6),
+            ]
+        );
+        assert_eq!(
+            snapshot.assets[1].hash,
+            "709e80c88487a2411e1ee4dfb9f22a861492d20c4765150c0c794abd70f8147c"
+        );
+    }
+}
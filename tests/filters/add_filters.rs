@@ -0,0 +1,59 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::filters::{ElementHidingFilter, FilterList};
+
+    #[test]
+    fn skips_comments_and_headers() {
+        let mut filter_list = FilterList::default();
+        filter_list.add_filters("[Adblock Plus 2.0]\n! Title: Example\n\n  \n");
+
+        assert!(filter_list.is_empty());
+    }
+
+    #[test]
+    fn network_and_element_hiding_filters() {
+        let mut filter_list = FilterList::default();
+        filter_list.add_filters(
+            "||ads.example.com^\n@@||ads.example.com/allowed.js\n##.banner\nexample.com,~shop.example.com#@#.banner\n",
+        );
+
+        assert_eq!(filter_list.network_filters.len(), 2);
+        assert!(!filter_list.network_filters[0].is_exception);
+        assert!(filter_list.network_filters[1].is_exception);
+        assert_eq!(
+            filter_list.element_hiding_filters,
+            vec![
+                ElementHidingFilter {
+                    selector: ".banner".to_string(),
+                    is_exception: false,
+                    domains: vec![],
+                    excluded_domains: vec![],
+                },
+                ElementHidingFilter {
+                    selector: ".banner".to_string(),
+                    is_exception: true,
+                    domains: vec!["example.com".to_string()],
+                    excluded_domains: vec!["shop.example.com".to_string()],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn skips_unsupported_filters() {
+        let mut filter_list = FilterList::default();
+        filter_list.add_filters(
+            "||example.com^$popup\n||example.com^$redirect=noopjs\nexample.com##+js(nobab)\n\
+example.com#?#div:has-text(Ad)\nexample.com#$#body { color: red }\n*\n/[invalid/\n",
+        );
+
+        assert!(filter_list.is_empty());
+    }
+}
@@ -0,0 +1,304 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::filters::{guess_resource_type, FilterList, ResourceType};
+    use monolith::url::Url;
+
+    fn blocks(filters: &str, url: &str, parent_url: &str) -> bool {
+        let url: Url = Url::parse(url).unwrap();
+
+        blocks_type(filters, &url, parent_url, guess_resource_type(&url))
+    }
+
+    fn blocks_type(
+        filters: &str,
+        url: &Url,
+        parent_url: &str,
+        resource_type: Option<ResourceType>,
+    ) -> bool {
+        let mut filter_list = FilterList::default();
+        filter_list.add_filters(filters);
+
+        filter_list.blocks_request(url, &Url::parse(parent_url).unwrap(), resource_type)
+    }
+
+    #[test]
+    fn domain_anchor() {
+        let filters = "||ads.example.com^";
+
+        assert!(blocks(
+            filters,
+            "https://ads.example.com/a.js",
+            "https://site.com/"
+        ));
+        assert!(blocks(
+            filters,
+            "http://cdn.ads.example.com/",
+            "https://site.com/"
+        ));
+        assert!(blocks(
+            filters,
+            "https://ads.example.com",
+            "https://site.com/"
+        ));
+        assert!(!blocks(
+            filters,
+            "https://badads.example.com/",
+            "https://site.com/"
+        ));
+        assert!(!blocks(
+            filters,
+            "https://ads.example.com.org/",
+            "https://site.com/"
+        ));
+        assert!(!blocks(
+            filters,
+            "https://site.com/?ads.example.com",
+            "https://site.com/"
+        ));
+    }
+
+    #[test]
+    fn path_patterns() {
+        let filters = "/banners/*.gif\n|https://cdn.site.com/track\n.swf|";
+
+        assert!(blocks(
+            filters,
+            "https://site.com/img/banners/top.gif",
+            "https://site.com/"
+        ));
+        assert!(!blocks(
+            filters,
+            "https://site.com/img/banners/top.png",
+            "https://site.com/"
+        ));
+        assert!(blocks(
+            filters,
+            "https://cdn.site.com/track?id=1",
+            "https://site.com/"
+        ));
+        assert!(!blocks(
+            filters,
+            "https://site.com/?https://cdn.site.com/track",
+            "https://site.com/"
+        ));
+        assert!(blocks(
+            filters,
+            "https://site.com/movie.swf",
+            "https://site.com/"
+        ));
+        assert!(!blocks(
+            filters,
+            "https://site.com/movie.swf?x",
+            "https://site.com/"
+        ));
+    }
+
+    #[test]
+    fn separators() {
+        let filters = "||example.com/ad^";
+
+        assert!(blocks(
+            filters,
+            "https://example.com/ad?x=1",
+            "https://site.com/"
+        ));
+        assert!(blocks(
+            filters,
+            "https://example.com/ad/",
+            "https://site.com/"
+        ));
+        assert!(blocks(
+            filters,
+            "https://example.com/ad",
+            "https://site.com/"
+        ));
+        assert!(!blocks(
+            filters,
+            "https://example.com/admin",
+            "https://site.com/"
+        ));
+        assert!(!blocks(
+            filters,
+            "https://example.com/ad.js",
+            "https://site.com/"
+        ));
+    }
+
+    #[test]
+    fn regular_expressions() {
+        let filters = "/\\/ad[0-9]+\\.js$/$script";
+
+        assert!(blocks(
+            filters,
+            "https://site.com/ad123.js",
+            "https://site.com/"
+        ));
+        assert!(!blocks(
+            filters,
+            "https://site.com/ad.js",
+            "https://site.com/"
+        ));
+    }
+
+    #[test]
+    fn third_party() {
+        let filters = "||tracker.com^$third-party\n/analytics.js$~third-party";
+
+        assert!(blocks(
+            filters,
+            "https://tracker.com/t.gif",
+            "https://site.com/"
+        ));
+        assert!(!blocks(
+            filters,
+            "https://tracker.com/t.gif",
+            "https://www.tracker.com/"
+        ));
+        assert!(blocks(
+            filters,
+            "https://site.com/analytics.js",
+            "https://www.site.com/"
+        ));
+        assert!(!blocks(
+            filters,
+            "https://cdn.com/analytics.js",
+            "https://site.com/"
+        ));
+    }
+
+    #[test]
+    fn resource_types() {
+        let filters = "||cdn.com/ads/$image,script\n||cdn.com/tracking/$~image";
+
+        assert!(blocks(
+            filters,
+            "https://cdn.com/ads/a.png",
+            "https://site.com/"
+        ));
+        assert!(blocks(
+            filters,
+            "https://cdn.com/ads/a.js",
+            "https://site.com/"
+        ));
+        assert!(!blocks(
+            filters,
+            "https://cdn.com/ads/a.css",
+            "https://site.com/"
+        ));
+        // Requests of unknown types match any of them
+        assert!(blocks(
+            filters,
+            "https://cdn.com/ads/pixel?id=1",
+            "https://site.com/"
+        ));
+        assert!(!blocks(
+            filters,
+            "https://cdn.com/tracking/a.gif",
+            "https://site.com/"
+        ));
+        assert!(blocks(
+            filters,
+            "https://cdn.com/tracking/a.js",
+            "https://site.com/"
+        ));
+    }
+
+    #[test]
+    fn given_resource_types() {
+        let filters = "||cdn.com/pixel$image";
+        let url: Url = Url::parse("https://cdn.com/pixel?id=1").unwrap();
+
+        assert!(blocks_type(
+            filters,
+            &url,
+            "https://site.com/",
+            Some(ResourceType::Image)
+        ));
+        assert!(!blocks_type(
+            filters,
+            &url,
+            "https://site.com/",
+            Some(ResourceType::Script)
+        ));
+    }
+
+    #[test]
+    fn ignored_resource_types() {
+        assert!(!blocks(
+            "||cdn.com^$ping,xhr",
+            "https://cdn.com/a",
+            "https://site.com/"
+        ));
+    }
+
+    #[test]
+    fn domain_option() {
+        let filters = "/ads.js$domain=site.com|~shop.site.com";
+
+        assert!(blocks(
+            filters,
+            "https://cdn.com/ads.js",
+            "https://www.site.com/"
+        ));
+        assert!(!blocks(
+            filters,
+            "https://cdn.com/ads.js",
+            "https://shop.site.com/"
+        ));
+        assert!(!blocks(
+            filters,
+            "https://cdn.com/ads.js",
+            "https://other.com/"
+        ));
+    }
+
+    #[test]
+    fn exceptions() {
+        let filters =
+            "||cdn.com^\n@@||cdn.com/jquery.js\n||cdn.com/evil.js$important\n@@||cdn.com/evil.js";
+
+        assert!(blocks(
+            filters,
+            "https://cdn.com/ads.js",
+            "https://site.com/"
+        ));
+        assert!(!blocks(
+            filters,
+            "https://cdn.com/jquery.js",
+            "https://site.com/"
+        ));
+        assert!(blocks(
+            filters,
+            "https://cdn.com/evil.js",
+            "https://site.com/"
+        ));
+    }
+
+    #[test]
+    fn match_case() {
+        let filters = "/Banner/*$match-case\n/popup/*";
+
+        assert!(blocks(
+            filters,
+            "https://site.com/Banner/1.png",
+            "https://site.com/"
+        ));
+        assert!(!blocks(
+            filters,
+            "https://site.com/banner/1.png",
+            "https://site.com/"
+        ));
+        assert!(blocks(
+            filters,
+            "https://site.com/PopUp/1.png",
+            "https://site.com/"
+        ));
+    }
+}
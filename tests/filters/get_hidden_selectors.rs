@@ -0,0 +1,58 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::filters::FilterList;
+    use monolith::url::Url;
+
+    #[test]
+    fn generic_and_specific() {
+        let mut filter_list = FilterList::default();
+        filter_list.add_filters(
+            "##.ad\nexample.com###sidebar\n~shop.example.com##.promo\nother.com##.banner\n",
+        );
+
+        assert_eq!(
+            filter_list.get_hidden_selectors(&Url::parse("https://www.example.com/").unwrap()),
+            vec![".ad", "#sidebar", ".promo"]
+        );
+        assert_eq!(
+            filter_list.get_hidden_selectors(&Url::parse("https://shop.example.com/").unwrap()),
+            vec![".ad", "#sidebar"]
+        );
+    }
+
+    #[test]
+    fn exceptions() {
+        let mut filter_list = FilterList::default();
+        filter_list.add_filters("##.ad\n##.banner\nexample.com#@#.ad\n");
+
+        assert_eq!(
+            filter_list.get_hidden_selectors(&Url::parse("https://example.com/").unwrap()),
+            vec![".banner"]
+        );
+        assert_eq!(
+            filter_list.get_hidden_selectors(&Url::parse("https://other.com/").unwrap()),
+            vec![".ad", ".banner"]
+        );
+    }
+
+    #[test]
+    fn wildcard_top_level_domains() {
+        let mut filter_list = FilterList::default();
+        filter_list.add_filters("google.*##.ad\n");
+
+        assert_eq!(
+            filter_list.get_hidden_selectors(&Url::parse("https://www.google.de/").unwrap()),
+            vec![".ad"]
+        );
+        assert!(filter_list
+            .get_hidden_selectors(&Url::parse("https://google.example.com/").unwrap())
+            .is_empty());
+    }
+}
@@ -0,0 +1,38 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::filters::{self, ResourceType};
+    use monolith::url::Url;
+
+    #[test]
+    fn known_extensions() {
+        for (url, resource_type) in [
+            ("https://site.com/style.css", ResourceType::Stylesheet),
+            ("https://site.com/app.js?v=1", ResourceType::Script),
+            ("https://site.com/photo.JPG", ResourceType::Image),
+            ("https://site.com/font.woff2", ResourceType::Font),
+            ("https://site.com/clip.mp4", ResourceType::Media),
+            ("https://site.com/frame.html", ResourceType::Subdocument),
+            ("https://site.com/data.json", ResourceType::Other),
+        ] {
+            assert_eq!(
+                filters::guess_resource_type(&Url::parse(url).unwrap()),
+                Some(resource_type)
+            );
+        }
+    }
+
+    #[test]
+    fn unknown_extension() {
+        assert_eq!(
+            filters::guess_resource_type(&Url::parse("https://site.com/pixel?id=1").unwrap()),
+            None
+        );
+    }
+}
@@ -0,0 +1,6 @@
+mod add_filters;
+mod blocks_request;
+mod get_hidden_selectors;
+mod guess_resource_type;
+mod parse_url_rule;
+mod rules_allow_url;
@@ -0,0 +1,90 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::filters::{self, ResourceType, UrlPattern};
+
+    #[test]
+    fn glob() {
+        let url_rule = filters::parse_url_rule("*/analytics/*.js", true).unwrap();
+
+        assert!(
+            matches!(url_rule.pattern, UrlPattern::Glob(ref glob) if glob == "*/analytics/*.js")
+        );
+        assert!(url_rule.is_exclusion);
+        assert_eq!(url_rule.resource_type, None);
+    }
+
+    #[test]
+    fn glob_with_scheme() {
+        let url_rule = filters::parse_url_rule("https://cdn.com/*", false).unwrap();
+
+        assert!(
+            matches!(url_rule.pattern, UrlPattern::Glob(ref glob) if glob == "https://cdn.com/*")
+        );
+        assert!(!url_rule.is_exclusion);
+        assert_eq!(url_rule.resource_type, None);
+    }
+
+    #[test]
+    fn asset_types() {
+        for (url_rule, resource_type) in [
+            ("css:*", ResourceType::Stylesheet),
+            ("font:*", ResourceType::Font),
+            ("frame:*", ResourceType::Subdocument),
+            ("image:*", ResourceType::Image),
+            ("media:*", ResourceType::Media),
+            ("script:https://cdn.com/*", ResourceType::Script),
+        ] {
+            assert_eq!(
+                filters::parse_url_rule(url_rule, true)
+                    .unwrap()
+                    .resource_type,
+                Some(resource_type)
+            );
+        }
+    }
+
+    #[test]
+    fn regex() {
+        let url_rule = filters::parse_url_rule(r"script:regex:\.min\.js$", true).unwrap();
+
+        assert!(
+            matches!(url_rule.pattern, UrlPattern::Regex(ref regex) if regex.as_str() == r"\.min\.js$")
+        );
+        assert_eq!(url_rule.resource_type, Some(ResourceType::Script));
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::filters;
+
+    #[test]
+    fn unknown_asset_type() {
+        assert!(filters::parse_url_rule("img:*.png", true).is_none());
+    }
+
+    #[test]
+    fn bad_regex() {
+        assert!(filters::parse_url_rule("regex:[a-", true).is_none());
+    }
+
+    #[test]
+    fn empty() {
+        assert!(filters::parse_url_rule("", true).is_none());
+        assert!(filters::parse_url_rule("script:", true).is_none());
+    }
+}
@@ -0,0 +1,133 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::filters::{self, ResourceType, UrlRule};
+    use monolith::url::Url;
+
+    fn allows(url_rules: &[UrlRule], url: &str, resource_type: Option<ResourceType>) -> bool {
+        filters::rules_allow_url(url_rules, &Url::parse(url).unwrap(), resource_type)
+    }
+
+    fn parse_url_rules(exclusion_rules: &[&str], inclusion_rules: &[&str]) -> Vec<UrlRule> {
+        exclusion_rules
+            .iter()
+            .map(|rule| filters::parse_url_rule(rule, true).unwrap())
+            .chain(
+                inclusion_rules
+                    .iter()
+                    .map(|rule| filters::parse_url_rule(rule, false).unwrap()),
+            )
+            .collect()
+    }
+
+    #[test]
+    fn no_rules() {
+        assert!(allows(
+            &[],
+            "https://site.com/a.js",
+            Some(ResourceType::Script)
+        ));
+    }
+
+    #[test]
+    fn exclusion_by_type() {
+        let url_rules = parse_url_rules(&["script:/analytics/*.js"], &[]);
+
+        assert!(!allows(
+            &url_rules,
+            "https://site.com/analytics/track.js",
+            Some(ResourceType::Script)
+        ));
+        assert!(!allows(
+            &url_rules,
+            "https://site.com/analytics/track.js?v=2#top",
+            Some(ResourceType::Script)
+        ));
+        assert!(allows(
+            &url_rules,
+            "https://site.com/app.js",
+            Some(ResourceType::Script)
+        ));
+        assert!(allows(
+            &url_rules,
+            "https://site.com/analytics/track.js",
+            Some(ResourceType::Image)
+        ));
+        assert!(allows(
+            &url_rules,
+            "https://site.com/js/analytics/track.js",
+            Some(ResourceType::Script)
+        ));
+    }
+
+    #[test]
+    fn exclusion_by_query() {
+        let url_rules = parse_url_rules(&["/*?*utm_*", "*://*/pixel?id=*"], &[]);
+
+        assert!(!allows(
+            &url_rules,
+            "https://site.com/a.png?utm_source=x",
+            None
+        ));
+        assert!(!allows(&url_rules, "http://cdn.com/pixel?id=1", None));
+        assert!(allows(&url_rules, "https://site.com/a.png?v=1", None));
+        assert!(allows(&url_rules, "https://site.com/pixel", None));
+    }
+
+    #[test]
+    fn exclusion_by_regex() {
+        let url_rules = parse_url_rules(&[r"regex:^https?://[^/]*\.ads\."], &[]);
+
+        assert!(!allows(&url_rules, "https://cdn.ads.com/a.png", None));
+        assert!(allows(&url_rules, "https://site.com/x.ads.png", None));
+    }
+
+    #[test]
+    fn inclusion_by_type() {
+        let url_rules = parse_url_rules(&[], &["font:https://fonts.site.com/*"]);
+
+        assert!(allows(
+            &url_rules,
+            "https://fonts.site.com/a.woff2",
+            Some(ResourceType::Font)
+        ));
+        assert!(!allows(
+            &url_rules,
+            "https://cdn.com/a.woff2",
+            Some(ResourceType::Font)
+        ));
+        // Assets of other types aren't affected
+        assert!(allows(
+            &url_rules,
+            "https://cdn.com/a.png",
+            Some(ResourceType::Image)
+        ));
+    }
+
+    #[test]
+    fn exclusion_over_inclusion() {
+        let url_rules = parse_url_rules(&["script:*/analytics/*"], &["https://site.com/*"]);
+
+        assert!(allows(
+            &url_rules,
+            "https://site.com/app.js",
+            Some(ResourceType::Script)
+        ));
+        assert!(!allows(
+            &url_rules,
+            "https://site.com/analytics/a.js",
+            Some(ResourceType::Script)
+        ));
+        assert!(!allows(
+            &url_rules,
+            "https://cdn.com/app.js",
+            Some(ResourceType::Script)
+        ));
+    }
+}
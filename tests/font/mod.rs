@@ -0,0 +1 @@
+mod subset_font;
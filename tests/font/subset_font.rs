@@ -0,0 +1,60 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use std::collections::HashSet;
+    use std::fs;
+
+    use allsorts::binary::read::ReadScope;
+    use allsorts::font::MatchingPresentation;
+    use allsorts::font_data::FontData;
+    use allsorts::Font;
+
+    use monolith::font::subset_font;
+
+    #[test]
+    fn woff2() {
+        let data: Vec<u8> = fs::read("tests/_data_/fonts/open-sans-regular.woff2").unwrap();
+        let chars: HashSet<char> = "Hello".chars().collect();
+
+        let (subset_data, media_type) = subset_font(&data, &chars).unwrap();
+
+        assert!(subset_data.len() < data.len());
+        assert_eq!(media_type, "font/ttf");
+
+        let font_data: FontData = ReadScope::new(&subset_data).read::<FontData>().unwrap();
+        let mut font = Font::new(font_data.table_provider(0).unwrap()).unwrap();
+        for c in "Hello".chars() {
+            let (glyph_id, _) = font.lookup_glyph_index(c, MatchingPresentation::NotRequired, None);
+            assert_ne!(glyph_id, 0);
+        }
+        let (glyph_id, _) = font.lookup_glyph_index('Z', MatchingPresentation::NotRequired, None);
+        assert_eq!(glyph_id, 0);
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use std::collections::HashSet;
+
+    use monolith::font::subset_font;
+
+    #[test]
+    fn not_a_font() {
+        let chars: HashSet<char> = "Hello".chars().collect();
+
+        assert_eq!(subset_font(b"\x89PNG\x0D\x0A\x1A\x0A", &chars), None);
+    }
+}
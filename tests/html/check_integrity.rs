@@ -40,6 +40,17 @@ mod passing {
             "sha512-zG5B88cYMqcdiMi9gz0XkOFYw2BpjeYdn5V6+oFrMgSNjRpqL7EF8JEwl17ztZbK3N7I/tTwp3kxQbN1RgFBww=="
         ));
     }
+
+    #[test]
+    fn multiple_hashes() {
+        assert!(html::check_integrity(
+            "abcdef0123456789".as_bytes(),
+            "sha256-47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU= \
+             sha384-47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU= \
+             sha384-gc9l7omltke8C33bedgh15E12M7RrAQa5t63Yb8APlpe7ZhiqV23+oqiulSJl3Kw?opt \
+             md5-x"
+        ));
+    }
 }
 
 //  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
@@ -53,6 +64,15 @@ mod passing {
 mod failing {
     use monolith::html;
 
+    #[test]
+    fn weaker_algorithm_matches() {
+        assert!(!html::check_integrity(
+            "abcdef0123456789".as_bytes(),
+            "sha256-9EWAHgy4mSYsm54hmDaIDXPKLRsLnBX7lZyQ6xISNOM= \
+             sha512-47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU="
+        ));
+    }
+
     #[test]
     fn empty_hash() {
         assert!(!html::check_integrity("abcdef0123456789".as_bytes(), ""));
@@ -0,0 +1,22 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::html;
+
+    #[test]
+    fn text_and_rendered_attributes() {
+        let html = "<!doctype html><html><head><title>Title</title><style>body{}</style></head><body><p>Hello <b>world</b></p><img alt=\"Alt\" /><input placeholder=\"Hint\" value=\"Value\" /><script>var a;</script></body></html>";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+
+        assert_eq!(
+            html::get_text(&dom.document),
+            "TitleHello worldAltHintValue"
+        );
+    }
+}
@@ -0,0 +1,126 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use markup5ever_rcdom::Handle;
+    use url::Url;
+
+    use monolith::html;
+
+    fn is_tracking(html: &str, element_name: &str) -> bool {
+        let dom = html::html_to_dom(
+            &format!("<body>{}</body>", html).as_bytes().to_vec(),
+            "".to_string(),
+        );
+        let url: Url = Url::parse("https://site.com/").unwrap();
+        let nodes: Vec<Handle> =
+            html::find_nodes(&dom.document, vec!["html", "body", element_name]);
+
+        html::is_tracking_element(&nodes[0], &url)
+    }
+
+    #[test]
+    fn analytics_script() {
+        assert!(is_tracking(
+            "<script async src=\"https://www.googletagmanager.com/gtag/js?id=G-1\"></script>",
+            "script"
+        ));
+    }
+
+    #[test]
+    fn analytics_snippet() {
+        assert!(is_tracking(
+            "<script>window.dataLayer = window.dataLayer || []; function gtag(){dataLayer.push(arguments);}</script>",
+            "script"
+        ));
+        assert!(is_tracking(
+            "<script>!function(f,b,e,v,n,t,s){}(window,document,'script','https://connect.facebook.net/en_US/fbevents.js');</script>",
+            "script"
+        ));
+    }
+
+    #[test]
+    fn tracking_pixel() {
+        assert!(is_tracking(
+            "<img src=\"https://www.facebook.com/tr?id=1&ev=PageView\">",
+            "img"
+        ));
+        assert!(is_tracking(
+            "<img src=\"/counter.gif?page=1\" width=\"1\" height=\"1\">",
+            "img"
+        ));
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use markup5ever_rcdom::Handle;
+    use url::Url;
+
+    use monolith::html;
+
+    fn is_tracking(html: &str, element_name: &str) -> bool {
+        let dom = html::html_to_dom(
+            &format!("<body>{}</body>", html).as_bytes().to_vec(),
+            "".to_string(),
+        );
+        let url: Url = Url::parse("https://site.com/").unwrap();
+        let nodes: Vec<Handle> =
+            html::find_nodes(&dom.document, vec!["html", "body", element_name]);
+
+        html::is_tracking_element(&nodes[0], &url)
+    }
+
+    #[test]
+    fn regular_script() {
+        assert!(!is_tracking("<script src=\"/app.js\"></script>", "script"));
+        assert!(!is_tracking(
+            "<script>document.title = 'Hello';</script>",
+            "script"
+        ));
+    }
+
+    #[test]
+    fn application_script_mentioning_analytics() {
+        assert!(!is_tracking(
+            &format!(
+                "<script>function track(event) {{ if (window.gtag) {{ gtag('event', event); }} }}{}</script>",
+                "render();".repeat(500)
+            ),
+            "script"
+        ));
+        assert!(!is_tracking(
+            "<script>console.log('Consent declined, google-analytics.com is not loaded');</script>",
+            "script"
+        ));
+    }
+
+    #[test]
+    fn structured_data() {
+        assert!(!is_tracking(
+            "<script type=\"application/ld+json\">{\"url\": \"https://www.google-analytics.com/\"}</script>",
+            "script"
+        ));
+    }
+
+    #[test]
+    fn regular_image() {
+        assert!(!is_tracking("<img src=\"/photo.jpg\">", "img"));
+        assert!(!is_tracking(
+            "<img src=\"data:image/gif;base64,R0lGODlhAQABAAAAACw=\" width=\"1\" height=\"1\">",
+            "img"
+        ));
+    }
+}
@@ -7,10 +7,16 @@ mod get_base_url;
 mod get_charset;
 mod get_node_attr;
 mod get_node_name;
+mod get_text;
 mod has_favicon;
 mod is_favicon;
+mod is_tracking_element;
+mod parse_html_document;
 mod parse_link_type;
+mod parse_metadata_tag;
 mod parse_srcset;
+mod regenerate_integrity;
+mod resolve_lazy_loading;
 mod serialize_document;
 mod set_node_attr;
 mod walk;
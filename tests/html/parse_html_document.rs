@@ -0,0 +1,48 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::html;
+
+    #[test]
+    fn utf8_by_default() {
+        let (dom, encoding) = html::parse_html_document("<title>Café</title>".as_bytes(), "");
+
+        assert_eq!(encoding, "utf-8");
+        assert_eq!(html::get_title(&dom.document), Some("Café".to_string()));
+    }
+
+    #[test]
+    fn declared_charset() {
+        let (dom, encoding) = html::parse_html_document(
+            b"<meta charset=\"windows-1251\"><title>\xcf\xf0\xe8\xe2\xe5\xf2</title>",
+            "",
+        );
+
+        assert_eq!(encoding, "windows-1251");
+        assert_eq!(html::get_title(&dom.document), Some("Привет".to_string()));
+    }
+
+    #[test]
+    fn protocol_charset_takes_precedence() {
+        let (dom, encoding) = html::parse_html_document(
+            b"<meta charset=\"windows-1251\"><title>\xe9</title>",
+            "ISO-8859-1",
+        );
+
+        assert_eq!(encoding, "ISO-8859-1");
+        assert_eq!(html::get_title(&dom.document), Some("é".to_string()));
+    }
+
+    #[test]
+    fn implied_us_ascii_charset() {
+        let (_, encoding) = html::parse_html_document(b"<title></title>", "US-ASCII");
+
+        assert_eq!(encoding, "utf-8");
+    }
+}
@@ -29,6 +29,16 @@ mod passing {
         assert!(html::parse_link_type("preload stylesheet").contains(&html::LinkType::Stylesheet));
     }
 
+    #[test]
+    fn manifest() {
+        assert!(html::parse_link_type("manifest").contains(&html::LinkType::Manifest));
+    }
+
+    #[test]
+    fn modulepreload() {
+        assert!(html::parse_link_type("modulepreload").contains(&html::LinkType::ModulePreload));
+    }
+
     #[test]
     fn apple_touch_icon() {
         assert!(html::parse_link_type("apple-touch-icon").contains(&html::LinkType::AppleTouchIcon));
@@ -0,0 +1,75 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::html;
+
+    #[test]
+    fn http_url() {
+        assert_eq!(
+            html::parse_metadata_tag(
+                "<!-- Saved from https://example.com/page at 2024-01-01T00:00:00Z using monolith v2.11.0 -->\n<html></html>"
+            ),
+            Some((
+                "https://example.com/page".to_string(),
+                "2024-01-01T00:00:00Z".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn local_source() {
+        assert_eq!(
+            html::parse_metadata_tag(
+                "\u{feff}<!-- Saved from local source at 2024-01-01T00:00:00Z using monolith v2.11.0 -->"
+            ),
+            Some((
+                "local source".to_string(),
+                "2024-01-01T00:00:00Z".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn created_by_create_metadata_tag() {
+        let url = monolith::url::Url::parse("http://192.168.1.1/").unwrap();
+        let (source, _) = html::parse_metadata_tag(&html::create_metadata_tag(&url)).unwrap();
+
+        assert_eq!(source, "http://192.168.1.1/");
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::html;
+
+    #[test]
+    fn no_comment() {
+        assert_eq!(
+            html::parse_metadata_tag("<html><head></head><body></body></html>"),
+            None
+        );
+    }
+
+    #[test]
+    fn comment_not_at_top() {
+        assert_eq!(
+            html::parse_metadata_tag(
+                "<html><!-- Saved from https://example.com/ at 2024-01-01T00:00:00Z using monolith v2.11.0 --></html>"
+            ),
+            None
+        );
+    }
+}
@@ -0,0 +1,76 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::html;
+
+    #[test]
+    fn same_algorithm() {
+        assert_eq!(
+            html::regenerate_integrity(
+                "abcdef0123456789".as_bytes(),
+                "sha256-47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU="
+            ),
+            Some("sha256-9EWAHgy4mSYsm54hmDaIDXPKLRsLnBX7lZyQ6xISNOM=".to_string())
+        );
+    }
+
+    #[test]
+    fn multiple_algorithms() {
+        assert_eq!(
+            html::regenerate_integrity(
+                "abcdef0123456789".as_bytes(),
+                "sha256-a sha384-b   sha256-c"
+            ),
+            Some(
+                "sha256-9EWAHgy4mSYsm54hmDaIDXPKLRsLnBX7lZyQ6xISNOM= \
+                sha384-gc9l7omltke8C33bedgh15E12M7RrAQa5t63Yb8APlpe7ZhiqV23+oqiulSJl3Kw"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn unsupported_algorithms() {
+        assert_eq!(
+            html::regenerate_integrity("abcdef0123456789".as_bytes(), "md5-a sha512-b"),
+            Some(
+                "sha512-zG5B88cYMqcdiMi9gz0XkOFYw2BpjeYdn5V6+oFrMgSNjRpqL7EF8JEwl17ztZbK3N7I/tTwp3kxQbN1RgFBww=="
+                    .to_string()
+            )
+        );
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::html;
+
+    #[test]
+    fn no_supported_algorithms() {
+        assert_eq!(
+            html::regenerate_integrity("abcdef0123456789".as_bytes(), "md5-a sha1-b"),
+            None
+        );
+    }
+
+    #[test]
+    fn malformed() {
+        assert_eq!(
+            html::regenerate_integrity("abcdef0123456789".as_bytes(), "sha256"),
+            None
+        );
+    }
+}
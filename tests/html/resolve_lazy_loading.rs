@@ -0,0 +1,178 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use markup5ever_rcdom::Handle;
+
+    use monolith::html;
+
+    fn default_lazy_load_attrs() -> Vec<String> {
+        html::LAZY_LOAD_ATTRS
+            .iter()
+            .map(|a| a.to_string())
+            .collect()
+    }
+
+    fn first_node(html: &str, path: Vec<&str>) -> (markup5ever_rcdom::RcDom, Handle) {
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+        let node = html::find_nodes(&dom.document, path)[0].clone();
+        (dom, node)
+    }
+
+    #[test]
+    fn data_src() {
+        let (_dom, node) = first_node(
+            "<img src=\"placeholder.gif\" data-src=\"real.png\" data-srcset=\"real@2x.png 2x\">",
+            vec!["html", "body", "img"],
+        );
+
+        html::resolve_lazy_loading(&node, &default_lazy_load_attrs());
+
+        assert_eq!(
+            html::get_node_attr(&node, "src"),
+            Some("real.png".to_string())
+        );
+        assert_eq!(
+            html::get_node_attr(&node, "srcset"),
+            Some("real@2x.png 2x".to_string())
+        );
+        assert_eq!(html::get_node_attr(&node, "data-src"), None);
+        assert_eq!(html::get_node_attr(&node, "data-srcset"), None);
+    }
+
+    #[test]
+    fn native_loading_attr_and_library_classes() {
+        let (_dom, node) = first_node(
+            "<img class=\"photo lazyload\" loading=\"lazy\" data-lazy-src=\"real.png\">",
+            vec!["html", "body", "img"],
+        );
+
+        html::resolve_lazy_loading(&node, &default_lazy_load_attrs());
+
+        assert_eq!(
+            html::get_node_attr(&node, "src"),
+            Some("real.png".to_string())
+        );
+        assert_eq!(html::get_node_attr(&node, "loading"), None);
+        assert_eq!(
+            html::get_node_attr(&node, "class"),
+            Some("photo lazyloaded".to_string())
+        );
+    }
+
+    #[test]
+    fn background() {
+        let (_dom, node) = first_node(
+            "<div data-bg=\"hero.jpg\" style=\"color: red;\"></div>",
+            vec!["html", "body", "div"],
+        );
+
+        html::resolve_lazy_loading(&node, &default_lazy_load_attrs());
+
+        assert_eq!(
+            html::get_node_attr(&node, "style"),
+            Some("background-image: url(\"hero.jpg\"); color: red;".to_string())
+        );
+        assert_eq!(html::get_node_attr(&node, "data-bg"), None);
+    }
+
+    #[test]
+    fn iframe_and_video() {
+        let (dom, iframe_node) = first_node(
+            "<iframe data-src=\"frame.html\"></iframe><video data-src=\"movie.mp4\" data-poster=\"poster.png\"></video>",
+            vec!["html", "body", "iframe"],
+        );
+        let video_node = html::find_nodes(&dom.document, vec!["html", "body", "video"])[0].clone();
+
+        html::resolve_lazy_loading(&iframe_node, &default_lazy_load_attrs());
+        html::resolve_lazy_loading(&video_node, &default_lazy_load_attrs());
+
+        assert_eq!(
+            html::get_node_attr(&iframe_node, "src"),
+            Some("frame.html".to_string())
+        );
+        assert_eq!(
+            html::get_node_attr(&video_node, "src"),
+            Some("movie.mp4".to_string())
+        );
+        assert_eq!(
+            html::get_node_attr(&video_node, "poster"),
+            Some("poster.png".to_string())
+        );
+    }
+
+    #[test]
+    fn noscript_fallback() {
+        let (dom, node) = first_node(
+            "<img loading=\"lazy\" src=\"data:image/gif;base64,R0lGODlhAQABAAAAACw=\"> <noscript><img src=\"real.png\" srcset=\"real@2x.png 2x\"></noscript>",
+            vec!["html", "body", "img"],
+        );
+
+        html::resolve_lazy_loading(&node, &default_lazy_load_attrs());
+
+        assert_eq!(
+            html::get_node_attr(&node, "src"),
+            Some("real.png".to_string())
+        );
+        assert_eq!(
+            html::get_node_attr(&node, "srcset"),
+            Some("real@2x.png 2x".to_string())
+        );
+        let noscript_node =
+            html::find_nodes(&dom.document, vec!["html", "body", "noscript"])[0].clone();
+        assert!(noscript_node.children.borrow().is_empty());
+    }
+
+    #[test]
+    fn custom_attrs() {
+        let (_dom, node) = first_node(
+            "<img data-src=\"ignored.png\" data-full=\"real.png\">",
+            vec!["html", "body", "img"],
+        );
+
+        html::resolve_lazy_loading(&node, &["data-full".to_string()]);
+
+        assert_eq!(
+            html::get_node_attr(&node, "src"),
+            Some("real.png".to_string())
+        );
+        assert_eq!(
+            html::get_node_attr(&node, "data-src"),
+            Some("ignored.png".to_string())
+        );
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use markup5ever_rcdom::Handle;
+
+    use monolith::html;
+
+    #[test]
+    fn ignores_src_attrs_on_unrelated_elements() {
+        let html = "<div data-src=\"real.png\"></div>";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+        let node: Handle = html::find_nodes(&dom.document, vec!["html", "body", "div"])[0].clone();
+
+        html::resolve_lazy_loading(&node, &["data-src".to_string()]);
+
+        assert_eq!(html::get_node_attr(&node, "src"), None);
+        assert_eq!(
+            html::get_node_attr(&node, "data-src"),
+            Some("real.png".to_string())
+        );
+    }
+}
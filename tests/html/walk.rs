@@ -12,9 +12,10 @@ mod passing {
     use url::Url;
 
     use monolith::core::MonolithOptions;
+    use monolith::filters::FilterList;
     use monolith::html;
     use monolith::session::Session;
-    use monolith::url::EMPTY_IMAGE_DATA_URL;
+    use monolith::url::{parse_data_url, EMPTY_IMAGE_DATA_URL};
 
     #[test]
     fn basic() {
@@ -574,4 +575,423 @@ mod passing {
             </html>"
         );
     }
+
+    #[test]
+    fn embeds_object_embed_and_track() {
+        let html = "\
+            <link rel=\"manifest\" href=\"data:application/manifest+json;base64,e30=\">\
+            <video><track kind=\"captions\" src=\"data:text/plain;base64,V0VCVlRU\"></video>\
+            <object data=\"data:application/pdf;base64,JVBERi0=\" type=\"application/pdf\"></object>\
+            <embed src=\"data:image/svg+xml;base64,PHN2Zz48L3N2Zz4=\" type=\"image/svg+xml\">\
+        ";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+        let url: Url = Url::parse("http://localhost").unwrap();
+
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+
+        let mut session: Session = Session::new(None, None, options);
+
+        html::walk(&mut session, &url, &dom.document);
+
+        let mut buf: Vec<u8> = Vec::new();
+        serialize(
+            &mut buf,
+            &SerializableHandle::from(dom.document.clone()),
+            SerializeOpts::default(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            buf.iter().map(|&c| c as char).collect::<String>(),
+            "\
+            <html>\
+                <head>\
+                    <link rel=\"manifest\" href=\"data:application/manifest+json;base64,e30=\">\
+                </head>\
+                <body>\
+                    <video><track kind=\"captions\" src=\"data:text/vtt;charset=utf-8;base64,V0VCVlRU\"></video>\
+                    <object data=\"data:application/pdf;base64,JVBERi0=\" type=\"application/pdf\"></object>\
+                    <embed src=\"data:image/svg+xml;base64,PHN2Zz48L3N2Zz4=\" type=\"image/svg+xml\">\
+                </body>\
+            </html>"
+        );
+    }
+
+    #[test]
+    fn no_images_removes_image_embeds() {
+        let html = "<embed src=\"icon.svg\"><object data=\"photo.png\"></object>";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+        let url: Url = Url::parse("http://localhost").unwrap();
+
+        let mut options = MonolithOptions::default();
+        options.no_images = true;
+        options.silent = true;
+
+        let mut session: Session = Session::new(None, None, options);
+
+        html::walk(&mut session, &url, &dom.document);
+
+        let mut buf: Vec<u8> = Vec::new();
+        serialize(
+            &mut buf,
+            &SerializableHandle::from(dom.document.clone()),
+            SerializeOpts::default(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            buf.iter().map(|&c| c as char).collect::<String>(),
+            "<html><head></head><body><embed><object></object></body></html>"
+        );
+    }
+
+    #[test]
+    fn processes_iframe_srcdoc() {
+        let html = "<iframe srcdoc=\"<img src='data:image/png;base64,iVBORw0KGgo='>\"></iframe>";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+        let url: Url = Url::parse("http://localhost").unwrap();
+
+        let mut options = MonolithOptions::default();
+        options.no_images = true;
+        options.silent = true;
+
+        let mut session: Session = Session::new(None, None, options);
+
+        html::walk(&mut session, &url, &dom.document);
+
+        let mut buf: Vec<u8> = Vec::new();
+        serialize(
+            &mut buf,
+            &SerializableHandle::from(dom.document.clone()),
+            SerializeOpts::default(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            buf.iter().map(|&c| c as char).collect::<String>(),
+            format!(
+                "<html><head></head><body>\
+                    <iframe srcdoc=\"<html><head></head><body><img src=&quot;{}&quot;></body></html>\"></iframe>\
+                </body></html>",
+                EMPTY_IMAGE_DATA_URL,
+            )
+        );
+    }
+
+    #[test]
+    fn frames_as_srcdoc() {
+        let html = "<iframe src=\"data:text/html,<p>hi</p>\"></iframe>";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+        let url: Url = Url::parse("http://localhost").unwrap();
+
+        let mut options = MonolithOptions::default();
+        options.frames_as_srcdoc = true;
+        options.silent = true;
+
+        let mut session: Session = Session::new(None, None, options);
+
+        html::walk(&mut session, &url, &dom.document);
+
+        let mut buf: Vec<u8> = Vec::new();
+        serialize(
+            &mut buf,
+            &SerializableHandle::from(dom.document.clone()),
+            SerializeOpts::default(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            buf.iter().map(|&c| c as char).collect::<String>(),
+            "<html><head></head><body>\
+                <iframe srcdoc=\"<html><head></head><body><p>hi</p></body></html>\"></iframe>\
+            </body></html>"
+        );
+    }
+
+    #[test]
+    fn limits_frame_depth() {
+        let html =
+            "<iframe src=\"data:text/html,<iframe src='data:text/html,hi'></iframe>\"></iframe>";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+        let url: Url = Url::parse("http://localhost").unwrap();
+
+        let mut options = MonolithOptions::default();
+        options.frames_as_srcdoc = true;
+        options.max_frame_depth = Some(0);
+        options.silent = true;
+
+        let mut session: Session = Session::new(None, None, options);
+
+        session.enter_frame(&url);
+        html::walk(&mut session, &url, &dom.document);
+        session.exit_frame();
+
+        let mut buf: Vec<u8> = Vec::new();
+        serialize(
+            &mut buf,
+            &SerializableHandle::from(dom.document.clone()),
+            SerializeOpts::default(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            buf.iter().map(|&c| c as char).collect::<String>(),
+            "<html><head></head><body><iframe></iframe></body></html>"
+        );
+    }
+
+    #[test]
+    fn skips_frames_embedding_themselves() {
+        let html = "<iframe src=\"/\"></iframe>";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+        let url: Url = Url::parse("http://localhost/").unwrap();
+
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+
+        let mut session: Session = Session::new(None, None, options);
+
+        session.enter_frame(&url);
+        html::walk(&mut session, &url, &dom.document);
+        session.exit_frame();
+
+        let mut buf: Vec<u8> = Vec::new();
+        serialize(
+            &mut buf,
+            &SerializableHandle::from(dom.document.clone()),
+            SerializeOpts::default(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            buf.iter().map(|&c| c as char).collect::<String>(),
+            "<html><head></head><body><iframe src=\"http://localhost/\"></iframe></body></html>"
+        );
+    }
+
+    #[test]
+    fn inline_stylesheets() {
+        let html = "<link rel=\"stylesheet\" media=\"screen\" title=\"Main\" href=\"data:text/css,@import url(data:text/css,p{margin:0}) layer(base);body{color:red}\">\
+                    <link rel=\"alternate stylesheet\" title=\"Alt\" href=\"data:text/css,body{color:blue}\">";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+        let url: Url = Url::parse("http://localhost").unwrap();
+
+        let mut options = MonolithOptions::default();
+        options.inline_stylesheets = true;
+        options.silent = true;
+
+        let mut session: Session = Session::new(None, None, options);
+
+        html::walk(&mut session, &url, &dom.document);
+
+        let mut buf: Vec<u8> = Vec::new();
+        serialize(
+            &mut buf,
+            &SerializableHandle::from(dom.document.clone()),
+            SerializeOpts::default(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            buf.iter().map(|&c| c as char).collect::<String>(),
+            "<html><head>\
+                <style media=\"screen\" title=\"Main\">@layer base {\np{margin:0}\n}body{color:red}</style>\
+                <link rel=\"alternate stylesheet\" title=\"Alt\" href=\"data:text/css;base64,Ym9keXtjb2xvcjpibHVlfQ==\">\
+            </head><body></body></html>"
+        );
+    }
+
+    #[test]
+    fn no_unused_css() {
+        let html = "<style>@keyframes spin{to{rotate:1turn}}@keyframes fade{to{opacity:0}}\
+                    .icon{animation:spin 1s}.modal{animation:fade 1s}</style>\
+                    <link rel=\"stylesheet\" href=\"data:text/css,h1{color:red}p{color:blue}\">\
+                    <p class=\"icon\">Hi</p>";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+        let url: Url = Url::parse("http://localhost").unwrap();
+
+        let mut options = MonolithOptions::default();
+        options.no_unused_css = true;
+        options.silent = true;
+
+        let mut session: Session = Session::new(None, None, options);
+
+        html::walk(&mut session, &url, &dom.document);
+
+        let mut buf: Vec<u8> = Vec::new();
+        serialize(
+            &mut buf,
+            &SerializableHandle::from(dom.document.clone()),
+            SerializeOpts::default(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            buf.iter().map(|&c| c as char).collect::<String>(),
+            "<html><head>\
+                <style>@keyframes spin{to{rotate:1turn}}.icon{animation:spin 1s}</style>\
+                <link rel=\"stylesheet\" href=\"data:text/css;base64,cHtjb2xvcjpibHVlfQ==\">\
+            </head><body><p class=\"icon\">Hi</p></body></html>"
+        );
+    }
+
+    #[test]
+    fn hides_elements_from_filter_lists() {
+        let html = "<div class=\"ad\"><img src=\"ad.png\"></div>\
+                    <p id=\"promo\">Promo</p><p class=\"ad-free\">Text</p>\
+                    <ul><li>One</li><li class=\"sponsored\">Two</li></ul>";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+        let url: Url = Url::parse("http://localhost").unwrap();
+
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+
+        let mut filter_list: FilterList = FilterList::default();
+        filter_list.add_filters("##.ad\nlocalhost###promo\nexample.com##.ad-free\n##ul > li + .sponsored\n##li:first-child");
+        let mut session: Session = Session::new(None, None, options);
+        session.filter_list = Some(filter_list);
+
+        html::walk(&mut session, &url, &dom.document);
+
+        let mut buf: Vec<u8> = Vec::new();
+        serialize(
+            &mut buf,
+            &SerializableHandle::from(dom.document.clone()),
+            SerializeOpts::default(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            buf.iter().map(|&c| c as char).collect::<String>(),
+            "<html><head></head><body>\
+                <p class=\"ad-free\">Text</p><ul><li>One</li></ul>\
+            </body></html>"
+        );
+    }
+
+    #[test]
+    fn removes_tracking() {
+        let html = "<script src=\"https://www.google-analytics.com/analytics.js\"></script>\
+                    <script>gtag('config', 'G-1');</script>\
+                    <a href=\"https://site.com/?id=1&amp;utm_source=feed\" ping=\"/ping\">Link</a>\
+                    <img src=\"https://site.com/hit.gif\" width=\"1\" height=\"1\">";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+        let url: Url = Url::parse("http://localhost").unwrap();
+
+        let mut options = MonolithOptions::default();
+        options.no_tracking = true;
+        options.silent = true;
+
+        let mut session: Session = Session::new(None, None, options);
+
+        html::walk(&mut session, &url, &dom.document);
+
+        let mut buf: Vec<u8> = Vec::new();
+        serialize(
+            &mut buf,
+            &SerializableHandle::from(dom.document.clone()),
+            SerializeOpts::default(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            buf.iter().map(|&c| c as char).collect::<String>(),
+            "<html><head></head><body>\
+                <a href=\"https://site.com/?id=1\">Link</a>\
+            </body></html>"
+        );
+    }
+
+    #[test]
+    fn removes_lazy_loaded_tracking_pixels() {
+        let html =
+            "<img width=\"1\" height=\"1\" src=\"data:image/gif;base64,R0lGODlhAQABAAAAACw=\" \
+                    data-src=\"https://www.google-analytics.com/collect?v=1\">";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+        let url: Url = Url::parse("http://localhost").unwrap();
+
+        let mut options = MonolithOptions::default();
+        options.no_tracking = true;
+        options.silent = true;
+
+        let mut session: Session = Session::new(None, None, options);
+
+        html::walk(&mut session, &url, &dom.document);
+
+        let mut buf: Vec<u8> = Vec::new();
+        serialize(
+            &mut buf,
+            &SerializableHandle::from(dom.document.clone()),
+            SerializeOpts::default(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            buf.iter().map(|&c| c as char).collect::<String>(),
+            "<html><head></head><body></body></html>"
+        );
+    }
+
+    #[test]
+    fn regenerates_integrity_of_stylesheets_without_unused_css() {
+        let css = "p{color:red}.unused{color:blue}";
+        let html = format!(
+            "<link rel=\"stylesheet\" integrity=\"sha256-{}\" href=\"data:text/css,{}\"><p>Hi</p>",
+            html::compute_integrity(css.as_bytes(), "sha256").unwrap(),
+            css
+        );
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+        let url: Url = Url::parse("http://localhost").unwrap();
+
+        let mut options = MonolithOptions::default();
+        options.keep_integrity = true;
+        options.no_unused_css = true;
+        options.silent = true;
+
+        let mut session: Session = Session::new(None, None, options);
+
+        html::walk(&mut session, &url, &dom.document);
+
+        let link_node = &html::find_nodes(&dom.document, vec!["html", "head", "link"])[0];
+        let href_url: Url = Url::parse(&html::get_node_attr(link_node, "href").unwrap()).unwrap();
+        let (_, _, data) = parse_data_url(&href_url);
+
+        assert_eq!(String::from_utf8_lossy(&data), "p{color:red}");
+        assert!(html::check_integrity(
+            &data,
+            &html::get_node_attr(link_node, "integrity").unwrap()
+        ));
+    }
+
+    #[test]
+    fn sanitizes_frames() {
+        let html = "<iframe srcdoc=\"<p onclick=&quot;alert(1)&quot;>Hi</p><object data=x></object>\"></iframe>";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+        let url: Url = Url::parse("http://localhost").unwrap();
+
+        let mut options = MonolithOptions::default();
+        options.no_js = true;
+        options.sanitize = true;
+        options.silent = true;
+
+        let mut session: Session = Session::new(None, None, options);
+
+        html::walk(&mut session, &url, &dom.document);
+
+        let mut buf: Vec<u8> = Vec::new();
+        serialize(
+            &mut buf,
+            &SerializableHandle::from(dom.document.clone()),
+            SerializeOpts::default(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            buf.iter().map(|&c| c as char).collect::<String>(),
+            "<html><head></head><body>\
+                <iframe srcdoc=\"<html><head></head><body><p>Hi</p></body></html>\" sandbox=\"\"></iframe>\
+            </body></html>"
+        );
+    }
 }
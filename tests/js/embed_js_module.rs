@@ -0,0 +1,73 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use std::fs;
+    use std::path::Path;
+
+    use monolith::core::MonolithOptions;
+    use monolith::js;
+    use monolith::session::Session;
+    use monolith::url::{parse_data_url, Url};
+
+    #[test]
+    fn static_imports() {
+        let path: &Path = Path::new("tests/_data_/js_modules/main.js");
+        let module_url: Url = Url::from_file_path(fs::canonicalize(path).unwrap()).unwrap();
+        let source: String = fs::read_to_string(path).unwrap();
+
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+        let mut session: Session = Session::new(None, None, options);
+
+        let result: String = js::embed_js_module(&mut session, &module_url, &source);
+
+        assert!(!result.contains("./lib/greet.js"));
+        assert!(!result.contains("./config.json"));
+        assert!(result.contains("from \"data:text/javascript;charset=utf-8;base64,"));
+        assert!(result.contains("from 'data:application/json;charset=utf-8;base64,"));
+        assert!(result.ends_with("greet(config.name);\n"));
+
+        // Circular imports keep pointing to the original module
+        let greet_data_url: &str = result
+            .split('"')
+            .find(|s| s.starts_with("data:text/javascript"))
+            .unwrap();
+        let (_, _, greet_source) = parse_data_url(&Url::parse(greet_data_url).unwrap());
+        assert!(String::from_utf8_lossy(&greet_source)
+            .starts_with(&format!("import \"{}\";", module_url)));
+    }
+
+    #[test]
+    fn dynamic_imports_are_left_alone() {
+        let module_url: Url = Url::parse("https://example.com/main.js").unwrap();
+
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+        let mut session: Session = Session::new(None, None, options);
+
+        assert_eq!(
+            js::embed_js_module(&mut session, &module_url, "import('./lazy.js');"),
+            "import('./lazy.js');"
+        );
+    }
+
+    #[test]
+    fn unmapped_bare_specifiers_are_left_alone() {
+        let module_url: Url = Url::parse("https://example.com/main.js").unwrap();
+
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+        let mut session: Session = Session::new(None, None, options);
+
+        assert_eq!(
+            js::embed_js_module(&mut session, &module_url, "import { h } from \"preact\";"),
+            "import { h } from \"preact\";"
+        );
+    }
+}
@@ -1 +1,4 @@
 mod attr_is_event_handler;
+mod embed_js_module;
+mod parse_import_map;
+mod resolve_module_specifier;
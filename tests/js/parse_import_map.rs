@@ -0,0 +1,73 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::js;
+    use monolith::url::Url;
+
+    #[test]
+    fn imports() {
+        let base_url: Url = Url::parse("https://example.com/app/index.html").unwrap();
+        let import_map = js::parse_import_map(
+            r#"{"imports": {"vue": "/js/vue.js", "lodash/": "https://cdn.example.com/lodash/"}}"#,
+            &base_url,
+        );
+
+        assert_eq!(import_map.imports.len(), 2);
+        assert_eq!(import_map.imports[0].0, "lodash/");
+        assert_eq!(
+            import_map.imports[0].1.as_str(),
+            "https://cdn.example.com/lodash/"
+        );
+        assert_eq!(import_map.imports[1].0, "vue");
+        assert_eq!(
+            import_map.imports[1].1.as_str(),
+            "https://example.com/js/vue.js"
+        );
+        assert!(import_map.scopes.is_empty());
+    }
+
+    #[test]
+    fn scopes() {
+        let base_url: Url = Url::parse("https://example.com/").unwrap();
+        let import_map = js::parse_import_map(
+            r#"{"scopes": {"/legacy/": {"vue": "/js/vue2.js"}}}"#,
+            &base_url,
+        );
+
+        assert!(import_map.imports.is_empty());
+        assert_eq!(import_map.scopes.len(), 1);
+        assert_eq!(import_map.scopes[0].0, "https://example.com/legacy/");
+        assert_eq!(
+            import_map.scopes[0].1[0].1.as_str(),
+            "https://example.com/js/vue2.js"
+        );
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::js;
+    use monolith::url::Url;
+
+    #[test]
+    fn invalid_json() {
+        let base_url: Url = Url::parse("https://example.com/").unwrap();
+        let import_map = js::parse_import_map("{imports:", &base_url);
+
+        assert!(import_map.imports.is_empty());
+        assert!(import_map.scopes.is_empty());
+    }
+}
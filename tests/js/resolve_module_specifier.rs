@@ -0,0 +1,89 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::js;
+    use monolith::url::Url;
+
+    #[test]
+    fn relative_specifier() {
+        let base_url: Url = Url::parse("https://example.com/js/main.js").unwrap();
+
+        assert_eq!(
+            js::resolve_module_specifier(None, &base_url, "../lib/util.js")
+                .unwrap()
+                .as_str(),
+            "https://example.com/lib/util.js"
+        );
+    }
+
+    #[test]
+    fn bare_specifier_via_import_map() {
+        let base_url: Url = Url::parse("https://example.com/js/main.js").unwrap();
+        let import_map = js::parse_import_map(
+            r#"{"imports": {"vue": "/vendor/vue.js", "lodash/": "/vendor/lodash/"}}"#,
+            &base_url,
+        );
+
+        assert_eq!(
+            js::resolve_module_specifier(Some(&import_map), &base_url, "vue")
+                .unwrap()
+                .as_str(),
+            "https://example.com/vendor/vue.js"
+        );
+        assert_eq!(
+            js::resolve_module_specifier(Some(&import_map), &base_url, "lodash/merge.js")
+                .unwrap()
+                .as_str(),
+            "https://example.com/vendor/lodash/merge.js"
+        );
+    }
+
+    #[test]
+    fn scoped_specifier() {
+        let base_url: Url = Url::parse("https://example.com/").unwrap();
+        let import_map = js::parse_import_map(
+            r#"{"imports": {"vue": "/vue3.js"}, "scopes": {"/legacy/": {"vue": "/vue2.js"}}}"#,
+            &base_url,
+        );
+        let legacy_module_url: Url = Url::parse("https://example.com/legacy/app.js").unwrap();
+
+        assert_eq!(
+            js::resolve_module_specifier(Some(&import_map), &legacy_module_url, "vue")
+                .unwrap()
+                .as_str(),
+            "https://example.com/vue2.js"
+        );
+        assert_eq!(
+            js::resolve_module_specifier(Some(&import_map), &base_url, "vue")
+                .unwrap()
+                .as_str(),
+            "https://example.com/vue3.js"
+        );
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::js;
+    use monolith::url::Url;
+
+    #[test]
+    fn unmapped_bare_specifier() {
+        let base_url: Url = Url::parse("https://example.com/js/main.js").unwrap();
+
+        assert_eq!(js::resolve_module_specifier(None, &base_url, "vue"), None);
+    }
+}
@@ -2,7 +2,16 @@ mod cli;
 mod cookies;
 mod core;
 mod css;
+mod diff;
+mod filters;
+mod font;
 mod html;
 mod js;
+mod reader;
+mod sanitize;
+mod serve;
 mod session;
+mod svg;
+mod unpack;
 mod url;
+mod verify;
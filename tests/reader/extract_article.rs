@@ -0,0 +1,93 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use html5ever::serialize::{serialize, SerializeOpts};
+    use markup5ever_rcdom::SerializableHandle;
+
+    use monolith::html;
+    use monolith::reader;
+
+    fn extract(html: &str) -> String {
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+        let article_dom = reader::extract_article(&dom.document);
+        let mut buf: Vec<u8> = Vec::new();
+        let serializable: SerializableHandle = article_dom.document.into();
+        serialize(&mut buf, &serializable, SerializeOpts::default()).unwrap();
+
+        String::from_utf8_lossy(&buf).to_string()
+    }
+
+    #[test]
+    fn main_content() {
+        let result = extract(
+            "<html><head><title>Article</title><link rel=\"stylesheet\" href=\"style.css\"></head><body>\
+<nav><a href=\"/\">Home</a></nav>\
+<div class=\"sidebar\"><img src=\"sidebar.png\"><p>Sidebar text which is long enough to be scored.</p></div>\
+<div class=\"content\">\
+<h1>Heading</h1>\
+<p>First paragraph, which is long enough, and has a couple of commas in it.</p>\
+<img src=\"photo.png\" style=\"float: left\">\
+<p>Second paragraph, which is also long enough to be considered as content.</p>\
+</div>\
+<div id=\"comments\"><p>A comment, which could be mistaken for content, if not for its ID.</p></div>\
+<footer>Footer</footer>\
+<script>alert(1)</script>\
+</body></html>",
+        );
+
+        assert_eq!(
+            result,
+            "<html><head><meta charset=\"utf-8\"><title>Article</title><style>body { max-width: 40em; margin: 0 auto; padding: 1em; \
+font: 1.125em/1.6 Georgia, serif; color: #222; background: #fff; } img, video, figure { max-width: 100%; height: auto; } \
+pre { overflow: auto; }</style></head><body><article>\
+<div class=\"content\">\
+<h1>Heading</h1>\
+<p>First paragraph, which is long enough, and has a couple of commas in it.</p>\
+<img src=\"photo.png\">\
+<p>Second paragraph, which is also long enough to be considered as content.</p>\
+</div>\
+</article></body></html>"
+        );
+    }
+
+    #[test]
+    fn adds_title_heading() {
+        let result = extract(
+            "<html><head><title>Title</title></head><body>\
+<div><p>Paragraph which is long enough, to be treated as content.</p></div>\
+</body></html>",
+        );
+
+        assert!(result.contains(
+            "<article><h1>Title</h1><div><p>Paragraph which is long enough, to be treated as content.</p></div></article>"
+        ));
+    }
+
+    #[test]
+    fn no_paragraphs() {
+        let result =
+            extract("<html><head></head><body><span>Short</span><!-- comment --></body></html>");
+
+        assert!(result.contains("<article><span>Short</span></article>"));
+    }
+
+    #[test]
+    fn link_lists_get_dropped() {
+        let result = extract(
+            "<html><head></head><body><div>\
+<p>Paragraph which is long enough, to be treated as content.</p>\
+<div><a href=\"/1\">First link</a> <a href=\"/2\">Second link</a></div>\
+</div></body></html>",
+        );
+
+        assert!(result.contains(
+            "<article><div><p>Paragraph which is long enough, to be treated as content.</p></div></article>"
+        ));
+    }
+}
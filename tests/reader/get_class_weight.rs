@@ -0,0 +1,58 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use markup5ever_rcdom::{Handle, RcDom};
+
+    use monolith::html;
+    use monolith::reader;
+
+    fn first_div(dom: &RcDom) -> Handle {
+        html::find_nodes(&dom.document, vec!["html", "body", "div"])[0].clone()
+    }
+
+    #[test]
+    fn neutral() {
+        let html = "<div class=\"wrapper\" id=\"x\"></div>";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+
+        assert_eq!(reader::get_class_weight(&first_div(&dom)), 0.0);
+    }
+
+    #[test]
+    fn positive() {
+        let html = "<div class=\"entry-content\" id=\"main\"></div>";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+
+        assert_eq!(reader::get_class_weight(&first_div(&dom)), 50.0);
+    }
+
+    #[test]
+    fn negative() {
+        let html = "<div class=\"left sidebar-widget\" id=\"ad\"></div>";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+
+        assert_eq!(reader::get_class_weight(&first_div(&dom)), -50.0);
+    }
+
+    #[test]
+    fn short_words_match_entirely() {
+        let html = "<div class=\"address loads\"></div>";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+
+        assert_eq!(reader::get_class_weight(&first_div(&dom)), 0.0);
+    }
+
+    #[test]
+    fn mixed() {
+        let html = "<div class=\"post-header\"></div>";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+
+        assert_eq!(reader::get_class_weight(&first_div(&dom)), 0.0);
+    }
+}
@@ -0,0 +1,50 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use markup5ever_rcdom::{Handle, RcDom};
+
+    use monolith::html;
+    use monolith::reader;
+
+    fn first_div(dom: &RcDom) -> Handle {
+        html::find_nodes(&dom.document, vec!["html", "body", "div"])[0].clone()
+    }
+
+    #[test]
+    fn no_links() {
+        let html = "<div>Some text</div>";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+
+        assert_eq!(reader::get_link_density(&first_div(&dom)), 0.0);
+    }
+
+    #[test]
+    fn half_links() {
+        let html = "<div>abcd <a href=\"#\">efgh</a></div>";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+
+        assert_eq!(reader::get_link_density(&first_div(&dom)), 4.0 / 9.0);
+    }
+
+    #[test]
+    fn only_links() {
+        let html = "<div><a href=\"/1\">one</a><a href=\"/2\">two</a></div>";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+
+        assert_eq!(reader::get_link_density(&first_div(&dom)), 1.0);
+    }
+
+    #[test]
+    fn empty() {
+        let html = "<div></div>";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+
+        assert_eq!(reader::get_link_density(&first_div(&dom)), 0.0);
+    }
+}
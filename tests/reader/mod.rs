@@ -0,0 +1,3 @@
+mod extract_article;
+mod get_class_weight;
+mod get_link_density;
@@ -0,0 +1,2 @@
+mod is_allowed_url;
+mod sanitize_document;
@@ -0,0 +1,165 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use html5ever::serialize::{serialize, SerializeOpts};
+    use markup5ever_rcdom::SerializableHandle;
+
+    use monolith::core::MonolithOptions;
+    use monolith::html;
+    use monolith::sanitize;
+    use monolith::session::Session;
+    use monolith::url::Url;
+
+    fn sanitize(html: &str) -> String {
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+        sanitize::sanitize_document(&dom.document);
+
+        let mut buf: Vec<u8> = Vec::new();
+        let serializable: SerializableHandle = dom.document.into();
+        serialize(&mut buf, &serializable, SerializeOpts::default()).unwrap();
+
+        String::from_utf8_lossy(&buf).to_string()
+    }
+
+    #[test]
+    fn keeps_safe_markup() {
+        let html = "<html><head><title>Title</title></head><body>\
+<p class=\"intro\" data-id=\"1\" aria-label=\"Intro\">Text <a href=\"https://site.com/\" target=\"_blank\">link</a></p>\
+<img src=\"data:image/png;base64,AAAA\" alt=\"Image\" width=\"10\">\
+</body></html>";
+
+        assert_eq!(sanitize(html), html);
+    }
+
+    #[test]
+    fn removes_scripts_and_event_handlers() {
+        assert_eq!(
+            sanitize(
+                "<body onload=\"alert(1)\"><script>alert(2)</script>\
+<img src=\"x.png\" onerror=\"alert(3)\"></body>"
+            ),
+            "<html><head></head><body><img src=\"x.png\"></body></html>"
+        );
+    }
+
+    #[test]
+    fn removes_javascript_urls() {
+        assert_eq!(
+            sanitize(
+                "<a href=\" java&#9;script:alert(1)\">A</a>\
+<iframe src=\"javascript:alert(2)\"></iframe>\
+<img srcset=\"a.png 1x, javascript:alert(3) 2x\">"
+            ),
+            "<html><head></head><body><a>A</a><iframe sandbox=\"\"></iframe><img></body></html>"
+        );
+    }
+
+    #[test]
+    fn removes_dangerous_meta_and_base() {
+        assert_eq!(
+            sanitize(
+                "<head><meta charset=\"utf-8\"><meta http-equiv=\"Set-Cookie\" content=\"a=b\">\
+<meta http-equiv=\"refresh\" content=\"0; url=https://evil.com/\">\
+<meta http-equiv=\"Content-Security-Policy\" content=\"default-src 'none'\">\
+<base href=\"https://evil.com/\" target=\"_top\"></head>"
+            ),
+            "<html><head><meta charset=\"utf-8\">\
+<meta http-equiv=\"Content-Security-Policy\" content=\"default-src 'none'\">\
+</head><body></body></html>"
+        );
+    }
+
+    #[test]
+    fn unwraps_forms_and_unknown_elements() {
+        assert_eq!(
+            sanitize(
+                "<form action=\"https://evil.com/\"><input name=\"q\">\
+<button formaction=\"javascript:alert(1)\">Go</button></form>\
+<custom-widget onclick=\"alert(2)\"><b>Bold</b></custom-widget><!-- comment -->"
+            ),
+            "<html><head></head><body><input name=\"q\"><button>Go</button><b>Bold</b></body></html>"
+        );
+    }
+
+    #[test]
+    fn removes_plugins() {
+        assert_eq!(
+            sanitize(
+                "<object data=\"movie.swf\"><param name=\"a\" value=\"b\">Fallback</object>\
+<embed src=\"movie.swf\"><template><script>alert(1)</script></template>"
+            ),
+            "<html><head></head><body></body></html>"
+        );
+    }
+
+    #[test]
+    fn sanitizes_svg() {
+        assert_eq!(
+            sanitize(
+                "<svg viewBox=\"0 0 10 10\" onload=\"alert(1)\">\
+<a href=\"javascript:alert(2)\"><animate attributeName=\"href\" to=\"javascript:alert(3)\"/>\
+<circle cx=\"5\" cy=\"5\" r=\"5\" fill=\"red\"/></a>\
+<foreignObject><img src=\"x.png\"></foreignObject><set attributeName=\"onmouseover\" to=\"alert(4)\"/>\
+<use xlink:href=\"#icon\"/></svg>"
+            ),
+            "<html><head></head><body><svg viewBox=\"0 0 10 10\">\
+<a><circle cx=\"5\" cy=\"5\" r=\"5\" fill=\"red\"></circle></a>\
+<use xlink:href=\"#icon\"></use></svg></body></html>"
+        );
+    }
+
+    #[test]
+    fn removes_mathml() {
+        assert_eq!(
+            sanitize(
+                "<math><maction actiontype=\"statusline\" href=\"javascript:alert(1)\">X</maction></math>"
+            ),
+            "<html><head></head><body></body></html>"
+        );
+    }
+
+    #[test]
+    fn removes_noscript() {
+        let html = "<noscript><p title=\"&lt;/noscript&gt;&lt;img src=x onerror=alert(1)&gt;\">\
+Fallback</p></noscript><p>Text</p>";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+        let url: Url = Url::parse("http://localhost").unwrap();
+
+        let mut options = MonolithOptions::default();
+        options.no_js = true;
+        options.sanitize = true;
+        options.silent = true;
+
+        let mut session: Session = Session::new(None, None, options);
+
+        html::walk(&mut session, &url, &dom.document);
+
+        let mut buf: Vec<u8> = Vec::new();
+        let serializable: SerializableHandle = dom.document.into();
+        serialize(&mut buf, &serializable, SerializeOpts::default()).unwrap();
+
+        assert_eq!(
+            String::from_utf8_lossy(&buf),
+            "<html><head></head><body><p>Text</p></body></html>"
+        );
+    }
+
+    #[test]
+    fn keeps_only_stylesheet_and_icon_links() {
+        assert_eq!(
+            sanitize(
+                "<head><link rel=\"stylesheet\" href=\"data:text/css,p{}\">\
+<link rel=\"icon\" href=\"data:image/png;base64,AAAA\"><link rel=\"prefetch\" href=\"https://evil.com/\">\
+<link rel=\"stylesheet\" href=\"javascript:alert(1)\"></head>"
+            ),
+            "<html><head><link rel=\"stylesheet\" href=\"data:text/css,p{}\">\
+<link rel=\"icon\" href=\"data:image/png;base64,AAAA\"><link rel=\"stylesheet\"></head><body></body></html>"
+        );
+    }
+}
@@ -0,0 +1,49 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::serve::{self, SavedDocument, SavedDocumentFormat};
+
+    #[test]
+    fn title_and_metadata() {
+        let documents = vec![
+            SavedDocument {
+                file_name: "a & b.html".to_string(),
+                format: SavedDocumentFormat::HTML,
+                title: Some("<Title>".to_string()),
+                source: Some("https://example.com/".to_string()),
+                saved_at: Some("2024-01-01T00:00:00Z".to_string()),
+            },
+            SavedDocument {
+                file_name: "untitled.mhtml".to_string(),
+                format: SavedDocumentFormat::MHTML,
+                title: None,
+                source: Some("local source".to_string()),
+                saved_at: None,
+            },
+        ];
+        let index_page: String = serve::create_index_page(&documents);
+
+        assert!(index_page.contains(
+            "<tr><td><a href=\"/a%20%26%20b.html\">&lt;Title&gt;</a></td>\
+<td><a href=\"https://example.com/\">https://example.com/</a></td>\
+<td>2024-01-01T00:00:00Z</td><td>a &amp; b.html</td></tr>"
+        ));
+        assert!(index_page.contains(
+            "<tr><td><a href=\"/untitled.mhtml\">untitled.mhtml</a></td>\
+<td>local source</td><td></td><td>untitled.mhtml</td></tr>"
+        ));
+    }
+
+    #[test]
+    fn empty() {
+        let index_page: String = serve::create_index_page(&[]);
+
+        assert!(index_page.contains("<tbody>\n</tbody>"));
+    }
+}
@@ -0,0 +1,65 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::serve::{self, SavedDocumentFormat};
+
+    #[test]
+    fn html() {
+        assert_eq!(
+            serve::detect_saved_document_format("page.html"),
+            Some(SavedDocumentFormat::HTML)
+        );
+        assert_eq!(
+            serve::detect_saved_document_format("page.HTM"),
+            Some(SavedDocumentFormat::HTML)
+        );
+    }
+
+    #[test]
+    fn mhtml() {
+        assert_eq!(
+            serve::detect_saved_document_format("page.mhtml"),
+            Some(SavedDocumentFormat::MHTML)
+        );
+        assert_eq!(
+            serve::detect_saved_document_format("page.mht"),
+            Some(SavedDocumentFormat::MHTML)
+        );
+    }
+
+    #[test]
+    fn warc() {
+        assert_eq!(
+            serve::detect_saved_document_format("crawl.warc"),
+            Some(SavedDocumentFormat::WARC)
+        );
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::serve;
+
+    #[test]
+    fn other_extension() {
+        assert_eq!(serve::detect_saved_document_format("image.png"), None);
+    }
+
+    #[test]
+    fn no_extension() {
+        assert_eq!(serve::detect_saved_document_format("html"), None);
+    }
+}
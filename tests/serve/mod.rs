@@ -0,0 +1,3 @@
+mod create_index_page;
+mod detect_saved_document_format;
+mod read_saved_document;
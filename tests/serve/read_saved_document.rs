@@ -0,0 +1,158 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::serve::{self, SavedDocumentFormat};
+
+    #[test]
+    fn html() {
+        let data = "<!-- Saved from https://example.com/ at 2024-01-01T00:00:00Z using monolith v2.11.0 -->\n\
+<html><head><title> Example </title></head><body></body></html>";
+        let document = serve::read_saved_document("example.html", data.as_bytes()).unwrap();
+
+        assert_eq!(document.format, SavedDocumentFormat::HTML);
+        assert_eq!(document.title, Some("Example".to_string()));
+        assert_eq!(document.source, Some("https://example.com/".to_string()));
+        assert_eq!(document.saved_at, Some("2024-01-01T00:00:00Z".to_string()));
+    }
+
+    #[test]
+    fn html_without_metadata() {
+        let data = "<html><head></head><body></body></html>";
+        let document = serve::read_saved_document("example.html", data.as_bytes()).unwrap();
+
+        assert_eq!(document.title, None);
+        assert_eq!(document.source, None);
+        assert_eq!(document.saved_at, None);
+    }
+
+    #[test]
+    fn mhtml() {
+        let data = "MIME-Version: 1.0\r\n\
+Content-Type: multipart/related; boundary=\"----=_NextPart_000_0000\"\r\n\
+\r\n\
+------=_NextPart_000_0000\r\n\
+Content-Type: text/html; charset=\"utf-8\"\r\n\
+Content-Location: http://example.com/\r\n\
+\r\n\
+<!-- Saved from local source at 2024-01-01T00:00:00Z using monolith v2.11.0 -->\n\
+<html><head><title>Example</title></head><body></body></html>\r\n\
+------=_NextPart_000_0000--\r\n";
+        let document = serve::read_saved_document("example.mhtml", data.as_bytes()).unwrap();
+
+        assert_eq!(document.format, SavedDocumentFormat::MHTML);
+        assert_eq!(document.title, Some("Example".to_string()));
+        assert_eq!(document.source, Some("local source".to_string()));
+        assert_eq!(
+            serve::extract_html_document(SavedDocumentFormat::MHTML, data.as_bytes()).unwrap(),
+            b"<!-- Saved from local source at 2024-01-01T00:00:00Z using monolith v2.11.0 -->\n\
+<html><head><title>Example</title></head><body></body></html>"
+        );
+    }
+
+    #[test]
+    fn mhtml_quoted_printable() {
+        let data = "MIME-Version: 1.0\r\n\
+Content-Type: multipart/related;\r\n\
+\tboundary=\"boundary\"\r\n\
+\r\n\
+--boundary\r\n\
+Content-Type: text/html\r\n\
+Content-Transfer-Encoding: quoted-printable\r\n\
+\r\n\
+<html><head><title>Caf=C3=A9 =\r\n\
+menu</title></head></html>\r\n\
+--boundary--\r\n";
+        let document = serve::read_saved_document("cafe.mht", data.as_bytes()).unwrap();
+
+        assert_eq!(document.title, Some("Café menu".to_string()));
+    }
+
+    #[test]
+    fn warc() {
+        let http_response = "HTTP/1.1 200 OK\r\n\
+Content-Type: text/html\r\n\
+Transfer-Encoding: chunked\r\n\
+\r\n\
+18\r\n\
+<title>Example</title>Hi\r\n\
+0\r\n\
+\r\n";
+        let data = format!(
+            "WARC/1.0\r\n\
+WARC-Type: warcinfo\r\n\
+Content-Length: 0\r\n\
+\r\n\
+\r\n\
+\r\n\
+WARC/1.0\r\n\
+WARC-Type: response\r\n\
+WARC-Target-URI: https://example.com/\r\n\
+WARC-Date: 2024-01-01T00:00:00Z\r\n\
+Content-Length: {}\r\n\
+\r\n\
+{}\r\n\
+\r\n",
+            http_response.len(),
+            http_response
+        );
+        let document = serve::read_saved_document("crawl.warc", data.as_bytes()).unwrap();
+
+        assert_eq!(document.format, SavedDocumentFormat::WARC);
+        assert_eq!(document.title, Some("Example".to_string()));
+        assert_eq!(document.source, Some("https://example.com/".to_string()));
+        assert_eq!(document.saved_at, Some("2024-01-01T00:00:00Z".to_string()));
+        assert_eq!(
+            serve::extract_html_document(SavedDocumentFormat::WARC, data.as_bytes()).unwrap(),
+            b"<title>Example</title>Hi"
+        );
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::serve;
+
+    #[test]
+    fn unsupported_file() {
+        assert!(serve::read_saved_document("image.png", b"").is_none());
+    }
+
+    #[test]
+    fn mhtml_without_html_part() {
+        let data = "MIME-Version: 1.0\r\n\
+Content-Type: multipart/related; boundary=\"boundary\"\r\n\
+\r\n\
+--boundary\r\n\
+Content-Type: image/png\r\n\
+\r\n\
+\r\n\
+--boundary--\r\n";
+
+        assert!(serve::read_saved_document("example.mhtml", data.as_bytes()).is_none());
+    }
+
+    #[test]
+    fn warc_without_responses() {
+        let data = "WARC/1.0\r\n\
+WARC-Type: warcinfo\r\n\
+Content-Length: 0\r\n\
+\r\n\
+\r\n\
+\r\n";
+
+        assert!(serve::read_saved_document("crawl.warc", data.as_bytes()).is_none());
+    }
+}
@@ -0,0 +1,89 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use reqwest::Url;
+
+    use monolith::core::MonolithOptions;
+    use monolith::session::Session;
+
+    #[test]
+    fn different_document() {
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+
+        let mut session: Session = Session::new(None, None, options);
+
+        session.enter_frame(&Url::parse("https://localhost/index.html").unwrap());
+        assert!(session.can_enter_frame(&Url::parse("https://localhost/frame.html").unwrap()));
+    }
+
+    #[test]
+    fn srcdoc_within_srcdoc() {
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+
+        let mut session: Session = Session::new(None, None, options);
+
+        session.enter_frame(&Url::parse("about:srcdoc").unwrap());
+        assert!(session.can_enter_frame(&Url::parse("about:srcdoc").unwrap()));
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use reqwest::Url;
+
+    use monolith::core::MonolithOptions;
+    use monolith::session::Session;
+
+    #[test]
+    fn same_document() {
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+
+        let mut session: Session = Session::new(None, None, options);
+
+        session.enter_frame(&Url::parse("https://localhost/index.html").unwrap());
+        assert!(!session.can_enter_frame(&Url::parse("https://localhost/index.html#top").unwrap()));
+    }
+
+    #[test]
+    fn same_document_with_different_query_string() {
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+
+        let mut session: Session = Session::new(None, None, options);
+
+        session.enter_frame(&Url::parse("https://localhost/index.html?page=1").unwrap());
+        session.enter_frame(&Url::parse("https://localhost/other.html").unwrap());
+        assert!(
+            !session.can_enter_frame(&Url::parse("https://localhost/index.html?page=2").unwrap())
+        );
+    }
+
+    #[test]
+    fn too_deep() {
+        let mut options = MonolithOptions::default();
+        options.max_frame_depth = Some(1);
+        options.silent = true;
+
+        let mut session: Session = Session::new(None, None, options);
+
+        session.enter_frame(&Url::parse("https://localhost/1.html").unwrap());
+        session.enter_frame(&Url::parse("https://localhost/2.html").unwrap());
+        assert!(!session.can_enter_frame(&Url::parse("https://localhost/3.html").unwrap()));
+    }
+}
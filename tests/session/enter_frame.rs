@@ -0,0 +1,46 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use reqwest::Url;
+
+    use monolith::core::MonolithOptions;
+    use monolith::js;
+    use monolith::session::Session;
+
+    #[test]
+    fn scopes_import_map_to_frame() {
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+
+        let mut session: Session = Session::new(None, None, options);
+        let document_url: Url = Url::parse("https://example.com/index.html").unwrap();
+
+        session.enter_frame(&document_url);
+        session.import_map = Some(js::parse_import_map(
+            r#"{"imports": {"vue": "/js/vue.js"}}"#,
+            &document_url,
+        ));
+
+        // Frames don't inherit import maps of their parent documents
+        session.enter_frame(&Url::parse("about:srcdoc").unwrap());
+        assert!(session.import_map.is_none());
+        session.import_map = Some(js::parse_import_map(
+            r#"{"imports": {"vue": "/js/vue-frame.js"}}"#,
+            &document_url,
+        ));
+        session.exit_frame();
+
+        // Parent document gets its own import map back once the frame is processed
+        assert_eq!(
+            session.import_map.as_ref().unwrap().imports[0].1.as_str(),
+            "https://example.com/js/vue.js"
+        );
+        session.exit_frame();
+    }
+}
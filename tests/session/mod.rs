@@ -1 +1,3 @@
+mod can_enter_frame;
+mod enter_frame;
 mod retrieve_asset;
@@ -27,6 +27,7 @@ mod passing {
             .retrieve_asset(
                 &Url::parse("data:text/html;base64,c291cmNl").unwrap(),
                 &Url::parse("data:text/html;base64,dGFyZ2V0").unwrap(),
+                None,
             )
             .unwrap();
         assert_eq!(&media_type, "text/html");
@@ -66,6 +67,7 @@ mod passing {
                     cwd = cwd.to_str().unwrap()
                 ))
                 .unwrap(),
+                None,
             )
             .unwrap();
         assert_eq!(&media_type, "text/javascript");
@@ -98,7 +100,9 @@ mod passing {
 mod failing {
     use reqwest::Url;
 
+    use monolith::cache::Cache;
     use monolith::core::MonolithOptions;
+    use monolith::filters::{FilterList, ResourceType};
     use monolith::session::Session;
 
     #[test]
@@ -112,6 +116,7 @@ mod failing {
         match session.retrieve_asset(
             &Url::parse("data:text/html;base64,SoUrCe").unwrap(),
             &Url::parse("file:///etc/passwd").unwrap(),
+            None,
         ) {
             Ok((..)) => {
                 assert!(false);
@@ -133,6 +138,7 @@ mod failing {
         match session.retrieve_asset(
             &Url::parse("https://kernel.org/").unwrap(),
             &Url::parse("file:///etc/passwd").unwrap(),
+            None,
         ) {
             Ok((..)) => {
                 assert!(false);
@@ -142,4 +148,62 @@ mod failing {
             }
         }
     }
+
+    #[test]
+    fn read_cached_asset_blocked_by_filter_list() {
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+
+        let mut cache: Cache = Cache::new(0, None);
+        cache.set(
+            "https://ads.example.com/banner.png",
+            &b"PNG".to_vec(),
+            "image/png".to_string(),
+            "".to_string(),
+        );
+        let mut filter_list = FilterList::default();
+        filter_list.add_filters("||ads.example.com^");
+
+        let mut session: Session = Session::new(Some(cache), None, options);
+        session.filter_list = Some(filter_list);
+
+        // Assets which are already cached should not bypass filter lists
+        assert!(session
+            .retrieve_asset(
+                &Url::parse("https://site.example.com/").unwrap(),
+                &Url::parse("https://ads.example.com/banner.png").unwrap(),
+                Some(ResourceType::Image),
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn read_third_party_asset_referenced_by_stylesheet() {
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+
+        let mut cache: Cache = Cache::new(0, None);
+        cache.set(
+            "https://cdn.example.net/font.woff2",
+            &b"wOF2".to_vec(),
+            "font/woff2".to_string(),
+            "".to_string(),
+        );
+        let mut filter_list = FilterList::default();
+        filter_list.add_filters("||cdn.example.net^$third-party");
+
+        let mut session: Session = Session::new(Some(cache), None, options);
+        session.filter_list = Some(filter_list);
+
+        // Being third-party is relative to the document, not to the stylesheet referencing the asset
+        session.enter_frame(&Url::parse("https://site.example.com/").unwrap());
+        assert!(session
+            .retrieve_asset(
+                &Url::parse("https://cdn.example.net/style.css").unwrap(),
+                &Url::parse("https://cdn.example.net/font.woff2").unwrap(),
+                Some(ResourceType::Font),
+            )
+            .is_err());
+        session.exit_frame();
+    }
 }
@@ -0,0 +1,96 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use std::fs;
+    use std::path::Path;
+
+    use monolith::core::MonolithOptions;
+    use monolith::session::Session;
+    use monolith::svg;
+    use monolith::url::Url;
+
+    #[test]
+    fn external_resources() {
+        let path: &Path = Path::new("tests/_data_/svg_external/sprite.svg");
+        let svg_url: Url = Url::from_file_path(fs::canonicalize(path).unwrap()).unwrap();
+
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+        let mut session: Session = Session::new(None, None, options);
+
+        let result: String = String::from_utf8(svg::embed_svg(
+            &mut session,
+            &svg_url,
+            &fs::read(path).unwrap(),
+        ))
+        .unwrap();
+
+        // Stylesheet processing instructions
+        assert!(result.contains(
+            "<?xml-stylesheet type=\"text/css\" href=\"data:text/css;base64,cmVjdCB7IHN0cm9rZTogYmxhY2s7IH0K\"?>"
+        ));
+        // STYLE elements
+        assert!(result.contains(
+            "<style><![CDATA[\n    @import \"data:text/css;base64,cmVjdCB7IHN0cm9rZTogYmxhY2s7IH0K\";\n  ]]></style>"
+        ));
+        // IMAGE elements
+        assert!(result.contains("<image xlink:href=\"data:image/png;base64,iVBORw0KGgo"));
+        // Local references and links stay untouched
+        assert!(result.contains("<rect style=\"fill: url(#gradient)\" width=\"8\" height=\"8\"/>"));
+        assert!(result.contains("<a href=\"https://example.com/\">link</a>"));
+    }
+
+    #[test]
+    fn escapes_attribute_values() {
+        let svg_url: Url = Url::parse("https://example.com/icon.svg").unwrap();
+
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+        let mut session: Session = Session::new(None, None, options);
+
+        assert_eq!(
+            String::from_utf8(svg::embed_svg(
+                &mut session,
+                &svg_url,
+                b"<svg><text style=\"font-family: &quot;Open Sans&quot;\">A</text></svg>",
+            ))
+            .unwrap(),
+            "<svg><text style=\"font-family: &quot;Open Sans&quot;\">A</text></svg>"
+        );
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::core::MonolithOptions;
+    use monolith::session::Session;
+    use monolith::svg;
+    use monolith::url::Url;
+
+    #[test]
+    fn non_utf8_documents() {
+        let svg_url: Url = Url::parse("https://example.com/icon.svg").unwrap();
+
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+        let mut session: Session = Session::new(None, None, options);
+
+        assert_eq!(
+            svg::embed_svg(&mut session, &svg_url, b"<svg>\xFF\xFE</svg>"),
+            b"<svg>\xFF\xFE</svg>"
+        );
+    }
+}
@@ -0,0 +1 @@
+mod embed_svg;
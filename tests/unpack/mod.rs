@@ -0,0 +1,4 @@
+mod unpack_css;
+mod unpack_data_url;
+mod unpack_monolithic_document;
+mod unpack_srcset;
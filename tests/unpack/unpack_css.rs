@@ -0,0 +1,73 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::unpack;
+
+    #[test]
+    fn unquoted_url() {
+        let mut assets = vec![];
+        let css = unpack::unpack_css(
+            "div { background: url(data:image/png;base64,iVBORw0KGgo=) no-repeat; }",
+            &mut assets,
+        );
+
+        assert_eq!(assets.len(), 1);
+        assert!(assets[0].file_name.ends_with(".png"));
+        assert_eq!(
+            css,
+            format!(
+                "div {{ background: url(\"{}\") no-repeat; }}",
+                assets[0].file_name
+            )
+        );
+    }
+
+    #[test]
+    fn quoted_url_within_nested_blocks() {
+        let mut assets = vec![];
+        let css = unpack::unpack_css(
+            "@media screen { div { background: image-set(url('data:image/gif;base64,R0lGODlh') 1x); } }",
+            &mut assets,
+        );
+
+        assert_eq!(
+            css,
+            format!(
+                "@media screen {{ div {{ background: image-set(url(\"{}\") 1x); }} }}",
+                assets[0].file_name
+            )
+        );
+    }
+
+    #[test]
+    fn same_asset_twice() {
+        let mut assets = vec![];
+        unpack::unpack_css(
+            "a { background: url(data:image/png;base64,iVBORw0KGgo=) } b { background: url(data:image/png;base64,iVBORw0KGgo=) }",
+            &mut assets,
+        );
+
+        assert_eq!(assets.len(), 1);
+    }
+
+    #[test]
+    fn remote_url() {
+        let mut assets = vec![];
+        let css = unpack::unpack_css(
+            "div { background: url(https://example.com/image.png) }",
+            &mut assets,
+        );
+
+        assert!(assets.is_empty());
+        assert_eq!(
+            css,
+            "div { background: url(https://example.com/image.png) }"
+        );
+    }
+}
@@ -0,0 +1,91 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::unpack;
+
+    #[test]
+    fn image() {
+        let mut assets = vec![];
+        let file_name =
+            unpack::unpack_data_url("data:image/png;base64,iVBORw0KGgo=", &mut assets).unwrap();
+
+        assert!(file_name.ends_with(".png"));
+        assert_eq!(assets[0].file_name, file_name);
+        assert_eq!(assets[0].data, b"\x89PNG\r\n\x1a\n");
+    }
+
+    #[test]
+    fn media_type_detected_from_data() {
+        let mut assets = vec![];
+        let file_name =
+            unpack::unpack_data_url("data:;base64,R0lGODlhAQABAAAAACw=", &mut assets).unwrap();
+
+        assert!(file_name.ends_with(".gif"));
+    }
+
+    #[test]
+    fn fragment() {
+        let mut assets = vec![];
+        let file_name = unpack::unpack_data_url(
+            "data:image/svg+xml,%3Csvg%3E%3Csymbol id=%22i%22/%3E%3C/svg%3E#i",
+            &mut assets,
+        )
+        .unwrap();
+
+        assert!(file_name.ends_with(".svg#i"));
+        assert_eq!(assets[0].data, b"<svg><symbol id=\"i\"/></svg>");
+    }
+
+    #[test]
+    fn stylesheet_with_nested_assets() {
+        let mut assets = vec![];
+        let file_name = unpack::unpack_data_url(
+            "data:text/css,div%7Bbackground:url(data:image/png;base64,iVBORw0KGgo=)%7D",
+            &mut assets,
+        )
+        .unwrap();
+
+        assert_eq!(assets.len(), 2);
+        assert!(file_name.ends_with(".css"));
+        assert_eq!(
+            String::from_utf8_lossy(&assets[1].data),
+            format!("div{{background:url(\"{}\")}}", assets[0].file_name)
+        );
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::unpack;
+
+    #[test]
+    fn remote_url() {
+        let mut assets = vec![];
+
+        assert_eq!(
+            unpack::unpack_data_url("https://example.com/image.png", &mut assets),
+            None
+        );
+        assert!(assets.is_empty());
+    }
+
+    #[test]
+    fn not_a_url() {
+        let mut assets = vec![];
+
+        assert_eq!(unpack::unpack_data_url("data", &mut assets), None);
+    }
+}
@@ -0,0 +1,75 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::unpack;
+
+    #[test]
+    fn attributes_and_styles() {
+        let html = "<html><head>\
+<link rel=\"icon\" href=\"data:image/png;base64,iVBORw0KGgo=\">\
+<style>body{background:url(data:image/gif;base64,R0lGODlh)}</style>\
+</head><body>\
+<img src=\"data:image/png;base64,iVBORw0KGgo=\" srcset=\"data:image/gif;base64,R0lGODlh 2x\">\
+<div style=\"background-image:url(data:image/gif;base64,R0lGODlh)\"></div>\
+<a href=\"https://example.com/\">link</a>\
+</body></html>";
+        let (document, assets) = unpack::unpack_monolithic_document(html.as_bytes());
+        let png: &str = &assets[0].file_name;
+        let gif: &str = &assets[1].file_name;
+
+        assert_eq!(assets.len(), 2);
+        assert_eq!(
+            String::from_utf8_lossy(&document),
+            format!(
+                "<html><head>\
+<link rel=\"icon\" href=\"{png}\">\
+<style>body{{background:url(\"{gif}\")}}</style>\
+</head><body>\
+<img src=\"{png}\" srcset=\"{gif} 2x\">\
+<div style=\"background-image:url(&quot;{gif}&quot;)\"></div>\
+<a href=\"https://example.com/\">link</a>\
+</body></html>"
+            )
+        );
+    }
+
+    #[test]
+    fn frame() {
+        let html = "<html><head></head><body>\
+<iframe src=\"data:text/html;charset=utf-8,%3Cimg%20src%3D%22data%3Aimage%2Fpng%3Bbase64%2CiVBORw0KGgo%3D%22%3E\"></iframe>\
+</body></html>";
+        let (document, assets) = unpack::unpack_monolithic_document(html.as_bytes());
+
+        assert_eq!(assets.len(), 2);
+        assert!(assets[1].file_name.ends_with(".html"));
+        assert_eq!(
+            String::from_utf8_lossy(&assets[1].data),
+            format!(
+                "<html><head></head><body><img src=\"{}\"></body></html>",
+                assets[0].file_name
+            )
+        );
+        assert_eq!(
+            String::from_utf8_lossy(&document),
+            format!(
+                "<html><head></head><body><iframe src=\"{}\"></iframe></body></html>",
+                assets[1].file_name
+            )
+        );
+    }
+
+    #[test]
+    fn no_assets() {
+        let html = "<html><head></head><body><p>data: none</p></body></html>";
+        let (document, assets) = unpack::unpack_monolithic_document(html.as_bytes());
+
+        assert!(assets.is_empty());
+        assert_eq!(String::from_utf8_lossy(&document), html);
+    }
+}
@@ -0,0 +1,36 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::unpack;
+
+    #[test]
+    fn mixed_candidates() {
+        let mut assets = vec![];
+        let srcset = unpack::unpack_srcset(
+            "data:image/png;base64,iVBORw0KGgo= 1x, https://example.com/image@2x.png 2x",
+            &mut assets,
+        );
+
+        assert_eq!(
+            srcset,
+            format!(
+                "{} 1x, https://example.com/image@2x.png 2x",
+                assets[0].file_name
+            )
+        );
+    }
+
+    #[test]
+    fn no_descriptor() {
+        let mut assets = vec![];
+        let srcset = unpack::unpack_srcset("data:image/png;base64,iVBORw0KGgo=", &mut assets);
+
+        assert_eq!(srcset, assets[0].file_name);
+    }
+}
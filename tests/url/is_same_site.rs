@@ -0,0 +1,63 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::url::{self, Url};
+
+    #[test]
+    fn same_host() {
+        assert!(url::is_same_site(
+            &Url::parse("https://example.com/page").unwrap(),
+            &Url::parse("https://example.com/style.css").unwrap()
+        ));
+    }
+
+    #[test]
+    fn subdomain() {
+        assert!(url::is_same_site(
+            &Url::parse("https://www.example.com/").unwrap(),
+            &Url::parse("https://cdn.example.com/app.js").unwrap()
+        ));
+    }
+
+    #[test]
+    fn local_document() {
+        assert!(url::is_same_site(
+            &Url::parse("file:///home/user/index.html").unwrap(),
+            &Url::parse("https://example.com/app.js").unwrap()
+        ));
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::url::{self, Url};
+
+    #[test]
+    fn different_domains() {
+        assert!(!url::is_same_site(
+            &Url::parse("https://example.com/").unwrap(),
+            &Url::parse("https://tracker.example.org/pixel.gif").unwrap()
+        ));
+    }
+
+    #[test]
+    fn different_ip_addresses() {
+        assert!(!url::is_same_site(
+            &Url::parse("http://10.0.0.1/").unwrap(),
+            &Url::parse("http://20.0.0.1/").unwrap()
+        ));
+    }
+}
@@ -0,0 +1,54 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use reqwest::Url;
+
+    use monolith::url;
+
+    #[test]
+    fn tracker_hosts() {
+        for tracker_url in [
+            "https://www.google-analytics.com/analytics.js",
+            "https://www.googletagmanager.com/gtag/js?id=G-1",
+            "https://static.hotjar.com/c/hotjar-1.js",
+            "https://www.facebook.com/tr?id=1&ev=PageView",
+            "https://t.co/i/adsct?txn_id=1",
+        ] {
+            assert!(url::is_tracker_url(&Url::parse(tracker_url).unwrap()));
+        }
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use reqwest::Url;
+
+    use monolith::url;
+
+    #[test]
+    fn regular_hosts() {
+        for url in [
+            "https://www.facebook.com/travel",
+            "https://www.facebook.com/",
+            "https://t.co/abc",
+            "https://site.com/google-analytics.com/analytics.js",
+            "https://not-google-analytics.com/",
+            "data:text/plain,google-analytics.com",
+        ] {
+            assert!(!url::is_tracker_url(&Url::parse(url).unwrap()));
+        }
+    }
+}
@@ -2,6 +2,9 @@ mod clean_url;
 mod create_data_url;
 mod domain_is_within_domain;
 mod get_referer_url;
+mod is_same_site;
+mod is_tracker_url;
 mod is_url_and_has_protocol;
 mod parse_data_url;
+mod remove_tracking_params;
 mod resolve_url;
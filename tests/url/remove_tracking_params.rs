@@ -0,0 +1,58 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use reqwest::Url;
+
+    use monolith::url;
+
+    #[test]
+    fn removes_tracking_params() {
+        assert_eq!(
+            url::remove_tracking_params(
+                Url::parse(
+                    "https://site.com/post?id=5&utm_source=news&UTM_Medium=email&fbclid=abc"
+                )
+                .unwrap()
+            )
+            .as_str(),
+            "https://site.com/post?id=5"
+        );
+    }
+
+    #[test]
+    fn removes_query_entirely() {
+        assert_eq!(
+            url::remove_tracking_params(
+                Url::parse("https://site.com/?gclid=123&msclkid=456#top").unwrap()
+            )
+            .as_str(),
+            "https://site.com/#top"
+        );
+    }
+
+    #[test]
+    fn keeps_other_params_as_is() {
+        assert_eq!(
+            url::remove_tracking_params(
+                Url::parse("https://site.com/search?q=a+b%20c&flag&utm_campaign=x&page=2").unwrap()
+            )
+            .as_str(),
+            "https://site.com/search?q=a+b%20c&flag&page=2"
+        );
+    }
+
+    #[test]
+    fn no_query() {
+        assert_eq!(
+            url::remove_tracking_params(Url::parse("https://site.com/utm_source").unwrap())
+                .as_str(),
+            "https://site.com/utm_source"
+        );
+    }
+}
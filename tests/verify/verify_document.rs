@@ -0,0 +1,204 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::verify::{self, DocumentReference};
+
+    #[test]
+    fn remote_resources() {
+        let report = verify::verify_document(
+            b"<html><head>\
+<link rel=\"stylesheet\" href=\"https://example.com/style.css\">\
+<style>@import 'https://example.com/import.css'; body { background: url(//example.com/bg.png) }</style>\
+</head><body>\
+<img srcset=\"https://example.com/image@2x.png 2x\">\
+<div style=\"background-image: image-set('https://example.com/image.png' 1x)\"></div>\
+</body></html>",
+        );
+
+        assert_eq!(
+            report.network_requests,
+            vec![
+                DocumentReference {
+                    location: "link[href]".to_string(),
+                    url: "https://example.com/style.css".to_string(),
+                },
+                DocumentReference {
+                    location: "style".to_string(),
+                    url: "https://example.com/import.css".to_string(),
+                },
+                DocumentReference {
+                    location: "style".to_string(),
+                    url: "https://example.com/bg.png".to_string(),
+                },
+                DocumentReference {
+                    location: "img[srcset]".to_string(),
+                    url: "https://example.com/image@2x.png".to_string(),
+                },
+                DocumentReference {
+                    location: "div[style]".to_string(),
+                    url: "https://example.com/image.png".to_string(),
+                },
+            ]
+        );
+        assert!(report.makes_network_requests());
+    }
+
+    #[test]
+    fn links_are_not_requests() {
+        let report = verify::verify_document(
+            b"<html><head><link rel=\"canonical\" href=\"https://example.com/\"></head>\
+<body><a href=\"https://example.com/page\" ping=\"https://example.com/ping\">link</a>\
+<form action=\"https://example.com/search\"></form></body></html>",
+        );
+
+        assert_eq!(report.remote_references.len(), 4);
+        assert!(report.network_requests.is_empty());
+        assert!(!report.makes_network_requests());
+    }
+
+    #[test]
+    fn relative_urls_resolved_against_remote_base() {
+        let report = verify::verify_document(
+            b"<html><head><base href=\"https://example.com/\"></head>\
+<body><img src=\"image.png\"><svg><use href=\"#icon\"></use></svg></body></html>",
+        );
+
+        assert_eq!(
+            report.network_requests,
+            vec![DocumentReference {
+                location: "img[src]".to_string(),
+                url: "https://example.com/image.png".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn references_within_data_urls() {
+        let report = verify::verify_document(
+            b"<html><body>\
+<iframe src=\"data:text/html,%3Cimg%20src%3D%22https%3A%2F%2Fexample.com%2Fa.png%22%3E\"></iframe>\
+<iframe srcdoc=\"<img src=https://example.com/b.png>\"></iframe>\
+</body></html>",
+        );
+
+        assert_eq!(
+            report.network_requests,
+            vec![
+                DocumentReference {
+                    location: "iframe[src] > img[src]".to_string(),
+                    url: "https://example.com/a.png".to_string(),
+                },
+                DocumentReference {
+                    location: "iframe[srcdoc] > img[src]".to_string(),
+                    url: "https://example.com/b.png".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn meta_refresh() {
+        let report = verify::verify_document(
+            b"<html><head><meta http-equiv=\"refresh\" content=\"0; url='https://example.com/'\"></head></html>",
+        );
+
+        assert_eq!(report.network_requests.len(), 1);
+        assert_eq!(report.network_requests[0].url, "https://example.com/");
+    }
+
+    #[test]
+    fn isolated_document() {
+        let report = verify::verify_document(
+            b"<html><head>\
+<meta http-equiv=\"Content-Security-Policy\" content=\"default-src 'unsafe-eval' 'unsafe-inline' data:;\">\
+</head><body><img src=\"https://example.com/image.png\"></body></html>",
+        );
+
+        assert!(report.is_isolated());
+        assert_eq!(report.network_requests.len(), 1);
+        assert!(!report.makes_network_requests());
+    }
+
+    #[test]
+    fn unpadded_base64_data_url() {
+        let report = verify::verify_document(
+            b"<html><body><img src=\"data:image/gif;base64,R0lGODlhAQABAAAAACw\"></body></html>",
+        );
+
+        assert!(report.broken_data_urls.is_empty());
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::verify;
+
+    #[test]
+    fn meta_refresh_within_isolated_document() {
+        let report = verify::verify_document(
+            b"<html><head>\
+<meta http-equiv=\"Content-Security-Policy\" content=\"default-src 'unsafe-eval' 'unsafe-inline' data:;\">\
+<meta http-equiv=\"refresh\" content=\"0; url=https://example.com/\">\
+</head></html>",
+        );
+
+        assert!(report.is_isolated());
+        assert!(report.makes_network_requests());
+        assert_eq!(report.unblocked_requests[0].url, "https://example.com/");
+    }
+
+    #[test]
+    fn request_before_isolation_csp() {
+        let report = verify::verify_document(
+            b"<html><head>\
+<link rel=\"stylesheet\" href=\"https://example.com/style.css\">\
+<meta http-equiv=\"Content-Security-Policy\" content=\"default-src 'unsafe-eval' 'unsafe-inline' data:;\">\
+</head><body><img src=\"https://example.com/image.png\"></body></html>",
+        );
+
+        assert!(report.is_isolated());
+        assert_eq!(report.network_requests.len(), 2);
+        assert_eq!(report.unblocked_requests.len(), 1);
+        assert_eq!(
+            report.unblocked_requests[0].url,
+            "https://example.com/style.css"
+        );
+        assert!(report.makes_network_requests());
+    }
+
+    #[test]
+    fn broken_data_urls() {
+        let report = verify::verify_document(
+            b"<html><body><img src=\"data:image/png;base64,!!!\"><img src=\"data:image/png\"></body></html>",
+        );
+
+        assert_eq!(report.broken_data_urls.len(), 2);
+        assert_eq!(report.broken_data_urls[0].location, "img[src]");
+    }
+
+    #[test]
+    fn csp_without_isolation() {
+        let report = verify::verify_document(
+            b"<html><head>\
+<meta http-equiv=\"Content-Security-Policy\" content=\"script-src 'none';\">\
+</head><body><img src=\"https://example.com/image.png\"></body></html>",
+        );
+
+        assert_eq!(report.csp, Some("script-src 'none';".to_string()));
+        assert!(!report.is_isolated());
+        assert!(report.makes_network_requests());
+    }
+}